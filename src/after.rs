@@ -0,0 +1,178 @@
+// File: src\after.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: `[after=...]` annotations - explicit creation order between entries
+// License: MIT
+
+use std::collections::HashMap;
+
+use crate::PlannedEntry;
+
+// `[after=Cargo.toml]`, `[after=Cargo.toml,build.rs]`; a line may carry several
+fn targets(entry: &PlannedEntry) -> impl Iterator<Item = &str> {
+    entry
+        .annotations
+        .iter()
+        .filter(|a| a.key == "after")
+        .filter_map(|a| a.value.as_deref())
+        .flat_map(|value| value.split(','))
+        .map(|target| target.trim().trim_end_matches('/'))
+        .filter(|target| !target.is_empty())
+}
+
+fn parent_of(path: &str) -> Option<&str> {
+    path.rsplit_once('/').map(|(parent, _)| parent)
+}
+
+// A target names a sibling, a path from the top of the plan, or any single
+// entry whose path ends with it
+fn resolve(plan: &[PlannedEntry], index: &HashMap<&str, usize>, from: usize, target: &str) -> Result<usize, String> {
+    let entry = &plan[from];
+    let sibling = parent_of(&entry.path).map(|parent| format!("{}/{}", parent, target));
+    if let Some(&i) = sibling.as_deref().and_then(|path| index.get(path)).or_else(|| index.get(target)) {
+        return Ok(i);
+    }
+    let suffix = format!("/{}", target);
+    let matches: Vec<usize> = (0..plan.len()).filter(|&i| plan[i].path.ends_with(&suffix)).collect();
+    match matches.as_slice() {
+        [i] => Ok(*i),
+        [] => Err(t!("line {}: [after={}] matches no entry", entry.line + 1, target)),
+        _ => {
+            let paths: Vec<&str> = matches.iter().map(|&i| plan[i].path.as_str()).collect();
+            Err(t!("line {}: [after={}] is ambiguous: {}", entry.line + 1, target, paths.join(", ")))
+        }
+    }
+}
+
+// Lines of a cycle among the entries not yet placed, starting from `start`
+fn cycle(plan: &[PlannedEntry], deps: &[Vec<usize>], placed: &[bool], start: usize) -> String {
+    let mut seen: Vec<usize> = Vec::new();
+    let mut current = start;
+    while !seen.contains(&current) {
+        seen.push(current);
+        current = deps[current].iter().copied().find(|&d| !placed[d]).unwrap_or(current);
+    }
+    let from = seen.iter().position(|&i| i == current).unwrap_or(0);
+    let mut steps: Vec<String> = seen[from..]
+        .iter()
+        .map(|&i| t!("line {} '{}'", plan[i].line + 1, plan[i].path))
+        .collect();
+    steps.push(steps[0].clone());
+    steps.join(" → ")
+}
+
+// Move entries behind what their `[after=...]` names, keeping the current
+// order wherever no edge says otherwise. Folders still come before their
+// contents; a cycle is an error naming the lines involved.
+pub fn apply(plan: &mut Vec<PlannedEntry>) -> Result<(), Box<dyn std::error::Error>> {
+    if !plan.iter().any(|entry| targets(entry).next().is_some()) {
+        return Ok(());
+    }
+    let mut index: HashMap<&str, usize> = HashMap::new();
+    for (i, entry) in plan.iter().enumerate() {
+        index.entry(entry.path.as_str()).or_insert(i);
+    }
+
+    let mut deps: Vec<Vec<usize>> = vec![Vec::new(); plan.len()];
+    for i in 0..plan.len() {
+        if let Some(&parent) = parent_of(&plan[i].path).and_then(|parent| index.get(parent)) {
+            deps[i].push(parent);
+        }
+        for target in targets(&plan[i]) {
+            let dep = resolve(plan, &index, i, target)?;
+            if dep == i {
+                return Err(t!("line {}: [after={}] names the entry itself", plan[i].line + 1, target).into());
+            }
+            deps[i].push(dep);
+        }
+    }
+
+    // Always take the earliest entry whose dependencies are all placed
+    let mut placed = vec![false; plan.len()];
+    let mut order = Vec::with_capacity(plan.len());
+    while order.len() < plan.len() {
+        let next = (0..plan.len()).find(|&i| !placed[i] && deps[i].iter().all(|&d| placed[d]));
+        let Some(next) = next else {
+            let start = (0..plan.len()).find(|&i| !placed[i]).unwrap_or(0);
+            return Err(t!("[after=...] cycle: {}", cycle(plan, &deps, &placed, start)).into());
+        };
+        placed[next] = true;
+        order.push(next);
+    }
+
+    let mut slots: Vec<Option<PlannedEntry>> = plan.drain(..).map(Some).collect();
+    plan.extend(order.into_iter().filter_map(|i| slots[i].take()));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{plan_entries, resolve_lines, ParseOptions};
+
+    use super::*;
+
+    fn planned(text: &str) -> Vec<PlannedEntry> {
+        let lines: Vec<String> = text.lines().map(String::from).collect();
+        plan_entries(resolve_lines(&lines, &ParseOptions::default()), false)
+    }
+
+    fn ordered(text: &str) -> Result<Vec<String>, String> {
+        let mut plan = planned(text);
+        apply(&mut plan).map_err(|e| e.to_string())?;
+        Ok(plan.into_iter().map(|entry| entry.path).collect())
+    }
+
+    #[test]
+    fn entries_move_behind_what_they_name() {
+        let order = ordered("app/\n    build.rs [after=Cargo.toml]\n    Cargo.toml\n    src/\n").unwrap();
+        assert_eq!(order, ["app", "app/Cargo.toml", "app/build.rs", "app/src"]);
+    }
+
+    #[test]
+    fn plans_without_edges_keep_their_order() {
+        let text = "app/\n    z.txt\n    a.txt\n";
+        assert_eq!(ordered(text).unwrap(), ["app", "app/z.txt", "app/a.txt"]);
+    }
+
+    #[test]
+    fn folders_still_come_before_their_contents() {
+        let order = ordered("app/\n    src/ [after=z.txt]\n        main.rs\n    z.txt\n").unwrap();
+        assert_eq!(order, ["app", "app/z.txt", "app/src", "app/src/main.rs"]);
+    }
+
+    #[test]
+    fn targets_resolve_by_path_from_the_top_and_by_suffix() {
+        let text = "app/\n    src/\n        lib.rs [after=app/Cargo.toml]\n        main.rs [after=gen/schema.rs]\n    gen/\n        schema.rs\n    Cargo.toml\n";
+        let order = ordered(text).unwrap();
+        let at = |path: &str| order.iter().position(|p| p == path).unwrap();
+        assert!(at("app/Cargo.toml") < at("app/src/lib.rs"));
+        assert!(at("app/gen/schema.rs") < at("app/src/main.rs"));
+    }
+
+    #[test]
+    fn a_cycle_names_every_line_in_it() {
+        let error = ordered("app/\n    a.txt [after=c.txt]\n    b.txt [after=a.txt]\n    c.txt [after=b.txt]\n").unwrap_err();
+        assert!(error.starts_with("[after=...] cycle: "), "{}", error);
+        for step in ["line 2 'app/a.txt'", "line 3 'app/b.txt'", "line 4 'app/c.txt'"] {
+            assert!(error.contains(step), "{} missing from {}", step, error);
+        }
+        // The cycle is closed: it ends where it started
+        let steps: Vec<&str> = error["[after=...] cycle: ".len()..].split(" → ").collect();
+        assert_eq!(steps.first(), steps.last());
+        assert_eq!(steps.len(), 4);
+    }
+
+    #[test]
+    fn a_two_line_cycle_through_a_folder_is_found() {
+        let error = ordered("app/\n    src/ [after=main.rs]\n        main.rs\n").unwrap_err();
+        assert!(error.contains("line 2 'app/src'") && error.contains("line 3 'app/src/main.rs'"), "{}", error);
+    }
+
+    #[test]
+    fn bad_targets_are_reported_with_their_line() {
+        assert_eq!(ordered("app/\n    a.txt [after=a.txt]\n").unwrap_err(), "line 2: [after=a.txt] names the entry itself");
+        assert_eq!(ordered("app/\n    a.txt [after=nothing.txt]\n").unwrap_err(), "line 2: [after=nothing.txt] matches no entry");
+        let error = ordered("x/\n    a/\n        mod.rs\n    b/\n        mod.rs\n    lib.rs [after=mod.rs]\n").unwrap_err();
+        assert_eq!(error, "line 6: [after=mod.rs] is ambiguous: x/a/mod.rs, x/b/mod.rs");
+    }
+}
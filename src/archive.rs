@@ -0,0 +1,356 @@
+// File: src\archive.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: `--into-archive bundle.zip` - merge the tree into an existing zip; `--to-zip`/`--to-tar` - build a new archive
+// License: MIT
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs::{self, File},
+    io::{self, Cursor, Write},
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+use flate2::{write::GzEncoder, Compression};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::{
+    fetch, i18n, placeholder_body, protected_entries, shebang_for,
+    tarball::{self, Member},
+    EntryOutcome, NodeKind, OnConflict, Options, PlannedEntry, Summary,
+};
+
+// What the archive holds: file entries, and folders that are listed or
+// implied by an entry below them
+#[derive(Default)]
+struct Listing {
+    files: BTreeSet<String>,
+    dirs: BTreeSet<String>,
+}
+
+fn listing(archive: &ZipArchive<File>) -> Listing {
+    let mut listing = Listing::default();
+    for name in archive.file_names() {
+        let mut rest = name.trim_end_matches('/');
+        if name.ends_with('/') {
+            listing.dirs.insert(rest.to_string());
+        } else {
+            listing.files.insert(rest.to_string());
+        }
+        while let Some(slash) = rest.rfind('/') {
+            rest = &rest[..slash];
+            listing.dirs.insert(rest.to_string());
+        }
+    }
+    listing
+}
+
+// The first `name.bak.N` that is not taken in the archive
+fn backup_name(path: &str, listing: &Listing) -> String {
+    (1..)
+        .map(|n| format!("{}.bak.{}", path, n))
+        .find(|name| !listing.files.contains(name) && !listing.dirs.contains(name))
+        .unwrap_or_default()
+}
+
+// One entry to write into the new archive
+struct Added<'a> {
+    entry: &'a PlannedEntry,
+    bytes: Vec<u8>,
+}
+
+// Contents of a new file entry: downloaded, from the tree, or a shebang
+fn file_bytes(entry: &PlannedEntry, opts: &Options) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    if let Some(url) = entry.annotation_value("src") {
+        return fetch::download(url, entry.annotation_value("sha256"), opts.download);
+    }
+    let shebang = if entry.executable && opts.shebang { shebang_for(&entry.path) } else { None };
+    if let Some(body) = placeholder_body(entry, shebang, opts) {
+        return Ok(body.to_vec());
+    }
+    Ok(entry.contents.as_deref().or(shebang).unwrap_or("").as_bytes().to_vec())
+}
+
+fn failed(reason: String) -> EntryOutcome {
+    EntryOutcome::Failed { kind: "other".to_string(), reason, source: None }
+}
+
+// A folder where a file goes or the other way round; only a conflict with `--merge`
+fn in_the_way(found: NodeKind, opts: &Options) -> EntryOutcome {
+    let reason = t!("a {} is in the way", i18n::tr(found.label()));
+    if opts.merge {
+        EntryOutcome::Conflict { reason }
+    } else {
+        failed(reason)
+    }
+}
+
+// Copy the old entries (minus replaced ones, backups renamed) and then the
+// new ones into `temp`
+fn write(
+    archive: &mut ZipArchive<File>,
+    temp: &Path,
+    replaced: &BTreeSet<String>,
+    backups: &BTreeMap<String, String>,
+    added: &[Added],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut writer = ZipWriter::new(File::create(temp)?);
+    for i in 0..archive.len() {
+        let file = archive.by_index_raw(i)?;
+        let name = file.name().to_string();
+        match backups.get(&name) {
+            Some(backup) => writer.raw_copy_file_rename(file, backup)?,
+            None if replaced.contains(&name) => {}
+            None => writer.raw_copy_file(file)?,
+        }
+    }
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    for Added { entry, bytes } in added {
+        if entry.kind == NodeKind::Dir {
+            writer.add_directory(format!("{}/", entry.path), options.unix_permissions(entry.mode().unwrap_or(0o755)))?;
+        } else {
+            let mode = entry.mode().unwrap_or(if entry.executable { 0o755 } else { 0o644 });
+            writer.start_file(entry.path.as_str(), options.unix_permissions(mode))?;
+            writer.write_all(bytes)?;
+        }
+    }
+    writer.set_raw_comment(archive.comment().into());
+    writer.finish()?.sync_all()?;
+    Ok(())
+}
+
+// `--into-archive <zip>`: add the plan's missing entries to an existing zip.
+// Files already in it follow `--on-conflict` (and `-i`); folders are reused.
+// The archive is rewritten next to itself and then renamed over the old one,
+// so a failure leaves it as it was. Without `apply` nothing is written.
+pub fn merge(plan: &[PlannedEntry], zip_path: &str, opts: &Options, apply: bool) -> Result<Summary, Box<dyn std::error::Error>> {
+    let started = Instant::now();
+    if !Path::new(zip_path).is_file() {
+        return Err(t!("'{}' does not exist; --into-archive adds to an existing zip", zip_path).into());
+    }
+    let mut archive = ZipArchive::new(File::open(zip_path)?).map_err(|e| t!("'{}' is not a readable zip: {}", zip_path, e))?;
+    let listing = listing(&archive);
+    let protection = protected_entries(plan, opts);
+    if opts.on_conflict == OnConflict::Error {
+        let existing: Vec<&str> = plan
+            .iter()
+            .zip(&protection)
+            .filter(|(entry, &protected)| entry.kind == NodeKind::File && !protected && listing.files.contains(&entry.path))
+            .map(|(entry, _)| entry.path.as_str())
+            .collect();
+        if !existing.is_empty() {
+            return Err(t!("{} file(s) already exist (--on-conflict=error): {}", existing.len(), existing.join(", ")).into());
+        }
+    }
+
+    if !apply {
+        println!("🧪 {}\n", t!("Dry run: nothing is written"));
+    }
+    let mut summary = Summary { planned: plan.len(), merge: opts.merge, ..Summary::default() };
+    let (mut replaced, mut backups, mut added) = (BTreeSet::new(), BTreeMap::new(), Vec::new());
+    for (entry, &protected) in plan.iter().zip(&protection) {
+        let path = entry.path.as_str();
+        let in_archive = listing.files.contains(path) || listing.dirs.contains(path);
+        let outcome = match entry.kind {
+            NodeKind::Dir if listing.dirs.contains(path) => EntryOutcome::Existed,
+            NodeKind::Dir if listing.files.contains(path) => in_the_way(NodeKind::File, opts),
+            NodeKind::Dir => {
+                added.push(Added { entry, bytes: Vec::new() });
+                EntryOutcome::Created
+            }
+            NodeKind::File if listing.dirs.contains(path) => in_the_way(NodeKind::Dir, opts),
+            NodeKind::File if in_archive && protected => EntryOutcome::Protected,
+            NodeKind::File => {
+                let keep = in_archive
+                    && match opts.on_conflict {
+                        OnConflict::Skip => true,
+                        OnConflict::Overwrite => opts.interactive.as_ref().is_some_and(|prompt| !prompt.overwrite(path)),
+                        OnConflict::Error | OnConflict::Backup => false,
+                    };
+                let bytes = if keep { None } else { Some(file_bytes(entry, opts)) };
+                match bytes {
+                    None => EntryOutcome::Existed,
+                    Some(Err(e)) => failed(t!("line {}: {}", entry.line + 1, e)),
+                    Some(Ok(bytes)) => {
+                        summary.breakdown.add(path, entry.kind, bytes.len() as u64);
+                        added.push(Added { entry, bytes });
+                        match (in_archive, opts.on_conflict) {
+                            (false, _) => EntryOutcome::Created,
+                            (true, OnConflict::Backup) => {
+                                let backup = backup_name(path, &listing);
+                                backups.insert(path.to_string(), backup.clone());
+                                EntryOutcome::BackedUp { backup }
+                            }
+                            (true, _) => {
+                                replaced.insert(path.to_string());
+                                EntryOutcome::Replaced
+                            }
+                        }
+                    }
+                }
+            }
+            kind => EntryOutcome::Skipped { reason: t!("a {} cannot be stored in a zip archive", i18n::tr(kind.label())) },
+        };
+        if entry.kind == NodeKind::Dir && matches!(outcome, EntryOutcome::Created) {
+            summary.breakdown.add(path, entry.kind, 0);
+        }
+        summary.entries.push((path.to_string(), outcome));
+    }
+
+    if !apply {
+        // Nothing was written, so there is nothing to break down
+        summary.breakdown = Default::default();
+        return Ok(summary);
+    }
+    if !added.is_empty() {
+        let mut temp = PathBuf::from(zip_path).into_os_string();
+        temp.push(".mks-tmp");
+        let temp = PathBuf::from(temp);
+        // The rewritten zip takes the place of the original, mode included
+        let written = write(&mut archive, &temp, &replaced, &backups, &added)
+            .and_then(|_| Ok(fs::set_permissions(&temp, fs::metadata(zip_path)?.permissions())?));
+        if let Err(e) = written {
+            let _ = fs::remove_file(&temp);
+            return Err(t!("could not update '{}': {}", zip_path, e).into());
+        }
+        // Windows does not replace a file that is still open
+        drop(archive);
+        fs::rename(&temp, zip_path)?;
+    }
+    summary.elapsed = started.elapsed();
+    Ok(summary)
+}
+
+// What `--to-zip` / `--to-tar` write
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+// `--to-zip <file>` or `--to-tar <file>`; a `.tar` name is left uncompressed
+pub fn requested<'a>(to_zip: Option<&'a str>, to_tar: Option<&'a str>) -> Result<Option<(&'a str, Format)>, Box<dyn std::error::Error>> {
+    match (to_zip, to_tar) {
+        (Some(_), Some(_)) => Err(t!("--to-zip and --to-tar cannot be combined").into()),
+        (Some(file), None) => Ok(Some((file, Format::Zip))),
+        (None, Some(file)) if file.to_lowercase().ends_with(".tar") => Ok(Some((file, Format::Tar))),
+        (None, Some(file)) => Ok(Some((file, Format::TarGz))),
+        (None, None) => Ok(None),
+    }
+}
+
+// The project name an archive stands for: `app.tar.gz` -> `app`
+pub fn stem(file: &str) -> Option<String> {
+    let name = Path::new(file).file_name()?.to_string_lossy().into_owned();
+    let lower = name.to_lowercase();
+    let len = [".tar.gz", ".tgz", ".tar", ".zip"].iter().find(|ext| lower.ends_with(*ext)).map_or(0, |ext| ext.len());
+    name.get(..name.len() - len).filter(|stem| !stem.is_empty()).map(String::from)
+}
+
+fn zip_bytes(added: &[Added]) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    for Added { entry, bytes } in added {
+        match entry.kind {
+            NodeKind::Dir => writer.add_directory(format!("{}/", entry.path), options.unix_permissions(entry.mode().unwrap_or(0o755)))?,
+            NodeKind::Symlink => writer.add_symlink(entry.path.as_str(), String::from_utf8_lossy(bytes), options)?,
+            _ => {
+                let mode = entry.mode().unwrap_or(if entry.executable { 0o755 } else { 0o644 });
+                writer.start_file(entry.path.as_str(), options.unix_permissions(mode))?;
+                writer.write_all(bytes)?;
+            }
+        }
+    }
+    Ok(writer.finish()?.into_inner())
+}
+
+fn tar_bytes(added: &[Added], gzip: bool) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let mut writer = tarball::Writer::default();
+    for Added { entry, bytes } in added {
+        let (member, mode) = match entry.kind {
+            NodeKind::Dir => (Member::Dir, entry.mode().unwrap_or(0o755)),
+            NodeKind::Symlink => (Member::Link(std::str::from_utf8(bytes)?), 0o777),
+            _ => (Member::File(bytes), entry.mode().unwrap_or(if entry.executable { 0o755 } else { 0o644 })),
+        };
+        writer.add(&entry.path, member, mode)?;
+    }
+    let tar = writer.finish();
+    if !gzip {
+        return Ok(tar);
+    }
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&tar)?;
+    Ok(encoder.finish()?)
+}
+
+// `--to-zip` / `--to-tar`: the whole plan as a new archive, empty folders and
+// contents included, instead of entries on disk. `-` writes it to stdout; a
+// file is written next to itself and renamed into place, and never replaces
+// an existing one. Without `apply` nothing is written.
+pub fn create(plan: &[PlannedEntry], file: &str, format: Format, opts: &Options, apply: bool) -> Result<Summary, Box<dyn std::error::Error>> {
+    let started = Instant::now();
+    if file != "-" && Path::new(file).exists() {
+        return Err(t!("'{}' already exists; remove it first, or add to a zip with --into-archive", file).into());
+    }
+    if !apply {
+        status!("🧪 {}\n", t!("Dry run: nothing is written"));
+    }
+    let mut summary = Summary { planned: plan.len(), ..Summary::default() };
+    let mut added = Vec::new();
+    for entry in plan {
+        let outcome = match (entry.kind, entry.annotation_value("target")) {
+            (NodeKind::Dir, _) => {
+                added.push(Added { entry, bytes: Vec::new() });
+                EntryOutcome::Created
+            }
+            (NodeKind::Symlink, Some(target)) => {
+                added.push(Added { entry, bytes: target.as_bytes().to_vec() });
+                EntryOutcome::Created
+            }
+            (NodeKind::File, _) => match file_bytes(entry, opts) {
+                Ok(bytes) => {
+                    added.push(Added { entry, bytes });
+                    EntryOutcome::Created
+                }
+                Err(e) => failed(t!("line {}: {}", entry.line + 1, e)),
+            },
+            (kind, _) => EntryOutcome::Skipped { reason: t!("a {} cannot be stored in an archive", i18n::tr(kind.label())) },
+        };
+        summary.entries.push((entry.path.clone(), outcome));
+    }
+    if !apply {
+        return Ok(summary);
+    }
+    for Added { entry, bytes } in &added {
+        summary.breakdown.add(&entry.path, entry.kind, bytes.len() as u64);
+    }
+
+    let bytes = match format {
+        Format::Zip => zip_bytes(&added),
+        Format::Tar => tar_bytes(&added, false),
+        Format::TarGz => tar_bytes(&added, true),
+    }
+    .map_err(|e| t!("could not write '{}': {}", file, e))?;
+    if file == "-" {
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(&bytes)?;
+        stdout.flush()?;
+        return Ok(summary);
+    }
+    let mut temp = PathBuf::from(file).into_os_string();
+    temp.push(".mks-tmp");
+    let temp = PathBuf::from(temp);
+    let written = File::create(&temp).and_then(|mut out| {
+        out.write_all(&bytes)?;
+        out.sync_all()
+    });
+    if let Err(e) = written {
+        let _ = fs::remove_file(&temp);
+        return Err(t!("could not write '{}': {}", file, e).into());
+    }
+    fs::rename(&temp, file)?;
+    summary.elapsed = started.elapsed();
+    Ok(summary)
+}
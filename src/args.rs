@@ -0,0 +1,524 @@
+// File: src\args.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: The command line: subcommands and their flags (clap)
+// License: MIT
+
+use clap::{error::ErrorKind, parser::ValueSource, Args, CommandFactory, FromArgMatches, Parser, Subcommand};
+
+#[derive(Parser)]
+#[command(
+    name = "mks",
+    about = "Create folders and files from a tree pasted from the clipboard or read from a file",
+    override_usage = "mks [OPTIONS] [FILE]...\n       mks <COMMAND> [OPTIONS]",
+    disable_version_flag = true
+)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    // `mks tree.txt` is `mks create tree.txt`
+    #[command(flatten)]
+    pub create: CreateArgs,
+    #[command(flatten)]
+    pub global: GlobalArgs,
+    /// Print the version
+    #[arg(short = 'V', long)]
+    pub version: bool,
+}
+
+// `mks [flags] <file>` or `mks <command> [flags]`. Flags given before a
+// subcommand would be silently ignored, so only --lang and --theme may go there.
+pub fn parse() -> Result<Cli, clap::Error> {
+    let mut command = Cli::command();
+    let matches = command.try_get_matches_from_mut(std::env::args_os())?;
+    if let Some((name, _)) = matches.subcommand() {
+        let misplaced = command
+            .get_arguments()
+            .filter(|arg| !matches!(arg.get_id().as_str(), "lang" | "theme"))
+            .find(|arg| matches.value_source(arg.get_id().as_str()) == Some(ValueSource::CommandLine));
+        if let Some(arg) = misplaced {
+            let shown = match (arg.get_long(), arg.get_short()) {
+                (Some(long), _) => format!("--{}", long),
+                (None, Some(short)) => format!("-{}", short),
+                (None, None) => arg.get_id().to_string(),
+            };
+            let message = format!("'{}' must come after the subcommand: mks {} {} ...", shown, name, shown);
+            return Err(command.error(ErrorKind::ArgumentConflict, message));
+        }
+    }
+    Cli::from_arg_matches(&matches)
+}
+
+// Accepted before or after the subcommand
+#[derive(Args)]
+pub struct GlobalArgs {
+    /// Language for messages: en or id (defaults to LC_ALL / LC_MESSAGES / LANG)
+    #[arg(long, global = true, value_name = "CODE")]
+    pub lang: Option<String>,
+    /// Icon theme: plain, emoji or nerd (overrides the config)
+    #[arg(long, global = true, value_name = "THEME")]
+    pub theme: Option<String>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Create the tree (the default when no subcommand is given)
+    Create(CreateArgs),
+    /// Print the plan as JSON instead of creating it
+    Plan(CreateArgs),
+    /// Create a plan read from a JSON plan file
+    Apply(CreateArgs),
+    /// Print an existing directory as a tree
+    Dump(DumpArgs),
+    /// Check the clipboard, terminal, filesystem and config
+    Doctor,
+    /// Print the JSON Schema of the plan, report or result format
+    Schema {
+        #[arg(value_name = "plan|report|result")]
+        name: Option<String>,
+    },
+    /// Report problems in a tree file, or in a preset and its placeholders
+    Check(CheckArgs),
+    /// Remove what earlier runs created in a directory
+    Undo {
+        #[arg(default_value = ".")]
+        dir: String,
+        /// List what would be removed
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Compare disk usage with the sizes in a tree
+    Stats(StatsArgs),
+    /// List, or remove, folders that hold nothing but empty folders
+    PruneEmpty(PruneArgs),
+    /// Remove the `.mks/` state directory
+    Clean {
+        #[arg(default_value = ".")]
+        dir: String,
+    },
+    /// Print every parse decision as JSON lines
+    Explain(ExplainArgs),
+    /// Show (or create) the bundled example trees
+    Demo(DemoArgs),
+    /// Re-apply a tree file every time it is saved
+    Watch(WatchArgs),
+    /// Recreate whatever gets deleted from a directory
+    WatchDir(WatchDirArgs),
+    /// Create the tree on the clipboard, or with --watch every tree that gets copied
+    Clipboard(Box<ClipboardArgs>),
+    /// Save, apply, list or delete named templates
+    Template {
+        #[command(subcommand)]
+        action: TemplateAction,
+    },
+    /// Check or print the configuration files
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ConfigAction {
+    /// Validate each config file on its own
+    Check,
+    /// Print the config files as written
+    Show {
+        /// Print the merged settings every run uses
+        #[arg(long)]
+        effective: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TemplateAction {
+    /// Store a tree file under a name
+    Save(TemplateSaveArgs),
+    /// Build a stored template (same as --preset NAME)
+    Apply(Box<TemplateApplyArgs>),
+    /// List the stored templates
+    List,
+    /// Remove a stored template
+    #[command(alias = "rm")]
+    Delete { name: String },
+}
+
+#[derive(Args)]
+pub struct TemplateApplyArgs {
+    pub name: String,
+    #[command(flatten)]
+    pub options: CreateOptions,
+}
+
+#[derive(Args)]
+pub struct ClipboardArgs {
+    /// Keep watching and offer each new tree that is copied
+    #[arg(long)]
+    pub watch: bool,
+    /// With --watch, create each tree without asking
+    #[arg(short = 'y', long)]
+    pub yes: bool,
+    /// With --watch, how often to look at the clipboard (default 1s)
+    #[arg(long, value_name = "DURATION")]
+    pub interval: Option<String>,
+    #[command(flatten)]
+    pub metrics: MetricsArgs,
+    #[command(flatten)]
+    pub options: CreateOptions,
+}
+
+#[derive(Args)]
+pub struct TemplateSaveArgs {
+    pub name: String,
+    /// Tree file (the clipboard with -c)
+    pub file: Option<String>,
+    /// Replace a template of the same name
+    #[arg(long)]
+    pub force: bool,
+    #[command(flatten)]
+    pub input: InputArgs,
+    #[command(flatten)]
+    pub parse: ParseArgs,
+}
+
+// How tree text is read
+#[derive(Args)]
+pub struct ParseArgs {
+    /// Kind for childless names with no '/' and no extension: dir or file
+    #[arg(long, value_name = "dir|file")]
+    pub assume: Option<String>,
+    /// Take names verbatim: no comments, emoji, suffixes or '&' splitting
+    #[arg(long)]
+    pub raw_names: bool,
+    /// Group inconsistent indent widths into levels
+    #[arg(long)]
+    pub infer_hierarchy: bool,
+    /// Treat a root file with indented children as a folder
+    #[arg(long = "promote-root-to-dir")]
+    pub promote_root: bool,
+    /// Treat any file with indented children as a folder
+    #[arg(long)]
+    pub auto_dir: bool,
+    /// Keep trailing comments as descriptions instead of dropping them
+    #[arg(long)]
+    pub keep_comments: bool,
+    /// What separates several names on one line (default '&'); a backslash before it keeps it in the name
+    #[arg(long, value_name = "SEP")]
+    pub multi_sep: Option<String>,
+    /// One name per line: never split a line into several names
+    #[arg(long, conflicts_with = "multi_sep")]
+    pub no_split: bool,
+    /// Stop on a path that is listed twice instead of warning
+    #[arg(long)]
+    pub strict: bool,
+    /// Rename invalid names ('a?b' -> 'a_b', 'con' -> 'con_') instead of skipping them
+    #[arg(long, conflicts_with = "raw_names")]
+    pub sanitize: bool,
+}
+
+// Prometheus counters for the long-running watch modes
+#[derive(Args)]
+pub struct MetricsArgs {
+    /// Serve Prometheus metrics at http://HOST:PORT/metrics
+    #[arg(long, value_name = "HOST:PORT")]
+    pub metrics_addr: Option<String>,
+    /// Keep Prometheus metrics in this file (node_exporter textfile collector)
+    #[arg(long, value_name = "FILE")]
+    pub metrics_file: Option<String>,
+}
+
+// Where tree text comes from when no file is given
+#[derive(Args)]
+pub struct InputArgs {
+    /// Read the tree from standard input (same as the file name `-`)
+    #[arg(long)]
+    pub stdin: bool,
+    /// Accept any TLS certificate when reading an https:// tree or download
+    #[arg(long)]
+    pub insecure: bool,
+    /// Read the tree from the clipboard
+    #[arg(short = 'c', long)]
+    pub clipboard: bool,
+    /// X11 selection to read: primary or clipboard
+    #[arg(long, value_name = "primary|clipboard")]
+    pub selection: Option<String>,
+    /// Read the n-th fenced code block of a Markdown input
+    #[arg(long, value_name = "N")]
+    pub fence_index: Option<String>,
+}
+
+// `{{name}}` placeholder values
+#[derive(Args)]
+pub struct VarArgs {
+    /// Value for a {{name}} placeholder (repeatable)
+    #[arg(long, value_name = "NAME=VALUE")]
+    pub var: Vec<String>,
+    /// TOML file of name = "value" pairs
+    #[arg(long, value_name = "FILE")]
+    pub vars: Option<String>,
+}
+
+#[derive(Args)]
+pub struct CreateArgs {
+    /// Tree files (the clipboard with -c); a plan file for `mks apply`
+    #[arg(value_name = "FILE")]
+    pub files: Vec<String>,
+    #[command(flatten)]
+    pub options: CreateOptions,
+}
+
+// Everything that shapes a run that creates entries
+#[derive(Args)]
+pub struct CreateOptions {
+    #[command(flatten)]
+    pub input: InputArgs,
+    #[command(flatten)]
+    pub parse: ParseArgs,
+    #[command(flatten)]
+    pub vars: VarArgs,
+    /// Print every parse decision and the directory stack
+    #[arg(long)]
+    pub debug: bool,
+    /// fsync every created file and directory
+    #[arg(long)]
+    pub durable: bool,
+    /// Seed executable files with an interpreter line
+    #[arg(long)]
+    pub shebang: bool,
+    /// Apply permission, owner and date columns of `tree -p -u -g -D` listings
+    #[arg(long)]
+    pub honor_attrs: bool,
+    /// Create level by level instead of in document order
+    #[arg(long)]
+    pub breadth_first: bool,
+    /// Create through directories that are symlinks (the default)
+    #[arg(long, overrides_with = "no_follow")]
+    pub follow: bool,
+    /// Fail instead of writing through a symlinked directory
+    #[arg(long, overrides_with = "follow")]
+    pub no_follow: bool,
+    /// Write `name -> target` entries as plain folders and file copies instead of links
+    #[arg(long)]
+    pub no_symlinks: bool,
+    /// Write JSON progress records to a file descriptor or named pipe
+    #[arg(long, value_name = "FD|PIPE")]
+    pub progress_fd: Option<String>,
+    /// Write contents verbatim instead of following .editorconfig
+    #[arg(long)]
+    pub no_editorconfig: bool,
+    /// Show resolved paths with '/' on every OS
+    #[arg(long)]
+    pub posix_paths: bool,
+    /// Remove what a partly failed run created, without asking
+    #[arg(long)]
+    pub rollback_on_error: bool,
+    /// Existing files: overwrite, skip, error or backup
+    #[arg(long, value_name = "POLICY")]
+    pub on_conflict: Option<String>,
+    /// Treat the tree as a delta: reuse folders, never touch existing files
+    #[arg(long)]
+    pub merge: bool,
+    /// Leave out entries matched by the target's .gitignore, as with .mksignore
+    #[arg(long)]
+    pub respect_gitignore: bool,
+    /// Give empty files of known binary types (png, pdf, zip, ...) a minimal valid body
+    #[arg(long)]
+    pub placeholders: bool,
+    /// Write a path → description table from the tree's comments into FILE
+    #[arg(long, value_name = "FILE")]
+    pub write_descriptions: Option<String>,
+    /// Start each file with its description as a comment in the file's own syntax
+    #[arg(long)]
+    pub description_headers: bool,
+    /// How to report the run: text, or one JSON document on stdout
+    #[arg(long, value_name = "text|json")]
+    pub output_format: Option<String>,
+    /// How to read the input: text, json or yaml
+    #[arg(long, value_name = "text|json|yaml")]
+    pub format: Option<String>,
+    /// Largest body a `name <= url` entry or a tree URL may download (512K, 10M)
+    #[arg(long, value_name = "SIZE")]
+    pub max_download: Option<String>,
+    /// Time limit for each download, in seconds
+    #[arg(long, value_name = "SECS")]
+    pub download_timeout: Option<String>,
+    /// Confirm the plan, and each file that would be overwritten
+    #[arg(short = 'i', long)]
+    pub interactive: bool,
+    /// Start no new operation after this long (90s, 5m, 1500ms)
+    #[arg(long, value_name = "DURATION")]
+    pub timeout: Option<String>,
+    /// Create the entries of one depth on N threads at a time
+    #[arg(short = 'j', long, value_name = "N")]
+    pub jobs: Option<String>,
+    /// Build from a preset instead of a tree file
+    #[arg(long, value_name = "NAME")]
+    pub preset: Option<String>,
+    /// Nest every given tree file below one new parent folder
+    #[arg(long, value_name = "DIR")]
+    pub under: Option<String>,
+    /// Create untagged entries plus those with one of these tags
+    #[arg(long, value_name = "A,B")]
+    pub tags: Vec<String>,
+    /// Leave out entries with one of these tags
+    #[arg(long, value_name = "A,B")]
+    pub skip_tags: Vec<String>,
+    /// Create only the subtree at this path (repeatable)
+    #[arg(long, value_name = "PATH")]
+    pub only: Vec<String>,
+    /// Create only the entries matching this glob, and the folders above them (repeatable)
+    #[arg(long, value_name = "GLOB")]
+    pub include: Vec<String>,
+    /// Leave out the entries matching this glob, and everything below them (repeatable)
+    #[arg(long, value_name = "GLOB")]
+    pub exclude: Vec<String>,
+    /// Create only shard K of N (`2/4`)
+    #[arg(long, value_name = "K/N")]
+    pub shard: Option<String>,
+    /// Create the structure inside this directory
+    #[arg(short = 'C', long = "dest", visible_short_alias = 'o', visible_alias = "output", value_name = "DIR")]
+    pub dest: Vec<String>,
+    /// Apply the same tree inside this directory (repeatable)
+    #[arg(long, value_name = "DIR")]
+    pub into: Vec<String>,
+    /// Apply the tree inside every directory matching the pattern
+    #[arg(long, value_name = "PATTERN")]
+    pub into_glob: Vec<String>,
+    /// Add the missing entries to an existing zip instead
+    #[arg(long, value_name = "ZIP")]
+    pub into_archive: Option<String>,
+    /// Make the created root a git repository with a first commit
+    #[arg(long)]
+    pub git_init: bool,
+    /// Message of the --git-init commit
+    #[arg(long, value_name = "MESSAGE")]
+    pub commit_message: Option<String>,
+    /// Write the tree into a new zip instead of the disk (- for stdout)
+    #[arg(long, value_name = "FILE")]
+    pub to_zip: Option<String>,
+    /// Write the tree into a new .tar.gz (or .tar) instead of the disk (- for stdout)
+    #[arg(long, value_name = "FILE")]
+    pub to_tar: Option<String>,
+    /// Print an equivalent script instead of creating anything
+    #[arg(long, value_name = "sh|powershell", num_args = 0..=1, require_equals = true, default_missing_value = "")]
+    pub emit_script: Option<String>,
+    /// List what would be created without touching the disk
+    #[arg(long)]
+    pub dry_run: bool,
+    /// Write the dry-run preview as a standalone HTML page; nothing is created
+    #[arg(long, value_name = "FILE")]
+    pub report_html: Option<String>,
+    // Test builds only: matching entries fail on purpose
+    #[arg(long, hide = true, value_name = "GLOB")]
+    pub simulate_failure: Vec<String>,
+}
+
+#[derive(Args)]
+pub struct DumpArgs {
+    /// Directory to print (the current one by default)
+    pub dir: Option<String>,
+    /// Show icons
+    #[arg(long)]
+    pub icons: bool,
+    /// Show sizes in bytes
+    #[arg(long)]
+    pub size: bool,
+    /// Show sizes in KiB, MiB, ...
+    #[arg(long)]
+    pub human: bool,
+    /// Show sizes in kB, MB, ...
+    #[arg(long)]
+    pub si: bool,
+    /// Show modification dates as `tree -D` does
+    #[arg(long)]
+    pub date: bool,
+    /// Show modification dates in ISO 8601
+    #[arg(long)]
+    pub iso: bool,
+    /// Descend into directory links and junctions
+    #[arg(long)]
+    pub follow_junctions: bool,
+}
+
+#[derive(Args)]
+pub struct CheckArgs {
+    /// Tree file to lint (the clipboard with -c)
+    #[arg(conflicts_with = "template")]
+    pub file: Option<String>,
+    /// Preset to validate instead
+    #[arg(long, value_name = "NAME")]
+    pub template: Option<String>,
+    #[command(flatten)]
+    pub input: InputArgs,
+    #[command(flatten)]
+    pub vars: VarArgs,
+    #[command(flatten)]
+    pub parse: ParseArgs,
+}
+
+#[derive(Args)]
+pub struct StatsArgs {
+    pub dir: String,
+    /// Tree with sizes (the clipboard with -c)
+    pub file: Option<String>,
+    #[command(flatten)]
+    pub input: InputArgs,
+    #[command(flatten)]
+    pub parse: ParseArgs,
+}
+
+#[derive(Args)]
+pub struct PruneArgs {
+    pub dir: String,
+    /// Tree whose [keep] folders stay
+    pub file: Option<String>,
+    /// Remove the folders instead of listing them
+    #[arg(long)]
+    pub apply: bool,
+    /// Read the n-th fenced code block of a Markdown tree
+    #[arg(long, value_name = "N")]
+    pub fence_index: Option<String>,
+    #[command(flatten)]
+    pub parse: ParseArgs,
+}
+
+#[derive(Args)]
+pub struct ExplainArgs {
+    /// Tree file (the clipboard with -c)
+    pub file: Option<String>,
+    #[command(flatten)]
+    pub input: InputArgs,
+    #[command(flatten)]
+    pub parse: ParseArgs,
+}
+
+#[derive(Args)]
+pub struct DemoArgs {
+    /// Example to show; all are listed without one
+    pub name: Option<String>,
+    /// Create the example instead of showing it
+    #[arg(long)]
+    pub apply: bool,
+    #[command(flatten)]
+    pub options: CreateOptions,
+}
+
+#[derive(Args)]
+pub struct WatchArgs {
+    pub tree: String,
+    #[command(flatten)]
+    pub metrics: MetricsArgs,
+    #[command(flatten)]
+    pub options: CreateOptions,
+}
+
+#[derive(Args)]
+pub struct WatchDirArgs {
+    pub tree: String,
+    /// Directory to watch (-C or the current one by default)
+    pub dir: Option<String>,
+    #[command(flatten)]
+    pub metrics: MetricsArgs,
+    #[command(flatten)]
+    pub options: CreateOptions,
+}
@@ -0,0 +1,223 @@
+// File: src\attrs.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: Bracketed attribute columns from `tree -p -u -g -s -D` listings
+// License: MIT
+
+use std::{fs::File, path::Path, time::SystemTime};
+
+use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveDateTime, TimeZone};
+use serde::{Deserialize, Serialize};
+
+// `[drwxr-xr-x user group 4096 Jan  1 12:00]`, every column is optional
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NodeAttrs {
+    pub file_type: Option<char>,
+    pub mode: Option<u32>,
+    pub owner: Option<String>,
+    pub group: Option<String>,
+    pub size: Option<String>,
+    pub mtime: Option<String>,
+}
+
+// `drwxr-sr-t` style permission string into its type character and mode bits
+fn parse_mode(token: &str) -> Option<(char, u32)> {
+    let chars: Vec<char> = token.chars().collect();
+    if chars.len() != 10 || !"-dlpscbD".contains(chars[0]) {
+        return None;
+    }
+
+    let mut mode = 0u32;
+    for (i, &c) in chars[1..].iter().enumerate() {
+        let bit = 1 << (8 - i);
+        // Special bits live in the execute column of each triplet
+        let special = match i {
+            2 => 0o4000,
+            5 => 0o2000,
+            8 => 0o1000,
+            _ => 0,
+        };
+        match (i % 3, c) {
+            (_, '-') => {}
+            (0, 'r') | (1, 'w') | (2, 'x') => mode |= bit,
+            (2, 's') | (2, 't') if special != 0 => mode |= bit | special,
+            (2, 'S') | (2, 'T') if special != 0 => mode |= special,
+            _ => return None,
+        }
+    }
+    Some((chars[0], mode))
+}
+
+// `755`, `0700`, `1777`: the octal modes tree annotations may carry
+pub fn parse_octal_mode(text: &str) -> Option<u32> {
+    if !(3..=4).contains(&text.len()) || !text.chars().all(|c| ('0'..='7').contains(&c)) {
+        return None;
+    }
+    u32::from_str_radix(text, 8).ok()
+}
+
+// `4096`, `4.0K`, `1.2M`, `512` (plain, `-h` and `--si` forms)
+pub fn is_size_token(token: &str) -> bool {
+    let digits = token.trim_end_matches(|c: char| "KMGTPEkBb".contains(c));
+    !digits.is_empty()
+        && digits.chars().all(|c| c.is_ascii_digit() || c == '.')
+        && digits.chars().next().is_some_and(|c| c.is_ascii_digit())
+}
+
+fn is_date_token(token: &str) -> bool {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    MONTHS.contains(&token)
+        || (token.len() >= 10
+            && token.as_bytes()[4] == b'-'
+            && token[..4].chars().all(|c| c.is_ascii_digit()))
+}
+
+// Split the text between the brackets into columns. GNU tree prints them in a
+// fixed order: permissions, user, group, size, date.
+pub fn parse_bracket_attrs(text: &str) -> NodeAttrs {
+    let mut attrs = NodeAttrs::default();
+    let mut date: Vec<&str> = Vec::new();
+
+    for (i, token) in text.split_whitespace().enumerate() {
+        if i == 0 {
+            if let Some((file_type, mode)) = parse_mode(token) {
+                attrs.file_type = Some(file_type);
+                attrs.mode = Some(mode);
+                continue;
+            }
+        }
+        if !date.is_empty() || is_date_token(token) {
+            date.push(token);
+        } else if attrs.size.is_none() && is_size_token(token) {
+            attrs.size = Some(token.to_string());
+        } else if attrs.owner.is_none() && attrs.size.is_none() {
+            attrs.owner = Some(token.to_string());
+        } else if attrs.group.is_none() && attrs.size.is_none() {
+            attrs.group = Some(token.to_string());
+        } else {
+            date.push(token);
+        }
+    }
+
+    if !date.is_empty() {
+        attrs.mtime = Some(date.join(" "));
+    }
+    attrs
+}
+
+// `Jan 1 12:00` (this year), `Jan 1 2024`, or ISO-8601 in local time or with an offset
+pub fn parse_mtime(text: &str) -> Option<SystemTime> {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if let Ok(dt) = DateTime::parse_from_rfc3339(&text) {
+        return Some(dt.into());
+    }
+
+    let year = Local::now().year();
+    let with_year = format!("{} {}", year, text);
+    let naive = NaiveDateTime::parse_from_str(&with_year, "%Y %b %e %H:%M")
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(&text, "%b %e %Y")
+                .ok()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+        })
+        .or_else(|| {
+            ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M"]
+                .iter()
+                .find_map(|fmt| NaiveDateTime::parse_from_str(&text, fmt).ok())
+        })
+        .or_else(|| {
+            NaiveDate::parse_from_str(&text, "%Y-%m-%d")
+                .ok()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+        })?;
+
+    Local.from_local_datetime(&naive).earliest().map(SystemTime::from)
+}
+
+#[cfg(unix)]
+fn lookup_id(name: &str, group: bool) -> Option<u32> {
+    if let Ok(id) = name.parse::<u32>() {
+        return Some(id);
+    }
+    let c_name = std::ffi::CString::new(name).ok()?;
+    // SAFETY: `c_name` is NUL-terminated; the returned records are only read
+    // immediately, before any other passwd/group lookup could overwrite them
+    unsafe {
+        if group {
+            let entry = libc::getgrnam(c_name.as_ptr());
+            (!entry.is_null()).then(|| (*entry).gr_gid)
+        } else {
+            let entry = libc::getpwnam(c_name.as_ptr());
+            (!entry.is_null()).then(|| (*entry).pw_uid)
+        }
+    }
+}
+
+#[cfg(unix)]
+fn apply_owner(path: &Path, attrs: &NodeAttrs) -> Result<(), String> {
+    if attrs.owner.is_none() && attrs.group.is_none() {
+        return Ok(());
+    }
+    let uid = match &attrs.owner {
+        Some(owner) => Some(lookup_id(owner, false).ok_or(format!("unknown user '{}'", owner))?),
+        None => None,
+    };
+    let gid = match &attrs.group {
+        Some(group) => Some(lookup_id(group, true).ok_or(format!("unknown group '{}'", group))?),
+        None => None,
+    };
+    std::os::unix::fs::chown(path, uid, gid).map_err(|e| e.to_string())
+}
+
+#[cfg(unix)]
+fn apply_mode(path: &Path, mode: u32) -> Result<(), String> {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode)).map_err(|e| e.to_string())
+}
+
+#[cfg(not(unix))]
+fn apply_owner(_path: &Path, attrs: &NodeAttrs) -> Result<(), String> {
+    if attrs.owner.is_some() || attrs.group.is_some() {
+        return Err("ownership is not supported on this platform".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn apply_mode(_path: &Path, _mode: u32) -> Result<(), String> {
+    Ok(())
+}
+
+// Apply owner, mtime and mode (in that order, so a restrictive mode cannot
+// block the other two). Returns one message per attribute that failed.
+pub fn apply_attrs(path: &Path, attrs: &NodeAttrs) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    if let Err(e) = apply_owner(path, attrs) {
+        problems.push(format!("owner: {}", e));
+    }
+
+    if let Some(text) = &attrs.mtime {
+        match parse_mtime(text) {
+            Some(time) => {
+                if let Err(e) = File::open(path).and_then(|f| f.set_modified(time)) {
+                    problems.push(format!("mtime: {}", e));
+                }
+            }
+            None => problems.push(format!("mtime: unrecognised date '{}'", text)),
+        }
+    }
+
+    if let Some(mode) = attrs.mode {
+        if let Err(e) = apply_mode(path, mode) {
+            problems.push(format!("mode: {}", e));
+        }
+    }
+
+    problems
+}
@@ -0,0 +1,87 @@
+// File: src\breakdown.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: What a run wrote, by kind and file extension
+// License: MIT
+
+use std::{collections::BTreeMap, path::Path};
+
+use serde::Serialize;
+
+use crate::{dump, NodeKind};
+
+// Extensions listed one by one; the rest are summed up as "other"
+const LISTED: usize = 8;
+
+#[derive(Debug, Default, Serialize)]
+pub struct Breakdown {
+    pub directories: usize,
+    // ".rs" -> count; "" for names without an extension
+    pub files: BTreeMap<String, usize>,
+    // Size of the files written
+    pub bytes: u64,
+    // Levels of the deepest entry written; 1 for top-level entries
+    pub depth: usize,
+}
+
+impl Breakdown {
+    pub fn add(&mut self, path: &str, kind: NodeKind, bytes: u64) {
+        self.depth = self.depth.max(path.split('/').count());
+        match kind {
+            NodeKind::Dir => self.directories += 1,
+            NodeKind::File => {
+                let extension = Path::new(path).extension().map(|e| format!(".{}", e.to_string_lossy().to_lowercase()));
+                *self.files.entry(extension.unwrap_or_default()).or_default() += 1;
+                self.bytes += bytes;
+            }
+            _ => {}
+        }
+    }
+
+    fn file_count(&self) -> usize {
+        self.files.values().sum()
+    }
+
+    // Most common first; extension-less files always get a row of their own,
+    // since hundreds of them usually mean a misparsed paste
+    fn rows(&self) -> Vec<(String, usize)> {
+        let mut counts: Vec<(&String, &usize)> = self.files.iter().collect();
+        counts.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+        let mut rows = Vec::new();
+        let mut other = 0;
+        for (extension, &count) in counts {
+            if extension.is_empty() {
+                rows.push((t!("no extension").to_string(), count));
+            } else if rows.len() < LISTED {
+                rows.push((extension.clone(), count));
+            } else {
+                other += count;
+            }
+        }
+        if other > 0 {
+            rows.push((t!("other").to_string(), other));
+        }
+        rows
+    }
+
+    pub fn print(&self) {
+        if self.directories == 0 && self.files.is_empty() {
+            return;
+        }
+        status!(
+            "🗂️ {}",
+            t!(
+                "Written: {} folder(s), {} file(s), {}B, {} level(s) deep",
+                self.directories,
+                self.file_count(),
+                dump::format_size(self.bytes, dump::SizeStyle::Binary),
+                self.depth
+            )
+        );
+        let rows = self.rows();
+        let width = rows.iter().map(|(label, _)| label.chars().count()).max().unwrap_or(0);
+        for (label, count) in rows {
+            status!("   {:<width$}  {:>5}", label, count, width = width);
+        }
+    }
+}
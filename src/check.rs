@@ -0,0 +1,200 @@
+// File: src\check.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: `mks check` - validate trees and presets without touching the filesystem
+// License: MIT
+
+use std::collections::BTreeMap;
+
+use crate::{i18n, noise, preview, presets, project, resolve_lines, split_names, validate, vars, NodeKind, ParseOptions, Placed};
+
+// `mks check --template <preset> [--vars vars.toml] [--var k=v]`: resolve the preset and
+// everything it extends, fill in the variables, then report unresolved
+// placeholders, invalid names and paths that collide. Errors exit non-zero.
+pub fn run_template(name: &str, values: &vars::Vars, popts: &ParseOptions) -> Result<(), Box<dyn std::error::Error>> {
+    let plan = presets::load_plan(name, popts, false)?;
+    println!("🔎 {}", t!("Checking preset '{}' ({} entries)", name, plan.len()));
+
+    let mut problems = 0;
+    let mut seen: BTreeMap<String, String> = BTreeMap::new();
+    for entry in &plan {
+        let path = vars::expand(&entry.path, values);
+        let contents = entry.contents.as_deref().map(|c| vars::expand(c, values));
+
+        let mut unresolved: Vec<&str> = vars::placeholders(&path);
+        unresolved.extend(contents.as_deref().map(vars::placeholders).unwrap_or_default());
+        // `{{project}}` falls back to the output folder or a prompt at run time
+        unresolved.retain(|v| format!("{{{{{}}}}}", v) != project::PLACEHOLDER);
+        unresolved.dedup();
+        for var in unresolved {
+            println!("   ❌ {}", t!("{}: unresolved variable '{}'", entry.path, var));
+            problems += 1;
+        }
+
+        for part in path.split('/') {
+            if !vars::placeholders(part).is_empty() {
+                continue;
+            }
+            if let Err(why) = validate::portable(part) {
+                println!("   ❌ {}", t!("{}: invalid name '{}': {}", entry.path, part, why));
+                problems += 1;
+            }
+        }
+
+        // Windows and macOS treat names that differ only in case as the same file
+        let key = path.to_lowercase();
+        match seen.get(&key) {
+            Some(first) => {
+                println!("   ❌ {}", t!("{} collides with {}", path, first));
+                problems += 1;
+            }
+            None => {
+                seen.insert(key, path);
+            }
+        }
+    }
+
+    if problems > 0 {
+        return Err(t!("{} problem(s) found", problems).into());
+    }
+    println!("✅ {}", t!("No problems found"));
+    Ok(())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Severity {
+    Error,
+    Warning,
+}
+
+struct Finding {
+    // 0-based input line
+    line: usize,
+    severity: Severity,
+    message: String,
+}
+
+// Everything in a text tree that the parser would have to guess around or
+// that would not come out as written
+fn lint(lines: &[String], popts: &ParseOptions) -> Vec<Finding> {
+    let resolved = resolve_lines(lines, popts);
+    let mut findings = Vec::new();
+    let mut add = |line: usize, severity: Severity, message: String| findings.push(Finding { line, severity, message });
+
+    // Files deeper down are reported below, with the line placed next to them
+    for root in resolved.file_parents.iter().filter(|file| file.root) {
+        add(
+            root.child,
+            Severity::Error,
+            t!("indented below '{}' (line {}), which is a file; write it as '{}/' or pass --promote-root-to-dir", root.name, root.line + 1, root.name),
+        );
+    }
+
+    // (parent, lowercased name) -> first line, name and kind
+    let mut siblings: BTreeMap<(Option<usize>, String), (usize, String, NodeKind)> = BTreeMap::new();
+    // The last placed line, and its line number
+    let mut previous: Option<(usize, &Placed)> = None;
+    for line in &resolved.lines {
+        let placed = match &line.placed {
+            Ok(placed) => placed,
+            Err("invalid file name") => {
+                if let Some((name, why)) = &line.trace.rejected {
+                    add(line.idx, Severity::Error, t!("invalid name '{}': {}", name, why));
+                }
+                continue;
+            }
+            Err(reason @ ("no valid names after splitting on '&'" | "heredoc without a file node above it")) => {
+                add(line.idx, Severity::Error, i18n::tr(reason).to_string());
+                continue;
+            }
+            Err("line too long") => {
+                add(line.idx, Severity::Warning, t!("skipped, {} bytes is too long for a tree line", lines[line.idx].len()));
+                continue;
+            }
+            Err(reason) => {
+                if let Some(code) = noise::code(reason) {
+                    let text = preview(lines[line.idx].trim());
+                    add(line.idx, Severity::Warning, t!("skipped {} [{}]: {}", i18n::tr(reason), code, text));
+                }
+                continue;
+            }
+        };
+
+        // Names dropped from `a & b`, or renamed by `--sanitize`
+        if !popts.raw_names {
+            for part in split_names(&placed.node.name, popts.separator()) {
+                match (validate::portable_path(&part), popts.sanitize) {
+                    (Ok(()), _) => {}
+                    (Err(_), true) => {
+                        let fixed = validate::sanitize_path(&part).unwrap_or_default();
+                        add(line.idx, Severity::Warning, t!("'{}' is renamed to '{}' (--sanitize)", part, fixed));
+                    }
+                    (Err((part, why)), false) => add(line.idx, Severity::Error, t!("invalid name '{}': {}", part, why)),
+                }
+            }
+        }
+
+        if let (true, Some((prev_idx, prev))) = (placed.clamped, previous) {
+            if matches!(prev.node.kind, NodeKind::Dir | NodeKind::Symlink) {
+                add(
+                    line.idx,
+                    Severity::Warning,
+                    t!("indentation jumps {} levels below line {}; placed directly under '{}'", placed.node.indent - prev.node.indent, prev_idx + 1, prev.names[0]),
+                );
+            } else {
+                add(
+                    line.idx,
+                    Severity::Error,
+                    t!("indented below '{}' (line {}), which is a file; write it as '{}/' or pass --auto-dir", prev.names[0], prev_idx + 1, prev.names[0]),
+                );
+            }
+        }
+
+        for &id in &placed.ids {
+            let node = resolved.tree.node(id);
+            // Windows and macOS treat names that differ only in case as the same file
+            let key = (node.parent, node.name.to_lowercase());
+            match siblings.get(&key) {
+                None => {
+                    siblings.insert(key, (line.idx, node.name.clone(), node.kind));
+                }
+                Some((first, name, _)) if *name != node.name => {
+                    add(line.idx, Severity::Warning, t!("'{}' differs only in case from '{}' (line {})", node.name, name, first + 1));
+                }
+                Some((first, _, kind)) if *kind == NodeKind::Dir && node.kind == NodeKind::Dir => {
+                    let severity = if popts.strict { Severity::Error } else { Severity::Warning };
+                    add(line.idx, severity, t!("folder '{}' is listed again (line {}); the two are merged", node.name, first + 1));
+                }
+                Some((first, _, _)) => {
+                    add(line.idx, Severity::Error, t!("'{}' is already listed at line {}", node.name, first + 1));
+                }
+            }
+        }
+        previous = Some((line.idx, placed));
+    }
+    findings.sort_by_key(|f| f.line);
+    findings
+}
+
+// `mks check [file]`: report structural problems in a tree with line numbers
+// and severity. Warnings are things the parser guesses around; errors are
+// entries that would be lost, misplaced or overwritten, and exit non-zero.
+pub fn run_tree(lines: &[String], label: &str, popts: &ParseOptions) -> Result<(), Box<dyn std::error::Error>> {
+    println!("🔎 {}", t!("Checking {} ({} lines)", label, lines.len()));
+    let findings = lint(lines, popts);
+    for finding in &findings {
+        let icon = if finding.severity == Severity::Error { "❌" } else { "⚠️" };
+        println!("   {} {}", icon, t!("line {}: {}", finding.line + 1, finding.message));
+    }
+    let errors = findings.iter().filter(|f| f.severity == Severity::Error).count();
+    let warnings = findings.len() - errors;
+    if errors > 0 {
+        return Err(t!("{} error(s), {} warning(s)", errors, warnings).into());
+    }
+    if warnings > 0 {
+        println!("✅ {}", t!("No errors, {} warning(s)", warnings));
+    } else {
+        println!("✅ {}", t!("No problems found"));
+    }
+    Ok(())
+}
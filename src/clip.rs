@@ -0,0 +1,70 @@
+// File: src\clip.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: Clipboard access, including the X11 PRIMARY selection
+// License: MIT
+
+use clipboard::{ClipboardContext, ClipboardProvider};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Selection {
+    // CLIPBOARD, falling back to PRIMARY on X11 when it is empty
+    Auto,
+    Clipboard,
+    Primary,
+}
+
+pub fn parse_selection(value: Option<&str>) -> Result<Selection, Box<dyn std::error::Error>> {
+    match value {
+        None => Ok(Selection::Auto),
+        Some("clipboard") => Ok(Selection::Clipboard),
+        Some("primary") => Ok(Selection::Primary),
+        Some(other) => Err(t!("--selection expects 'primary' or 'clipboard', got '{}'", other).into()),
+    }
+}
+
+// Check that a clipboard backend can be opened, without reading it
+pub fn probe() -> Result<(), Box<dyn std::error::Error>> {
+    let _: ClipboardContext = ClipboardProvider::new().map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn read_clipboard() -> Result<String, Box<dyn std::error::Error>> {
+    let mut ctx: ClipboardContext = ClipboardProvider::new()
+        .map_err(|_| t!("clipboard init failed"))?;
+    Ok(ctx.get_contents().map_err(|_| t!("clipboard read failed"))?)
+}
+
+// Text selected with the mouse; X11 answers from the owning window
+#[cfg(all(unix, not(any(target_os = "macos", target_os = "android"))))]
+fn read_primary() -> Result<String, Box<dyn std::error::Error>> {
+    use clipboard::x11_clipboard::{Primary, X11ClipboardContext};
+
+    let mut ctx: X11ClipboardContext<Primary> = ClipboardProvider::new()
+        .map_err(|_| t!("clipboard init failed"))?;
+    Ok(ctx.get_contents().map_err(|_| t!("primary selection read failed"))?)
+}
+
+#[cfg(not(all(unix, not(any(target_os = "macos", target_os = "android")))))]
+fn read_primary() -> Result<String, Box<dyn std::error::Error>> {
+    Err(t!("the primary selection is only available on X11").into())
+}
+
+// Contents and a label for where they came from
+pub fn read(selection: Selection) -> Result<(String, &'static str), Box<dyn std::error::Error>> {
+    match selection {
+        Selection::Clipboard => Ok((read_clipboard()?, "clipboard")),
+        Selection::Primary => Ok((read_primary()?, "primary selection")),
+        Selection::Auto => {
+            // An unowned CLIPBOARD reads as an error on X11, so treat it as empty
+            let clipboard = read_clipboard();
+            match clipboard {
+                Ok(content) if !content.trim().is_empty() => Ok((content, "clipboard")),
+                _ => match read_primary() {
+                    Ok(content) if !content.trim().is_empty() => Ok((content, "primary selection")),
+                    _ => clipboard.map(|content| (content, "clipboard")),
+                },
+            }
+        }
+    }
+}
@@ -0,0 +1,82 @@
+// File: src\clipboard_watch.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: `mks clipboard --watch` - offer to create every tree that gets copied
+// License: MIT
+
+use std::{
+    io::{self, IsTerminal},
+    path::Path,
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    apply_plan, clip, interactive, is_valid_structure, looks_like_tree,
+    metrics::Exporter,
+    order_plan, plan_from_lines, tree_lines, vars,
+    watch_dir::{count, log},
+    Options,
+};
+
+pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(1);
+
+// What is on the clipboard now, and where it came from; one that cannot be
+// read counts as empty
+fn current(selection: clip::Selection) -> (String, &'static str) {
+    clip::read(selection).unwrap_or_default()
+}
+
+// Parse the copied tree as it was polled, show it and create it in `target`
+// once confirmed
+fn offer(text: &str, source: &'static str, target: &str, yes: bool, opts: &Options, exporter: Option<&Exporter>) -> Result<(), Box<dyn std::error::Error>> {
+    let lines = tree_lines(text, source, opts.fence)?;
+    if !is_valid_structure(&lines, &opts.parse) {
+        return Err(t!("Input is empty or invalid.").into());
+    }
+    let mut plan = plan_from_lines(&lines, source, opts)?;
+    vars::apply(&mut plan, &opts.vars)?;
+    order_plan(&mut plan, opts.order)?;
+    if let Some(exporter) = exporter {
+        exporter.set_entries(plan.len());
+    }
+    if !yes && !interactive::confirm_plan(&plan, &opts.icons, Some(Path::new(target)), &[], opts.posix_paths) {
+        log(format!("⏭️ {}", t!("Skipped; nothing was created")));
+        return Ok(());
+    }
+    if let Some(deadline) = &opts.deadline {
+        deadline.next_pass();
+    }
+    let summary = apply_plan(plan, Some(target), opts)?;
+    count(exporter, &summary);
+    Ok(())
+}
+
+// Poll the clipboard every `interval`. Whatever is on it when the watch
+// starts was copied before and is left alone; each new copy that looks like
+// a tree is offered (or created right away with `yes`).
+pub fn run(target: &str, yes: bool, interval: Duration, opts: &Options, exporter: Option<Exporter>) -> Result<(), Box<dyn std::error::Error>> {
+    if !yes && !io::stdin().is_terminal() {
+        return Err(t!("mks clipboard --watch asks before creating; without a terminal, pass --yes").into());
+    }
+    clip::probe()?;
+    let selection = opts.selection.unwrap_or(clip::Selection::Auto);
+    log(format!("📋 {}", t!("Watching the clipboard, creating into {}; Ctrl-C to stop", target)));
+    let (mut seen, _) = current(selection);
+    loop {
+        thread::sleep(interval);
+        let (content, source) = current(selection);
+        if content == seen {
+            continue;
+        }
+        seen = content;
+        if !looks_like_tree(&seen) {
+            continue;
+        }
+        log(format!("🌳 {}", t!("A tree was copied ({} lines)", seen.lines().count())));
+        if let Err(e) = offer(&seen, source, target, yes, opts, exporter.as_ref()) {
+            log(format!("❌ {}", t!("Error: {}", e)));
+        }
+        log(format!("📋 {}", t!("Waiting for the next tree...")));
+    }
+}
@@ -0,0 +1,241 @@
+// File: src\config.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: User (`~/.config/mks/config.toml`) and project (`.mks.toml`) configuration
+// License: MIT
+
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde::{de, Deserialize, Deserializer, Serialize};
+
+use crate::{icons::THEMES, timeout};
+
+pub const PROJECT_FILE: &str = ".mks.toml";
+
+// `$XDG_CONFIG_HOME/mks`, `%APPDATA%\mks` or `~/.config/mks`
+pub fn config_dir() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os("XDG_CONFIG_HOME").filter(|d| !d.is_empty()) {
+        return Some(PathBuf::from(dir).join("mks"));
+    }
+    #[cfg(windows)]
+    if let Some(dir) = env::var_os("APPDATA").filter(|d| !d.is_empty()) {
+        return Some(PathBuf::from(dir).join("mks"));
+    }
+    env::var_os("HOME").map(|home| PathBuf::from(home).join(".config").join("mks"))
+}
+
+pub fn user_file() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("config.toml"))
+}
+
+// Unknown keys are errors rather than settings that silently do nothing
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct IconConfig {
+    // Built-in theme to start from: plain, emoji or nerd
+    #[serde(default, deserialize_with = "theme_name", skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dir: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub executable: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symlink: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub special: Option<String>,
+    // Extension (without the dot) to icon
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub extensions: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    // Read the clipboard when no file is given, without `--clipboard`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clipboard: Option<bool>,
+    // Glob patterns of files that are never overwritten (`*.env`, `secrets/**`)
+    #[serde(default, deserialize_with = "glob_patterns")]
+    pub protect: Vec<String>,
+    // Default for `--timeout` ("10m"); the flag wins
+    #[serde(default, skip_serializing_if = "Option::is_none", deserialize_with = "duration")]
+    pub timeout: Option<String>,
+    // Default for `--commit-message`; the flag wins
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit_message: Option<String>,
+    // `--placeholders`: extension to a sample file whose bytes empty files
+    // of that type get, next to the built-in formats
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub placeholders: BTreeMap<String, String>,
+    #[serde(default)]
+    pub icons: IconConfig,
+}
+
+// Checked while parsing, so the error points at the line in the file
+fn theme_name<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    if !THEMES.contains(&name.as_str()) {
+        return Err(de::Error::custom(t!("unknown icon theme '{}' (expected one of: {})", name, THEMES.join(", "))));
+    }
+    Ok(Some(name))
+}
+
+fn duration<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    let text = String::deserialize(deserializer)?;
+    if timeout::parse(&text).is_none() {
+        return Err(de::Error::custom(t!("timeout expects a duration such as 90s, 5m or 1500ms, got '{}'", text)));
+    }
+    Ok(Some(text))
+}
+
+fn glob_patterns<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    let patterns = Vec::<String>::deserialize(deserializer)?;
+    for pattern in &patterns {
+        Glob::new(pattern).map_err(|e| de::Error::custom(t!("protect pattern '{}': {}", pattern, e)))?;
+    }
+    Ok(patterns)
+}
+
+impl Config {
+    // Later files win field by field; extension maps are merged
+    fn merge(&mut self, other: Config) {
+        let (icons, theirs) = (&mut self.icons, other.icons);
+        icons.theme = theirs.theme.or(icons.theme.take());
+        icons.dir = theirs.dir.or(icons.dir.take());
+        icons.file = theirs.file.or(icons.file.take());
+        icons.executable = theirs.executable.or(icons.executable.take());
+        icons.symlink = theirs.symlink.or(icons.symlink.take());
+        icons.special = theirs.special.or(icons.special.take());
+        icons.extensions.extend(theirs.extensions);
+        self.protect.extend(other.protect);
+        self.placeholders.extend(other.placeholders);
+        self.clipboard = other.clipboard.or(self.clipboard);
+        self.timeout = other.timeout.or(self.timeout.take());
+        self.commit_message = other.commit_message.or(self.commit_message.take());
+    }
+
+    // Each pattern is tried against the whole path and against the file name
+    pub fn protect_set(&self) -> Result<GlobSet, Box<dyn std::error::Error>> {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in &self.protect {
+            let glob = Glob::new(pattern).map_err(|e| t!("protect pattern '{}': {}", pattern, e))?;
+            builder.add(glob);
+        }
+        Ok(builder.build()?)
+    }
+}
+
+// Number of single-character edits between two keys
+fn distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = (diagonal + usize::from(ca != cb)).min(row[j] + 1).min(above + 1);
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+// For "unknown field `protcet`, expected one of `clipboard`, `protect`, ...":
+// the expected key closest to the unknown one, if it is a likely typo
+fn suggestion(message: &str) -> Option<String> {
+    let rest = message.split("unknown field `").nth(1)?;
+    let (unknown, expected) = rest.split_once('`')?;
+    expected
+        .split('`')
+        .skip(1)
+        .step_by(2)
+        .map(|key| (distance(unknown, key), key))
+        .filter(|(d, key)| *d <= 2.max(key.len() / 3))
+        .min()
+        .map(|(_, key)| key.to_string())
+}
+
+// "<file>: <error with the offending line>", plus a likely fix for typos
+fn diagnostic(path: &Path, error: toml::de::Error) -> String {
+    let message = error.to_string();
+    let mut rendered = format!("{}: {}", path.display(), message.trim_end());
+    if let Some(key) = suggestion(&message) {
+        rendered.push_str(&format!("\n💡 {}", t!("did you mean `{}`?", key)));
+    }
+    rendered
+}
+
+fn read(path: &Path) -> Result<Option<Config>, Box<dyn std::error::Error>> {
+    match fs::read_to_string(path) {
+        Ok(text) => Ok(Some(toml::from_str(&text).map_err(|e| diagnostic(path, e))?)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(t!("{}: {}", path.display(), e).into()),
+    }
+}
+
+// Files that were looked at, in the order they are applied
+pub fn files() -> Vec<PathBuf> {
+    user_file().into_iter().chain([PathBuf::from(PROJECT_FILE)]).collect()
+}
+
+// The user config, overridden by `.mks.toml` in the current directory
+pub fn load() -> Result<Config, Box<dyn std::error::Error>> {
+    let mut config = Config::default();
+    for path in files() {
+        if let Some(found) = read(&path)? {
+            config.merge(found);
+        }
+    }
+    Ok(config)
+}
+
+// `mks config check`: parse every config file on its own and report each
+pub fn check() -> Result<(), Box<dyn std::error::Error>> {
+    let mut failed = 0;
+    for path in files() {
+        match read(&path) {
+            Ok(Some(_)) => println!("✅ {}", path.display()),
+            Ok(None) => println!("➖ {}", t!("{} (not found)", path.display())),
+            Err(e) => {
+                eprintln!("❌ {}", e);
+                failed += 1;
+            }
+        }
+    }
+    if failed > 0 {
+        return Err(t!("{} config file(s) have errors", failed).into());
+    }
+    println!("✨ {}", t!("Configuration is valid"));
+    Ok(())
+}
+
+// `mks config show [--effective]`: each config file as written, or the
+// merged settings every run actually uses
+pub fn show(effective: bool) -> Result<(), Box<dyn std::error::Error>> {
+    if effective {
+        let config = load()?;
+        let applied: Vec<String> = files().iter().filter(|p| p.is_file()).map(|p| p.display().to_string()).collect();
+        if applied.is_empty() {
+            println!("# {}", t!("no config files; these are the defaults"));
+        } else {
+            println!("# {}", t!("merged from: {}", applied.join(", ")));
+        }
+        print!("{}", toml::to_string(&config)?);
+        return Ok(());
+    }
+    for path in files() {
+        match fs::read_to_string(&path) {
+            Ok(text) => println!("# {}\n{}", path.display(), text.trim_end()),
+            Err(_) => println!("# {}", t!("{} (not found)", path.display())),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,92 @@
+// File: src\demo.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: `mks demo` - bundled example trees for every supported dialect
+// License: MIT
+
+use std::{env, path::PathBuf};
+
+use crate::{apply_plan, dump, i18n, plan_entries, resolve_lines, Options};
+
+struct Demo {
+    name: &'static str,
+    about: &'static str,
+    text: &'static str,
+}
+
+const DEMOS: &[Demo] = &[
+    Demo { name: "indent", about: "Plain indentation with spaces or tabs", text: include_str!("../demo/indent.txt") },
+    Demo { name: "tree", about: "`tree` output with icons, comments and '&'", text: include_str!("../demo/tree.txt") },
+    Demo { name: "attrs", about: "`tree -p -u -g -s -D` attribute columns", text: include_str!("../demo/attrs.txt") },
+    Demo { name: "classify", about: "`ls -F` suffixes and link entries", text: include_str!("../demo/classify.txt") },
+    Demo { name: "wide", about: "Heavy glyphs and the CJK console layout", text: include_str!("../demo/wide.txt") },
+    Demo { name: "heredoc", about: "File contents in heredoc blocks", text: include_str!("../demo/heredoc.txt") },
+    Demo { name: "annotations", about: "[tag=...] and [protect] annotations", text: include_str!("../demo/annotations.txt") },
+    Demo { name: "project", about: "The {{project}} placeholder", text: include_str!("../demo/project.txt") },
+    Demo { name: "multi", about: "Several trees separated by a blank line", text: include_str!("../demo/multi.txt") },
+    Demo { name: "outline", about: "Org-mode or Markdown headings as levels", text: include_str!("../demo/outline.txt") },
+];
+
+fn list() {
+    println!("📚 {}", t!("Example trees (mks demo <name> prints one, --apply creates it in a temporary folder):"));
+    let width = DEMOS.iter().map(|demo| demo.name.len()).max().unwrap_or(0);
+    for demo in DEMOS {
+        println!("   {:<width$}  {}", demo.name, i18n::tr(demo.about), width = width);
+    }
+    println!("   {:<width$}  {}", "all", t!("Every example above"), width = width);
+}
+
+fn select(name: &str) -> Result<Vec<&'static Demo>, Box<dyn std::error::Error>> {
+    if name == "all" {
+        return Ok(DEMOS.iter().collect());
+    }
+    match DEMOS.iter().find(|demo| demo.name == name) {
+        Some(demo) => Ok(vec![demo]),
+        None => {
+            let names: Vec<&str> = DEMOS.iter().map(|demo| demo.name).collect();
+            Err(t!("unknown demo '{}' (available: {}, all)", name, names.join(", ")).into())
+        }
+    }
+}
+
+// Create a demo below a fresh temporary folder and show what came out
+fn apply(demo: &Demo, opts: &Options) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let dir = env::temp_dir().join(format!("mks-demo-{}", std::process::id())).join(demo.name);
+    let lines: Vec<String> = demo.text.lines().map(String::from).collect();
+    let resolved = resolve_lines(&lines, &opts.parse);
+    resolved.check()?;
+    let plan = plan_entries(resolved, opts.debug);
+    let target = dir.to_string_lossy().into_owned();
+    apply_plan(plan, Some(&target), opts)?;
+
+    let dump_opts = dump::DumpOptions { icons: None, size: None, date: None, follow_links: false };
+    for line in dump::dump_tree(&dir, &dump_opts)? {
+        println!("   {}", line);
+    }
+    Ok(dir)
+}
+
+// `mks demo` lists the examples, `mks demo <name>` prints one so it can be
+// copied or piped back into mks, `--apply` creates it in a temporary folder
+pub fn run(name: Option<&str>, apply_it: bool, opts: &Options) -> Result<(), Box<dyn std::error::Error>> {
+    let Some(name) = name else {
+        list();
+        return Ok(());
+    };
+    let demos = select(name)?;
+    if !apply_it {
+        for (i, demo) in demos.iter().enumerate() {
+            if i > 0 {
+                println!();
+            }
+            print!("{}", demo.text);
+        }
+        return Ok(());
+    }
+    for demo in demos {
+        println!("🎬 {}", t!("Demo '{}': {}", demo.name, i18n::tr(demo.about)));
+        let dir = apply(demo, opts)?;
+        println!("📂 {}\n", t!("Created in {}", dir.display()));
+    }
+    Ok(())
+}
@@ -0,0 +1,115 @@
+// File: src\descriptions.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: `--write-descriptions README.md` - a path → description table from the tree's comments,
+//              and `--description-headers` - the same comments at the top of each file
+// License: MIT
+
+use std::{fs, path::Path};
+
+use crate::{shebang_for, NodeKind, PlannedEntry};
+
+const BEGIN: &str = "<!-- mks:descriptions -->";
+const END: &str = "<!-- /mks:descriptions -->";
+
+fn table(rows: &[(String, &str)]) -> String {
+    let mut out = format!("{}\n| {} | {} |\n|------|-------------|\n", BEGIN, t!("Path"), t!("Description"));
+    for (path, description) in rows {
+        out.push_str(&format!("| `{}` | {} |\n", path, description.replace('|', "\\|")));
+    }
+    out.push_str(END);
+    out.push('\n');
+    out
+}
+
+// Write the table of every entry with a `[description=...]` (a kept comment)
+// into `file`. The table sits between marker comments: a file that already
+// has them gets the block replaced, any other existing file gets it appended,
+// so the rest of a hand-written README survives re-runs. Returns the row count.
+pub fn write(plan: &[PlannedEntry], file: &str) -> Result<usize, Box<dyn std::error::Error>> {
+    let rows: Vec<(String, &str)> = plan
+        .iter()
+        .filter_map(|entry| {
+            let description = entry.annotation_value("description")?;
+            let suffix = if entry.kind == NodeKind::Dir { "/" } else { "" };
+            Some((format!("{}{}", entry.path, suffix), description))
+        })
+        .collect();
+    let block = table(&rows);
+    let text = match fs::read_to_string(file) {
+        Ok(old) => match (old.find(BEGIN), old.find(END)) {
+            (Some(begin), Some(end)) if begin < end => {
+                let after = old[end + END.len()..].strip_prefix('\n').unwrap_or(&old[end + END.len()..]);
+                format!("{}{}{}", &old[..begin], block, after)
+            }
+            _ if old.is_empty() || old.ends_with("\n\n") => format!("{}{}", old, block),
+            _ if old.ends_with('\n') => format!("{}\n{}", old, block),
+            _ => format!("{}\n\n{}", old, block),
+        },
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => block,
+        Err(e) => return Err(t!("could not read '{}': {}", file, e).into()),
+    };
+    fs::write(file, text).map_err(|e| t!("could not write '{}': {}", file, e))?;
+    Ok(rows.len())
+}
+
+// How a line comment opens and closes in a file, by extension (or by name
+// for the usual extension-less ones). None for formats without comments
+// (JSON) and for unknown files.
+fn comment_syntax(path: &str) -> Option<(&'static str, &'static str)> {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    match name {
+        "Dockerfile" | "Makefile" | "Gemfile" | "Rakefile" | "Procfile" | "Vagrantfile" | ".gitignore" | ".dockerignore" | ".env" => {
+            return Some(("#", ""))
+        }
+        _ => {}
+    }
+    let ext = Path::new(name).extension()?.to_string_lossy().to_lowercase();
+    Some(match ext.as_str() {
+        "rs" | "c" | "h" | "cc" | "cpp" | "hpp" | "cs" | "go" | "java" | "kt" | "kts" | "scala" | "swift" | "dart" | "zig" | "js" | "mjs"
+        | "cjs" | "jsx" | "ts" | "tsx" | "scss" | "less" | "proto" | "groovy" | "gradle" => ("//", ""),
+        "py" | "sh" | "bash" | "zsh" | "fish" | "rb" | "pl" | "r" | "ps1" | "toml" | "yaml" | "yml" | "cfg" | "conf" | "mk" | "cmake"
+        | "tf" | "nim" | "ex" | "exs" | "jl" | "dockerfile" | "env" => ("#", ""),
+        "sql" | "lua" | "hs" | "elm" | "ada" => ("--", ""),
+        "ini" | "asm" | "s" | "lisp" | "clj" | "el" => (";", ""),
+        "tex" | "erl" => ("%", ""),
+        "bat" | "cmd" => ("REM", ""),
+        "vb" | "bas" => ("'", ""),
+        "css" => ("/*", " */"),
+        "html" | "htm" | "xml" | "svg" | "vue" | "md" | "markdown" => ("<!--", " -->"),
+        _ => return None,
+    })
+}
+
+// `--description-headers`: start every file that has a description with it
+// as a comment, in the syntax its extension uses. The comment goes below a
+// shebang (the tree's own or `--shebang`'s) and above any contents. Files
+// fetched with `[src=...]` are written byte for byte and stay as they are.
+// Returns how many files got a header.
+pub fn add_headers(plan: &mut [PlannedEntry], shebang: bool) -> usize {
+    let mut added = 0;
+    for entry in plan.iter_mut().filter(|entry| entry.kind == NodeKind::File && !entry.has_annotation("src")) {
+        let Some(description) = entry.annotation_value("description") else {
+            continue;
+        };
+        let Some((open, close)) = comment_syntax(&entry.path) else {
+            continue;
+        };
+        let header = format!("{} {}{}\n", open, description, close);
+        let body = match entry.contents.as_deref() {
+            Some(contents) => contents,
+            None if entry.executable && shebang => shebang_for(&entry.path).unwrap_or(""),
+            None => "",
+        };
+        let contents = match body.starts_with("#!") {
+            true => {
+                let (first, rest) = body.split_once('\n').unwrap_or((body, ""));
+                format!("{}\n{}{}", first, header, rest)
+            }
+            false => format!("{}{}", header, body),
+        };
+        entry.contents = Some(contents);
+        added += 1;
+    }
+    added
+}
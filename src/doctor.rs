@@ -0,0 +1,220 @@
+// File: src\doctor.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: `mks doctor` - diagnose the environment and suggest fixes
+// License: MIT
+
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    io::IsTerminal,
+    path::Path,
+};
+
+use crate::{clip, config, presets};
+
+#[derive(Default)]
+struct Report {
+    problems: usize,
+}
+
+impl Report {
+    fn ok(&mut self, message: impl Display) {
+        println!("  ✅ {}", message);
+    }
+
+    fn info(&mut self, message: impl Display) {
+        println!("  ℹ️ {}", message);
+    }
+
+    fn problem(&mut self, message: impl Display, fix: impl Display) {
+        self.problems += 1;
+        println!("  ⚠️ {}", message);
+        println!("     → {}", fix);
+    }
+}
+
+fn is_wsl() -> bool {
+    fs::read_to_string("/proc/sys/kernel/osrelease")
+        .map(|release| release.to_lowercase().contains("microsoft"))
+        .unwrap_or(false)
+}
+
+fn env_set(name: &str) -> bool {
+    env::var_os(name).is_some_and(|v| !v.is_empty())
+}
+
+fn check_clipboard(report: &mut Report) {
+    println!("📋 {}", t!("Clipboard"));
+    if cfg!(all(unix, not(any(target_os = "macos", target_os = "android")))) {
+        let wsl = is_wsl();
+        if wsl {
+            report.info(t!("running under WSL"));
+        }
+        if !env_set("DISPLAY") {
+            let fix = if env_set("WAYLAND_DISPLAY") {
+                t!("mks talks to the clipboard through X11: enable XWayland, or save the tree to a file and run 'mks tree.txt'")
+            } else if wsl {
+                t!("update to a WSL version with WSLg (DISPLAY=:0), or save the tree to a file and run 'mks tree.txt'")
+            } else {
+                t!("no graphical session: save the tree to a file and run 'mks tree.txt'")
+            };
+            report.problem(t!("DISPLAY is not set, the clipboard cannot be reached"), fix);
+            return;
+        }
+        if env_set("WAYLAND_DISPLAY") {
+            report.info(t!("Wayland session, using the XWayland clipboard"));
+        }
+    }
+    match clip::probe() {
+        Ok(()) => report.ok(t!("clipboard backend is available")),
+        Err(e) => report.problem(
+            t!("clipboard backend is unavailable: {}", e),
+            t!("save the tree to a file and run 'mks tree.txt'"),
+        ),
+    }
+}
+
+fn check_terminal(report: &mut Report) {
+    println!("🖥️ {}", t!("Terminal"));
+    if !std::io::stdout().is_terminal() {
+        report.info(t!("output is not a terminal, glyphs are not checked"));
+        return;
+    }
+    if cfg!(windows) {
+        if env_set("WT_SESSION") {
+            report.ok(t!("Windows Terminal renders Unicode and emoji"));
+        } else {
+            report.problem(
+                t!("the classic console may show tree lines and emoji as '?'"),
+                t!("use Windows Terminal, or run 'chcp 65001' first"),
+            );
+        }
+        return;
+    }
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .find_map(|name| env::var(name).ok().filter(|v| !v.is_empty()))
+        .unwrap_or_default();
+    let upper = locale.to_uppercase();
+    if upper.contains("UTF-8") || upper.contains("UTF8") {
+        report.ok(t!("UTF-8 locale ({})", locale));
+    } else {
+        report.problem(
+            t!("locale '{}' is not UTF-8, tree lines and emoji may be garbled", locale),
+            t!("export LANG=en_US.UTF-8 (or another UTF-8 locale)"),
+        );
+    }
+    if env::var("TERM").as_deref() == Ok("linux") {
+        report.problem(
+            t!("the Linux console cannot draw emoji"),
+            t!("use a terminal emulator for emoji output"),
+        );
+    }
+}
+
+// Create a mixed-case probe file and look for it under its lowercase name
+fn case_sensitive(dir: &Path) -> std::io::Result<bool> {
+    let probe = dir.join(format!(".mks-doctor-{}-CASE", std::process::id()));
+    fs::write(&probe, b"")?;
+    let lower = dir.join(format!(".mks-doctor-{}-case", std::process::id()));
+    let sensitive = !lower.exists();
+    fs::remove_file(&probe)?;
+    Ok(sensitive)
+}
+
+#[cfg(unix)]
+fn path_limits(dir: &Path) -> Option<(i64, i64)> {
+    use std::{ffi::CString, os::unix::ffi::OsStrExt};
+
+    let c_dir = CString::new(dir.as_os_str().as_bytes()).ok()?;
+    let name_max = unsafe { libc::pathconf(c_dir.as_ptr(), libc::_PC_NAME_MAX) };
+    let path_max = unsafe { libc::pathconf(c_dir.as_ptr(), libc::_PC_PATH_MAX) };
+    Some((name_max as i64, path_max as i64))
+}
+
+#[cfg(not(unix))]
+fn path_limits(_dir: &Path) -> Option<(i64, i64)> {
+    // Without the long-path opt-in, Win32 paths stop at MAX_PATH
+    Some((255, 260))
+}
+
+fn check_filesystem(report: &mut Report) {
+    println!("💾 {}", t!("Filesystem"));
+    let cwd = match env::current_dir() {
+        Ok(cwd) => cwd,
+        Err(e) => {
+            report.problem(t!("cannot read the current directory: {}", e), t!("cd into an existing directory"));
+            return;
+        }
+    };
+    report.info(t!("current directory: {}", cwd.display()));
+
+    match case_sensitive(&cwd) {
+        Ok(true) => report.ok(t!("case-sensitive: 'README' and 'readme' are different entries")),
+        Ok(false) => report.info(t!("case-insensitive: names differing only in case end up as one entry")),
+        Err(e) => report.problem(
+            t!("the current directory is not writable: {}", e),
+            t!("run mks in a directory you own, or pass -o <dir>"),
+        ),
+    }
+
+    if let Some((name_max, path_max)) = path_limits(&cwd) {
+        let used = cwd.as_os_str().len() as i64;
+        report.info(t!("limits: {} bytes per name, {} per path ({} used by this directory)", name_max, path_max, used));
+        if path_max > 0 && path_max - used < 100 {
+            report.problem(
+                t!("only {} bytes of path left for the structure", path_max - used),
+                t!("create the structure closer to the filesystem root"),
+            );
+        }
+    }
+}
+
+fn check_config(report: &mut Report) {
+    println!("⚙️ {}", t!("Configuration"));
+    for path in config::files() {
+        if path.is_file() {
+            report.ok(t!("config file: {}", path.display()));
+        } else {
+            report.info(t!("no config file at {}", path.display()));
+        }
+    }
+    if let Err(e) = config::load() {
+        report.problem(e, t!("fix the TOML syntax or remove the file"));
+    }
+    let Some(dir) = presets::templates_dir() else {
+        report.problem(
+            t!("cannot locate the config directory (HOME is not set)"),
+            t!("set HOME (or XDG_CONFIG_HOME)"),
+        );
+        return;
+    };
+    match fs::read_dir(&dir) {
+        Ok(entries) => {
+            let count = entries
+                .filter_map(Result::ok)
+                .filter(|e| e.path().extension().is_some_and(|ext| ext == "txt"))
+                .count();
+            report.ok(t!("{} preset(s) in {}", count, dir.display()));
+        }
+        Err(_) => report.info(t!("no preset directory yet; put <name>.txt files in {} to use --preset", dir.display())),
+    }
+}
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let mut report = Report::default();
+    check_clipboard(&mut report);
+    check_terminal(&mut report);
+    check_filesystem(&mut report);
+    check_config(&mut report);
+
+    println!();
+    if report.problems == 0 {
+        println!("✅ {}", t!("No problems found"));
+    } else {
+        println!("⚠️ {}", t!("{} problem(s) found", report.problems));
+    }
+    Ok(())
+}
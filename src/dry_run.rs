@@ -0,0 +1,299 @@
+// File: src\dry_run.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: `--dry-run` - list the plan and summarise it per top-level directory
+// License: MIT
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{self, Path, PathBuf},
+};
+
+use unicode_width::UnicodeWidthStr;
+
+use crate::{escape, i18n, icons::IconTheme, project::single_root, NodeKind, PlannedEntry};
+
+#[derive(Default)]
+struct Group {
+    name: String,
+    files: usize,
+    dirs: usize,
+    other: usize,
+}
+
+impl Group {
+    fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if self.files > 0 {
+            parts.push(t!("{} file(s)", self.files));
+        }
+        if self.dirs > 0 {
+            parts.push(t!("{} dir(s)", self.dirs));
+        }
+        if self.other > 0 {
+            parts.push(t!("{} other", self.other));
+        }
+        if parts.is_empty() {
+            t!("empty").to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+}
+
+// Groups are the entries of the single root folder, or the top-level entries
+// when there is none. A group's own directory is not counted in it; loose
+// files at that level share one group.
+fn group_plan(plan: &[PlannedEntry]) -> Vec<Group> {
+    let base = single_root(plan).map(|root| format!("{}/", root));
+    let top_level = t!("(top level)").to_string();
+    let mut groups: Vec<Group> = Vec::new();
+
+    for entry in plan {
+        let rel = match &base {
+            Some(base) if entry.path == base.trim_end_matches('/') => continue,
+            Some(base) => entry.path.strip_prefix(base.as_str()).unwrap_or(&entry.path),
+            None => entry.path.as_str(),
+        };
+        let (name, inside) = match rel.split_once('/') {
+            Some((first, _)) => (format!("{}/", first), true),
+            None if entry.kind == NodeKind::Dir => (format!("{}/", rel), false),
+            None => (top_level.clone(), true),
+        };
+        let index = match groups.iter().position(|g| g.name == name) {
+            Some(index) => index,
+            None => {
+                groups.push(Group { name, ..Group::default() });
+                groups.len() - 1
+            }
+        };
+        if !inside {
+            continue;
+        }
+        let group = &mut groups[index];
+        match entry.kind {
+            NodeKind::File => group.files += 1,
+            NodeKind::Dir => group.dirs += 1,
+            _ => group.other += 1,
+        }
+    }
+
+    // Loose files last, after the folders
+    groups.sort_by_key(|g| g.name == top_level);
+    groups
+}
+
+// (roots, children of each entry) as plan indexes. Built from the paths,
+// so `--breadth-first` plans still come out nested.
+pub fn layout(plan: &[PlannedEntry]) -> (Vec<usize>, Vec<Vec<usize>>) {
+    let index: HashMap<&str, usize> = plan.iter().enumerate().map(|(i, e)| (e.path.as_str(), i)).collect();
+    let mut children: Vec<Vec<usize>> = vec![Vec::new(); plan.len()];
+    let mut roots = Vec::new();
+    for (i, entry) in plan.iter().enumerate() {
+        match entry.path.rsplit_once('/').and_then(|(parent, _)| index.get(parent)) {
+            Some(&parent) => children[parent].push(i),
+            None => roots.push(i),
+        }
+    }
+    (roots, children)
+}
+
+// The plan drawn as a tree, one (text, entry) per line
+pub fn preview<'a>(plan: &'a [PlannedEntry], icons: &IconTheme) -> Vec<(String, &'a PlannedEntry)> {
+    let (roots, children) = layout(plan);
+
+    fn walk<'a>(
+        i: usize,
+        prefix: &str,
+        marker: &str,
+        plan: &'a [PlannedEntry],
+        children: &[Vec<usize>],
+        icons: &IconTheme,
+        out: &mut Vec<(String, &'a PlannedEntry)>,
+    ) {
+        let entry = &plan[i];
+        let name = entry.path.rsplit('/').next().unwrap_or(&entry.path);
+        let suffix = if entry.kind == NodeKind::Dir { "/" } else { "" };
+        let icon = icons.icon(&entry.path, entry.kind, entry.executable);
+        out.push((format!("{}{}{} {}{}", prefix, marker, icon, name, suffix), entry));
+        let inner = match marker {
+            "├── " => format!("{}│   ", prefix),
+            "└── " => format!("{}    ", prefix),
+            _ => prefix.to_string(),
+        };
+        for (n, &child) in children[i].iter().enumerate() {
+            let last = n + 1 == children[i].len();
+            walk(child, &inner, if last { "└── " } else { "├── " }, plan, children, icons, out);
+        }
+    }
+
+    let mut out = Vec::with_capacity(plan.len());
+    for root in roots {
+        walk(root, "", "", plan, &children, icons, &mut out);
+    }
+    out
+}
+
+// What is on disk where an entry would go
+pub enum OnDisk {
+    Missing,
+    Matches,
+    // Something of another kind, e.g. a file where the plan has a folder
+    Conflict(NodeKind),
+}
+
+fn disk_kind(file_type: fs::FileType) -> NodeKind {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::FileTypeExt;
+        if file_type.is_fifo() {
+            return NodeKind::Fifo;
+        }
+        if file_type.is_socket() {
+            return NodeKind::Socket;
+        }
+    }
+    if file_type.is_dir() {
+        NodeKind::Dir
+    } else if file_type.is_symlink() {
+        NodeKind::Symlink
+    } else if file_type.is_file() {
+        NodeKind::File
+    } else {
+        NodeKind::Door
+    }
+}
+
+// Only the kind is compared, not contents. Folders and files reached through
+// a symlink count as what they point to, the way a run would use them.
+pub fn on_disk(target: &Path, kind: NodeKind) -> OnDisk {
+    let target = &escape::long_path(target);
+    let meta = match kind {
+        NodeKind::Dir | NodeKind::File => fs::metadata(target).or_else(|_| fs::symlink_metadata(target)),
+        _ => fs::symlink_metadata(target),
+    };
+    match meta {
+        Err(_) => OnDisk::Missing,
+        Ok(meta) if disk_kind(meta.file_type()) == kind => OnDisk::Matches,
+        Ok(meta) => OnDisk::Conflict(disk_kind(meta.file_type())),
+    }
+}
+
+// How far the target is from the plan, worst last. `--dry-run` exits with it.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verdict {
+    Matches,
+    Additions,
+    Conflicts,
+}
+
+impl Verdict {
+    pub fn exit_code(self) -> i32 {
+        match self {
+            Verdict::Matches => 0,
+            Verdict::Additions => 2,
+            Verdict::Conflicts => 3,
+        }
+    }
+}
+
+// (entries to create, entries in conflict) for one target directory
+fn compare(plan: &[PlannedEntry], base: &Path) -> (usize, usize) {
+    let (mut missing, mut conflicts) = (0, 0);
+    for entry in plan {
+        match on_disk(&resolve(base, &entry.path), entry.kind) {
+            OnDisk::Missing => missing += 1,
+            OnDisk::Conflict(_) => conflicts += 1,
+            OnDisk::Matches => {}
+        }
+    }
+    (missing, conflicts)
+}
+
+// Where an entry lands, in the separators of this OS: plan paths always use
+// '/', which Windows would otherwise show mixed with '\\'
+pub fn resolve(base: &Path, path: &str) -> PathBuf {
+    base.join(escape::os_path(path)).components().collect()
+}
+
+// A resolved path for the report; `--posix-paths` shows '/' on every OS
+pub fn shown(path: &Path, posix: bool) -> String {
+    let text = path.display().to_string();
+    if posix && path::MAIN_SEPARATOR != '/' {
+        text.replace(path::MAIN_SEPARATOR, "/")
+    } else {
+        text
+    }
+}
+
+// `base` is where a single run would create the plan; it is None when the
+// plan goes into several `--into` targets
+pub fn print(plan: &[PlannedEntry], icons: &IconTheme, base: Option<&Path>, targets: &[String], posix: bool) -> Verdict {
+    println!("🧪 {}\n", t!("Dry run: nothing is created"));
+    report(plan, icons, base, targets, posix)
+}
+
+// The preview itself, also shown by `--interactive` before it asks
+pub fn report(plan: &[PlannedEntry], icons: &IconTheme, base: Option<&Path>, targets: &[String], posix: bool) -> Verdict {
+    let lines = preview(plan, icons);
+    let width = lines.iter().map(|(text, _)| text.width()).max().unwrap_or(0);
+    for (text, entry) in &lines {
+        let Some(base) = base else {
+            println!("{}", text);
+            continue;
+        };
+        let target = resolve(base, &entry.path);
+        let suffix = match (entry.kind, posix) {
+            (NodeKind::Dir, true) => "/",
+            (NodeKind::Dir, false) => path::MAIN_SEPARATOR_STR,
+            _ => "",
+        };
+        let state = match on_disk(&target, entry.kind) {
+            OnDisk::Missing => String::new(),
+            OnDisk::Matches => format!("  ({})", t!("exists")),
+            OnDisk::Conflict(found) => format!("  ⚠️ ({})", t!("a {} is in the way", i18n::tr(found.label()))),
+        };
+        let pad = " ".repeat(width - text.width());
+        println!("{}{}  {}{}{}", text, pad, shown(&target, posix), suffix, state);
+    }
+
+    let files = plan.iter().filter(|e| e.kind == NodeKind::File).count();
+    let dirs = plan.iter().filter(|e| e.kind == NodeKind::Dir).count();
+    println!("\n🧾 {}", t!("Plan: {} file(s), {} dir(s), {} entries in total", files, dirs, plan.len()));
+
+    let groups = group_plan(plan);
+    let width = groups.iter().map(|g| g.name.chars().count()).max().unwrap_or(0);
+    for group in &groups {
+        let pad = width - group.name.chars().count();
+        println!("   {}{}  {}", group.name, " ".repeat(pad), group.describe());
+    }
+
+    if !targets.is_empty() {
+        println!("\n📂 {}", t!("Would be applied into {} target(s):", targets.len()));
+        for target in targets {
+            let resolved = path::absolute(target).unwrap_or_else(|_| target.into());
+            println!("   {}", shown(&resolved.components().collect::<PathBuf>(), posix));
+        }
+    }
+
+    let bases: Vec<&Path> = match base {
+        Some(base) => vec![base],
+        None => targets.iter().map(Path::new).collect(),
+    };
+    let (missing, conflicts) = bases
+        .iter()
+        .map(|base| compare(plan, base))
+        .fold((0, 0), |(m, c), (missing, conflicts)| (m + missing, c + conflicts));
+    println!();
+    if conflicts > 0 {
+        println!("⚠️ {}", t!("{} entries conflict with what is on disk", conflicts));
+        Verdict::Conflicts
+    } else if missing > 0 {
+        println!("➕ {}", t!("{} entries would be created", missing));
+        Verdict::Additions
+    } else {
+        println!("✅ {}", t!("Target already matches the plan"));
+        Verdict::Matches
+    }
+}
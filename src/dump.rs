@@ -0,0 +1,267 @@
+// File: src\dump.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: Reverse mode - export an existing directory as tree text
+// License: MIT
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use chrono::{DateTime, Duration, Local};
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+
+use crate::{escape, icons::IconTheme, NodeKind};
+
+pub const IGNORE_FILE: &str = ".mksignore";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SizeStyle {
+    // `4096`
+    Bytes,
+    // `--human`: powers of 1024, `4.0K`
+    Binary,
+    // `--si`: powers of 1000, `4.1k`
+    Si,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DateStyle {
+    // `Jan  1 12:00`, or `Jan  1  2023` when older than six months, like `tree -D`
+    Tree,
+    // `--iso`: `2024-01-01T12:00:00+07:00`
+    Iso,
+}
+
+pub struct DumpOptions<'a> {
+    pub icons: Option<&'a IconTheme>,
+    pub size: Option<SizeStyle>,
+    pub date: Option<DateStyle>,
+    // `--follow-junctions`: descend into linked directories (cycles are still cut)
+    pub follow_links: bool,
+}
+
+struct Entry {
+    name: String,
+    path: PathBuf,
+    is_dir: bool,
+    kind: NodeKind,
+    executable: bool,
+    size: u64,
+    modified: Option<SystemTime>,
+    // Symlink or junction: its target and whether that is a directory
+    link: Option<(String, bool)>,
+}
+
+// Build the ignore matcher from `<root>/.mksignore` (gitignore syntax).
+// A missing file simply yields a matcher that ignores nothing.
+pub fn load_ignore(root: &Path) -> Result<Gitignore, Box<dyn std::error::Error>> {
+    load_ignore_files(root, &[IGNORE_FILE])
+}
+
+// The same from several files in `root`, such as `.mksignore` and `.gitignore`
+pub fn load_ignore_files(root: &Path, names: &[&str]) -> Result<Gitignore, Box<dyn std::error::Error>> {
+    let mut builder = GitignoreBuilder::new(root);
+    for name in names {
+        let ignore_path = root.join(name);
+        if ignore_path.is_file() {
+            if let Some(err) = builder.add(&ignore_path) {
+                return Err(format!("{}: {}", ignore_path.display(), err).into());
+            }
+        }
+    }
+    Ok(builder.build()?)
+}
+
+fn read_entries(dir: &Path, ignore: &Gitignore) -> Result<Vec<Entry>, Box<dyn std::error::Error>> {
+    let mut entries = Vec::new();
+    for item in fs::read_dir(dir)? {
+        let item = item?;
+        // Non-UTF-8 names are carried as `\xHH` escapes, so they round-trip
+        let name = escape::escape_os_name(&item.file_name());
+        // Never export VCS internals or mks state
+        if name == ".git" || crate::state::is_state_name(&name) {
+            continue;
+        }
+        // Links are not followed here. On Windows, junctions and other name
+        // surrogate reparse points report as symlinks too.
+        let file_type = item.file_type()?;
+        let is_dir = file_type.is_dir();
+        let path = item.path();
+        if ignore.matched_path_or_any_parents(&path, is_dir).is_ignore() {
+            continue;
+        }
+        let kind = if is_dir {
+            NodeKind::Dir
+        } else if file_type.is_symlink() {
+            NodeKind::Symlink
+        } else if file_type.is_file() {
+            NodeKind::File
+        } else {
+            NodeKind::Fifo
+        };
+        let meta = item.metadata()?;
+        let executable = kind == NodeKind::File && is_executable(&meta);
+        let link = match kind {
+            NodeKind::Symlink => fs::read_link(&path)
+                .ok()
+                .map(|target| (target.display().to_string(), path.is_dir())),
+            _ => None,
+        };
+        entries.push(Entry {
+            name,
+            path,
+            is_dir,
+            kind,
+            executable,
+            size: meta.len(),
+            modified: meta.modified().ok(),
+            link,
+        });
+    }
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(entries)
+}
+
+#[cfg(unix)]
+fn is_executable(meta: &fs::Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    meta.permissions().mode() & 0o111 != 0
+}
+
+#[cfg(not(unix))]
+fn is_executable(_meta: &fs::Metadata) -> bool {
+    false
+}
+
+pub fn format_size(bytes: u64, style: SizeStyle) -> String {
+    let (base, units) = match style {
+        SizeStyle::Bytes => return bytes.to_string(),
+        SizeStyle::Binary => (1024.0, ["K", "M", "G", "T", "P", "E"]),
+        SizeStyle::Si => (1000.0, ["k", "M", "G", "T", "P", "E"]),
+    };
+    if (bytes as f64) < base {
+        return bytes.to_string();
+    }
+    let mut value = bytes as f64;
+    let mut unit = "";
+    for next in units {
+        if value < base {
+            break;
+        }
+        value /= base;
+        unit = next;
+    }
+    // One decimal below 10, like `ls -h`
+    if value < 10.0 {
+        format!("{:.1}{}", value, unit)
+    } else {
+        format!("{:.0}{}", value, unit)
+    }
+}
+
+fn format_date(time: SystemTime, style: DateStyle) -> String {
+    let time: DateTime<Local> = time.into();
+    match style {
+        DateStyle::Iso => time.to_rfc3339_opts(chrono::SecondsFormat::Secs, false),
+        DateStyle::Tree if Local::now() - time > Duration::days(182) => time.format("%b %e  %Y").to_string(),
+        DateStyle::Tree => time.format("%b %e %H:%M").to_string(),
+    }
+}
+
+// `[ 4.0K Jan  1 12:00]  ` in front of the name, empty without columns.
+// The parser reads this back through its bracketed-attribute handling.
+fn columns(entry: &Entry, opts: &DumpOptions) -> String {
+    let mut parts = Vec::new();
+    if let Some(style) = opts.size {
+        let width = if style == SizeStyle::Bytes { 8 } else { 5 };
+        parts.push(format!("{:>width$}", format_size(entry.size, style), width = width));
+    }
+    if let (Some(style), Some(modified)) = (opts.date, entry.modified) {
+        parts.push(format_date(modified, style));
+    }
+    if parts.is_empty() {
+        String::new()
+    } else {
+        format!("[{}]  ", parts.join(" "))
+    }
+}
+
+// `ancestors` holds the canonical directories being walked, so a followed
+// link that points back up the tree is listed but not entered again
+fn walk(
+    dir: &Path,
+    prefix: &str,
+    ignore: &Gitignore,
+    opts: &DumpOptions,
+    ancestors: &mut Vec<PathBuf>,
+    out: &mut Vec<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let entries = read_entries(dir, ignore)?;
+    let count = entries.len();
+    for (i, entry) in entries.iter().enumerate() {
+        let last = i + 1 == count;
+        let marker = if last { "└── " } else { "├── " };
+        let suffix = match &entry.link {
+            Some((target, true)) => format!(" -> {}/", target.trim_end_matches(['/', '\\'])),
+            Some((target, false)) => format!(" -> {}", target),
+            None if entry.is_dir => "/".to_string(),
+            None => String::new(),
+        };
+        let icon = match opts.icons {
+            Some(theme) => format!("{} ", theme.icon(&entry.name, entry.kind, entry.executable)),
+            None => String::new(),
+        };
+        // A bare '&' would split the name in two when the dump is read back
+        let name = entry.name.replace('&', "\\&");
+        out.push(format!("{}{}{}{}{}{}", prefix, marker, columns(entry, opts), icon, name, suffix));
+
+        let descend = match &entry.link {
+            Some((_, true)) if opts.follow_links => fs::canonicalize(&entry.path)
+                .ok()
+                .filter(|target| !ancestors.contains(target)),
+            Some(_) => None,
+            None if entry.is_dir => Some(fs::canonicalize(&entry.path).unwrap_or_else(|_| entry.path.clone())),
+            None => None,
+        };
+        if let Some(real) = descend {
+            let child_prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+            ancestors.push(real);
+            walk(&entry.path, &child_prefix, ignore, opts, ancestors, out)?;
+            ancestors.pop();
+        }
+    }
+    Ok(())
+}
+
+// Render `root` as tree text that mks can read back.
+pub fn dump_tree(root: &Path, opts: &DumpOptions) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    if !root.is_dir() {
+        return Err(t!("'{}' is not a directory", root.display()).into());
+    }
+    let ignore = load_ignore(root)?;
+
+    let canonical_root = fs::canonicalize(root)?;
+    let root_name = canonical_root
+        .file_name()
+        .map(escape::escape_os_name)
+        .unwrap_or_else(|| escape::escape_name(&root.display().to_string()));
+
+    let root_icon = match opts.icons {
+        Some(theme) => format!("{} ", theme.dir),
+        None => String::new(),
+    };
+    let mut out = vec![format!("{}{}/", root_icon, root_name.replace('&', "\\&"))];
+    walk(root, "", &ignore, opts, &mut vec![canonical_root.clone()], &mut out)?;
+    Ok(out)
+}
+
+pub fn run(dir: Option<&str>, opts: &DumpOptions) -> Result<(), Box<dyn std::error::Error>> {
+    for line in dump_tree(Path::new(dir.unwrap_or(".")), opts)? {
+        println!("{}", line);
+    }
+    Ok(())
+}
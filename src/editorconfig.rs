@@ -0,0 +1,131 @@
+// File: src\editorconfig.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: Write generated file contents the way `.editorconfig` asks for
+// License: MIT
+
+use std::path::{self, Path};
+
+use ec4rs::property::{Charset, EndOfLine, FinalNewline, IndentSize, IndentStyle, TabWidth};
+
+enum Indent {
+    Tabs,
+    Spaces(usize),
+}
+
+// The rules that change how contents are written; trailing whitespace and
+// line length are left to the author of the tree
+#[derive(Default)]
+struct Style {
+    indent: Option<Indent>,
+    end_of_line: Option<EndOfLine>,
+    final_newline: Option<bool>,
+    charset: Option<Charset>,
+}
+
+fn style_for(path: &Path) -> Result<Style, Box<dyn std::error::Error>> {
+    let mut props = ec4rs::properties_of(path::absolute(path)?)?;
+    props.use_fallbacks();
+    let size = match (props.get::<IndentSize>(), props.get::<TabWidth>()) {
+        (Ok(IndentSize::Value(size)), _) | (_, Ok(TabWidth::Value(size))) => Some(size),
+        _ => None,
+    };
+    // `indent_size` on its own still means spaces of that width
+    let indent = match (props.get::<IndentStyle>(), size) {
+        (Ok(IndentStyle::Tabs), _) => Some(Indent::Tabs),
+        (Ok(IndentStyle::Spaces), size) => Some(Indent::Spaces(size.unwrap_or(4))),
+        (Err(_), Some(size)) => Some(Indent::Spaces(size)),
+        (Err(_), None) => None,
+    };
+    Ok(Style {
+        indent,
+        end_of_line: props.get::<EndOfLine>().ok(),
+        final_newline: match props.get::<FinalNewline>() {
+            Ok(FinalNewline::Value(insert)) => Some(insert),
+            _ => None,
+        },
+        charset: props.get::<Charset>().ok(),
+    })
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+// Re-indent `text` level by level. A level is a tab, or the largest number of
+// spaces every indented line is a multiple of; columns left over are kept.
+fn reindent(text: &str, indent: &Indent) -> String {
+    let leading_spaces = |line: &str| line.len() - line.trim_start_matches(' ').len();
+    let unit = text.lines().filter(|line| !line.trim().is_empty()).map(leading_spaces).fold(0, gcd);
+    let mut out = String::with_capacity(text.len());
+    for line in text.split_inclusive('\n') {
+        let body = line.trim_start_matches([' ', '\t']);
+        let lead = &line[..line.len() - body.len()];
+        // Blank lines keep nothing
+        if body.trim_end_matches(['\r', '\n']).is_empty() {
+            out.push_str(body);
+            continue;
+        }
+        let tabs = lead.chars().filter(|&c| c == '\t').count();
+        let spaces = lead.len() - tabs;
+        let (levels, rest) = match unit {
+            0 => (tabs, spaces),
+            unit => (tabs + spaces / unit, spaces % unit),
+        };
+        match indent {
+            Indent::Tabs => out.push_str(&"\t".repeat(levels)),
+            Indent::Spaces(size) => out.push_str(&" ".repeat(levels * size)),
+        }
+        out.push_str(&" ".repeat(rest));
+        out.push_str(body);
+    }
+    out
+}
+
+fn encode(text: &str, charset: Option<Charset>, path: &Path) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    Ok(match charset {
+        None | Some(Charset::Utf8) => text.as_bytes().to_vec(),
+        Some(Charset::Utf8Bom) => ["\u{feff}", text].concat().into_bytes(),
+        Some(Charset::Latin1) => {
+            let mut bytes = Vec::with_capacity(text.len());
+            for c in text.chars() {
+                let byte = u8::try_from(u32::from(c))
+                    .map_err(|_| t!("'{}': '{}' cannot be written in latin1 (.editorconfig charset)", path.display(), c))?;
+                bytes.push(byte);
+            }
+            bytes
+        }
+        Some(Charset::Utf16Le) => "\u{feff}".encode_utf16().chain(text.encode_utf16()).flat_map(u16::to_le_bytes).collect(),
+        Some(Charset::Utf16Be) => "\u{feff}".encode_utf16().chain(text.encode_utf16()).flat_map(u16::to_be_bytes).collect(),
+    })
+}
+
+// Bytes to write for `text` at `path`, following the `.editorconfig` files
+// above it: indentation, line endings, the final newline and the charset.
+// Empty files stay empty.
+pub fn render(path: &Path, text: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    if text.is_empty() {
+        return Ok(Vec::new());
+    }
+    let style = style_for(path).map_err(|e| t!("'{}': .editorconfig: {}", path.display(), e))?;
+
+    let mut text = text.replace("\r\n", "\n");
+    if let Some(indent) = &style.indent {
+        text = reindent(&text, indent);
+    }
+    match style.final_newline {
+        Some(true) if !text.ends_with('\n') => text.push('\n'),
+        Some(false) => text.truncate(text.trim_end_matches('\n').len()),
+        _ => {}
+    }
+    match style.end_of_line {
+        Some(EndOfLine::CrLf) => text = text.replace('\n', "\r\n"),
+        Some(EndOfLine::Cr) => text = text.replace('\n', "\r"),
+        _ => {}
+    }
+    encode(&text, style.charset, path)
+}
@@ -0,0 +1,255 @@
+// File: src\escape.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: Escaping for names that contain tree glyphs, comment markers or non-UTF-8 bytes
+// License: MIT
+
+use std::{
+    ffi::{OsStr, OsString},
+    path::{Path, PathBuf},
+};
+
+use crate::glyphs;
+
+// Characters that would be read as tree structure or as the start of a comment
+fn needs_escape(c: char) -> bool {
+    glyphs::is_box_glyph(c) || matches!(c, '#' | '✅' | '←' | '\\')
+}
+
+// `a│b` -> `a\u{2502}b`, `\` -> `\\`. Used by `mks dump`.
+pub fn escape_name(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    for c in name.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            c if needs_escape(c) => out.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+// Undo `escape_name`. Anything that is not a valid escape is kept as written,
+// so `\xHH` byte escapes and surrogates survive until `os_path`.
+pub fn unescape_name(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    let mut rest = name;
+    while let Some(pos) = rest.find('\\') {
+        out.push_str(&rest[..pos]);
+        let after = &rest[pos + 1..];
+        if let Some(tail) = after.strip_prefix('\\') {
+            out.push_str(literal_backslash(tail));
+            rest = tail;
+            continue;
+        }
+        let decoded = after
+            .strip_prefix("u{")
+            .and_then(|tail| tail.split_once('}'))
+            .and_then(|(hex, tail)| {
+                let c = u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)?;
+                Some((c, tail))
+            });
+        match decoded {
+            Some((c, tail)) => {
+                out.push(c);
+                rest = tail;
+            }
+            None => {
+                out.push('\\');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+// In a planned path, `\xHH` stands for the raw byte HH (and `\u{d800}` for a
+// lone surrogate on Windows); everything else is ordinary text. A real
+// backslash that happens to be followed by such a sequence is kept as `\x5c`.
+fn literal_backslash(tail: &str) -> &'static str {
+    if byte_escape(tail).is_some() || lone_surrogate(tail).is_some() {
+        "\\x5c"
+    } else {
+        "\\"
+    }
+}
+
+// Names taken as written (`--raw-names`) still need that one protection
+pub fn literal_name(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    let mut rest = name;
+    while let Some(pos) = rest.find('\\') {
+        out.push_str(&rest[..pos]);
+        rest = &rest[pos + 1..];
+        out.push_str(literal_backslash(rest));
+    }
+    out.push_str(rest);
+    out
+}
+
+// `ff...` after a backslash -> the byte and the rest
+fn byte_escape(after: &str) -> Option<(u8, &str)> {
+    let hex = after.strip_prefix('x')?.get(..2)?;
+    let byte = u8::from_str_radix(hex, 16).ok()?;
+    Some((byte, &after[3..]))
+}
+
+// `u{d800}...` after a backslash -> the surrogate and the rest
+fn lone_surrogate(after: &str) -> Option<(u16, &str)> {
+    let (hex, tail) = after.strip_prefix("u{")?.split_once('}')?;
+    let unit = u16::from_str_radix(hex, 16).ok()?;
+    (0xd800..=0xdfff).contains(&unit).then_some((unit, tail))
+}
+
+// `\xHH` at the start of `text`
+pub fn starts_byte_escape(text: &str) -> bool {
+    text.strip_prefix('\\').and_then(byte_escape).is_some()
+}
+
+// Does the planned path carry name bytes that only `os_path` can express?
+pub fn has_raw_bytes(path: &str) -> bool {
+    path.match_indices('\\').any(|(pos, _)| {
+        let after = &path[pos + 1..];
+        byte_escape(after).is_some() || lone_surrogate(after).is_some()
+    })
+}
+
+// Dump a name that may not be valid UTF-8: undecodable bytes become `\xHH`
+// (Unix) and unpaired surrogates `\u{d800}` (Windows).
+pub fn escape_os_name(name: &OsStr) -> String {
+    if let Some(text) = name.to_str() {
+        return escape_name(text);
+    }
+    escape_invalid(name)
+}
+
+#[cfg(unix)]
+fn escape_invalid(name: &OsStr) -> String {
+    use std::os::unix::ffi::OsStrExt;
+    let mut out = String::new();
+    for chunk in name.as_bytes().utf8_chunks() {
+        out.push_str(&escape_name(chunk.valid()));
+        for byte in chunk.invalid() {
+            out.push_str(&format!("\\x{:02x}", byte));
+        }
+    }
+    out
+}
+
+#[cfg(windows)]
+fn escape_invalid(name: &OsStr) -> String {
+    use std::os::windows::ffi::OsStrExt;
+    let mut out = String::new();
+    for unit in char::decode_utf16(name.encode_wide()) {
+        match unit {
+            Ok(c) => out.push_str(&escape_name(c.encode_utf8(&mut [0; 4]))),
+            Err(e) => out.push_str(&format!("\\u{{{:x}}}", e.unpaired_surrogate())),
+        }
+    }
+    out
+}
+
+#[cfg(not(any(unix, windows)))]
+fn escape_invalid(name: &OsStr) -> String {
+    escape_name(&name.to_string_lossy())
+}
+
+// The real path for a planned one, decoding `\xHH` (and on Windows lone
+// surrogates). Plain UTF-8 paths come back unchanged.
+pub fn os_path(path: &str) -> PathBuf {
+    if !has_raw_bytes(path) {
+        return PathBuf::from(path);
+    }
+    PathBuf::from(decode_raw(path))
+}
+
+#[cfg(unix)]
+fn decode_raw(path: &str) -> OsString {
+    use std::os::unix::ffi::OsStringExt;
+    let mut bytes = Vec::with_capacity(path.len());
+    let mut rest = path;
+    while let Some(pos) = rest.find('\\') {
+        bytes.extend_from_slice(&rest.as_bytes()[..pos]);
+        let after = &rest[pos + 1..];
+        match byte_escape(after) {
+            Some((byte, tail)) => {
+                bytes.push(byte);
+                rest = tail;
+            }
+            None => {
+                bytes.push(b'\\');
+                rest = after;
+            }
+        }
+    }
+    bytes.extend_from_slice(rest.as_bytes());
+    OsString::from_vec(bytes)
+}
+
+#[cfg(windows)]
+fn decode_raw(path: &str) -> OsString {
+    use std::os::windows::ffi::OsStringExt;
+    let mut units: Vec<u16> = Vec::with_capacity(path.len());
+    let mut rest = path;
+    while let Some(pos) = rest.find('\\') {
+        units.extend(rest[..pos].encode_utf16());
+        let after = &rest[pos + 1..];
+        if let Some((unit, tail)) = lone_surrogate(after) {
+            units.push(unit);
+            rest = tail;
+        } else if let Some((byte, tail)) = byte_escape(after).filter(|(b, _)| b.is_ascii()) {
+            units.push(byte as u16);
+            rest = tail;
+        } else {
+            units.push(b'\\' as u16);
+            rest = after;
+        }
+    }
+    units.extend(rest.encode_utf16());
+    OsString::from_wide(&units)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn decode_raw(path: &str) -> OsString {
+    OsString::from(path)
+}
+
+// Paths this long are near Windows' MAX_PATH (260), which the plain file
+// APIs refuse; `\\?\` lifts it to about 32,767 characters
+#[cfg(windows)]
+const LONG_PATH: usize = 240;
+
+// The path to hand to the file system. On Windows a long one becomes an
+// absolute `\\?\C:\...` (or `\\?\UNC\server\share\...`) path; elsewhere,
+// and for short paths, it is returned as it is.
+#[cfg(windows)]
+pub fn long_path(path: &Path) -> PathBuf {
+    use std::{
+        os::windows::ffi::{OsStrExt, OsStringExt},
+        path::{Component, Prefix},
+    };
+    if path.as_os_str().encode_wide().count() <= LONG_PATH {
+        return path.to_path_buf();
+    }
+    // The prefix turns off normalization, so `/` and `..` are resolved first
+    let Ok(absolute) = std::path::absolute(path) else {
+        return path.to_path_buf();
+    };
+    let wide: Vec<u16> = absolute.as_os_str().encode_wide().collect();
+    let extended: Vec<u16> = match absolute.components().next() {
+        Some(Component::Prefix(prefix)) => match prefix.kind() {
+            Prefix::Disk(_) => r"\\?\".encode_utf16().chain(wide).collect(),
+            // `\\server\share` -> `\\?\UNC\server\share`
+            Prefix::UNC(..) => r"\\?\UNC".encode_utf16().chain(wide.into_iter().skip(1)).collect(),
+            _ => return absolute,
+        },
+        _ => return absolute,
+    };
+    PathBuf::from(OsString::from_wide(&extended))
+}
+
+#[cfg(not(windows))]
+pub fn long_path(path: &Path) -> PathBuf {
+    path.to_path_buf()
+}
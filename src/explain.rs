@@ -0,0 +1,62 @@
+// File: src\explain.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: `mks explain` - machine-readable parse decisions, one JSON object per line
+// License: MIT
+
+use serde_json::{json, Value};
+
+use crate::{preview, resolve_lines, tree::Tree, ParseOptions, ResolvedLine};
+
+fn explain_line(line: &ResolvedLine, tree: &Tree, text: &str) -> Value {
+    let mut record = json!({
+        "line": line.idx + 1,
+        "text": text,
+        "marker": line.trace.marker,
+        "chars_before_name": line.trace.chars_before_name,
+        "stripped": line.trace.stripped,
+    });
+
+    match &line.placed {
+        Err(reason) => {
+            record["status"] = json!("skipped");
+            record["reason"] = json!(reason);
+            // Not worth echoing a whole blob back
+            if *reason == "line too long" {
+                record["text"] = json!(preview(text));
+                record["length"] = json!(text.len());
+            }
+        }
+        Ok(placed) => {
+            let parent = placed.parent.map(|p| tree.path(p)).unwrap_or_default();
+            let paths: Vec<String> = placed.ids.iter().map(|&id| tree.path(id)).collect();
+            // Only the first name of `a & b` can hold children
+            let children = tree.node(placed.ids[0]).children.len();
+            record["status"] = json!("node");
+            record["indent"] = json!(placed.node.indent);
+            record["width"] = json!(placed.node.width);
+            record["clamped"] = json!(placed.clamped);
+            record["name"] = json!(placed.node.name);
+            record["names"] = json!(placed.names);
+            record["kind"] = json!(placed.node.kind.label());
+            record["kind_guessed"] = json!(placed.kind_guessed);
+            record["executable"] = json!(placed.node.executable);
+            record["attrs"] = json!(placed.node.attrs);
+            record["annotations"] = json!(placed.node.annotations);
+            record["contents"] = json!(placed.node.contents);
+            record["parent"] = json!(parent);
+            record["paths"] = json!(paths);
+            record["children"] = json!(children);
+        }
+    }
+    record
+}
+
+pub fn run(lines: &[String], popts: &ParseOptions) -> Result<(), Box<dyn std::error::Error>> {
+    let resolved = resolve_lines(lines, popts);
+    for line in &resolved.lines {
+        let record = explain_line(line, &resolved.tree, &lines[line.idx]);
+        println!("{}", serde_json::to_string(&record)?);
+    }
+    Ok(())
+}
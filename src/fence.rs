@@ -0,0 +1,90 @@
+// File: src\fence.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: Pick the tree out of a Markdown document (READMEs, chat answers)
+// License: MIT
+
+pub struct Block {
+    // 1-based position among the document's blocks
+    pub number: usize,
+    // Info string after the opening fence (`text`, `json`, ...), lowercased
+    pub lang: String,
+    // Index of the first line inside the fence
+    pub start: usize,
+    pub lines: Vec<String>,
+}
+
+// "```" or "~~~" (or longer), indented at most three spaces
+fn opening(line: &str) -> Option<(char, usize, &str)> {
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    if indent > 3 {
+        return None;
+    }
+    let rest = &line[indent..];
+    let marker = rest.chars().next().filter(|c| matches!(c, '`' | '~'))?;
+    let len = rest.chars().take_while(|&c| c == marker).count();
+    let info = rest[len..].trim();
+    // A backtick fence cannot have backticks in its info string
+    (len >= 3 && !(marker == '`' && info.contains('`'))).then_some((marker, len, info))
+}
+
+fn closes(line: &str, marker: char, len: usize) -> bool {
+    let trimmed = line.trim();
+    line.len() - line.trim_start_matches(' ').len() <= 3 && trimmed.len() >= len && trimmed.chars().all(|c| c == marker)
+}
+
+// Every fenced block, in order; an unclosed fence runs to the end
+pub fn blocks(content: &str) -> Vec<Block> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut found = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let Some((marker, len, info)) = opening(lines[i]) else {
+            i += 1;
+            continue;
+        };
+        let start = i + 1;
+        let end = (start..lines.len()).find(|&j| closes(lines[j], marker, len)).unwrap_or(lines.len());
+        found.push(Block {
+            number: found.len() + 1,
+            lang: info.split_whitespace().next().unwrap_or("").to_lowercase(),
+            start,
+            lines: lines[start..end].iter().map(|l| l.to_string()).collect(),
+        });
+        i = end + 1;
+    }
+    found
+}
+
+// `--fence-index N`, counted from 1
+pub fn index(value: Option<&str>) -> Result<Option<usize>, Box<dyn std::error::Error>> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    match value.trim().parse::<usize>() {
+        Ok(n) if n > 0 => Ok(Some(n)),
+        _ => Err(t!("--fence-index expects a block number from 1, got '{}'", value).into()),
+    }
+}
+
+// The block to read from a Markdown document: number `index` (1-based) when
+// given, else the first one `wanted` accepts. None when no block qualifies,
+// so the whole input is read as before.
+pub fn pick(content: &str, index: Option<usize>, wanted: impl Fn(&Block) -> bool) -> Result<Option<Block>, Box<dyn std::error::Error>> {
+    let mut found = blocks(content);
+    if let Some(n) = index {
+        if n > found.len() {
+            return Err(t!("--fence-index {}: the input has {} fenced block(s)", n, found.len()).into());
+        }
+        return Ok(Some(found.swap_remove(n - 1)));
+    }
+    Ok(found.into_iter().find(|block| wanted(block)))
+}
+
+// The block's lines at their place in the document, with blank lines
+// standing in for everything else, so line numbers in messages still match
+pub fn in_place(block: &Block) -> Vec<String> {
+    let mut lines = vec![String::new(); block.start];
+    lines.extend(block.lines.iter().cloned());
+    lines
+}
@@ -0,0 +1,94 @@
+// File: src\fetch.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: File contents downloaded at creation time (`spec.yaml <= https://...`), and trees read from a URL
+// License: MIT
+
+use std::time::Duration;
+
+use sha2::{Digest, Sha256};
+
+use crate::stats;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Limits {
+    // `--max-download <size>`: larger bodies fail the entry
+    pub max_bytes: u64,
+    // `--download-timeout <secs>`: for the whole request, body included
+    pub timeout: Duration,
+    // `--insecure`: accept any TLS certificate
+    pub insecure: bool,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            max_bytes: 10 * 1024 * 1024,
+            timeout: Duration::from_secs(30),
+            insecure: false,
+        }
+    }
+}
+
+// `--max-download 2M`, `--download-timeout 10`, `--insecure`
+pub fn limits(max_download: Option<&str>, download_timeout: Option<&str>, insecure: bool) -> Result<Limits, Box<dyn std::error::Error>> {
+    let mut limits = Limits { insecure, ..Limits::default() };
+    if let Some(value) = max_download {
+        limits.max_bytes = stats::parse_size(value, false)
+            .filter(|&bytes| bytes > 0)
+            .ok_or_else(|| t!("--max-download expects a size such as 512K or 10M, got '{}'", value))?;
+    }
+    if let Some(value) = download_timeout {
+        let secs: f64 = value
+            .parse()
+            .ok()
+            .filter(|&secs: &f64| secs > 0.0 && secs.is_finite())
+            .ok_or_else(|| t!("--download-timeout expects seconds, got '{}'", value))?;
+        limits.timeout = Duration::from_secs_f64(secs);
+    }
+    Ok(limits)
+}
+
+// `[sha256=<hex>]` next to the source
+fn verify(url: &str, bytes: &[u8], expected: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let actual: String = Sha256::digest(bytes).iter().map(|b| format!("{:02x}", b)).collect();
+    if !actual.eq_ignore_ascii_case(expected.trim()) {
+        return Err(t!("{}: sha256 is {}, expected {}", url, actual, expected.trim()).into());
+    }
+    Ok(())
+}
+
+pub fn is_url(text: &str) -> bool {
+    text.starts_with("https://") || text.starts_with("http://")
+}
+
+// The body of `url`, within the limits and matching the checksum if one is given
+pub fn download(url: &str, sha256: Option<&str>, limits: Limits) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    if !is_url(url) {
+        return Err(t!("{}: only http:// and https:// sources are supported", url).into());
+    }
+    let tls = ureq::tls::TlsConfig::builder().disable_verification(limits.insecure).build();
+    let agent: ureq::Agent = ureq::Agent::config_builder().timeout_global(Some(limits.timeout)).tls_config(tls).build().into();
+    let bytes = agent
+        .get(url)
+        .call()
+        .and_then(|mut response| response.body_mut().with_config().limit(limits.max_bytes).read_to_vec())
+        .map_err(|e| match e {
+            ureq::Error::BodyExceedsLimit(max) => t!("{}: larger than {} bytes (--max-download)", url, max),
+            ureq::Error::Timeout(_) => t!("{}: no complete response within {}s (--download-timeout)", url, limits.timeout.as_secs_f64()),
+            e => t!("{}: {}", url, e),
+        })?;
+    if let Some(expected) = sha256 {
+        verify(url, &bytes, expected)?;
+    }
+    Ok(bytes)
+}
+
+// `mks https://.../tree.txt`: the tree itself, as text
+pub fn tree(url: &str, limits: Limits) -> Result<String, Box<dyn std::error::Error>> {
+    if limits.insecure {
+        eprintln!("⚠️ {}", t!("{}: TLS certificates are not checked (--insecure)", url));
+    }
+    let bytes = download(url, None, limits)?;
+    String::from_utf8(bytes).map_err(|_| t!("{}: the tree is not UTF-8 text", url).into())
+}
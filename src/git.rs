@@ -0,0 +1,51 @@
+// File: src\git.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: `--git-init` - make the created structure a repository with a first commit
+// License: MIT
+
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+pub const DEFAULT_MESSAGE: &str = "Initial commit";
+
+// The folder to turn into a repository: the tree's single root folder, or
+// the target itself when the tree has several top-level entries
+fn root(base: &Path, paths: &[&str]) -> PathBuf {
+    let first = paths.first().and_then(|path| path.split('/').next()).unwrap_or("");
+    let single = !first.is_empty() && paths.iter().all(|path| *path == first || path.starts_with(&format!("{}/", first)));
+    match single {
+        true if base.join(first).is_dir() => base.join(first),
+        _ => base.to_path_buf(),
+    }
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<(), Box<dyn std::error::Error>> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| t!("--git-init could not run git: {}", e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(t!("git {} failed in '{}': {}", args[0], dir.display(), stderr.trim()).into());
+    }
+    Ok(())
+}
+
+// `git init`, `git add -A` and `git commit` in the created root. A folder
+// that is already a repository is left as it is.
+pub fn init(base: &Path, paths: &[&str], message: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let dir = root(base, paths);
+    if dir.join(".git").exists() {
+        status!("🌱 {}", t!("{} is already a git repository; --git-init left it as it is", dir.display()));
+        return Ok(());
+    }
+    git(&dir, &["init", "--quiet"])?;
+    git(&dir, &["add", "--all"])?;
+    git(&dir, &["commit", "--quiet", "--allow-empty", "--message", message])?;
+    status!("🌱 {}", t!("Initialized a git repository in {} with commit \"{}\"", dir.display(), message));
+    Ok(())
+}
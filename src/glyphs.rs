@@ -0,0 +1,100 @@
+// File: src\glyphs.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: Box-drawing glyph classes and display widths for tree indentation
+// License: MIT
+
+use unicode_width::UnicodeWidthChar;
+
+// `│`, and the heavy, double and dashed variants IDE exports use
+pub fn is_vertical(c: char) -> bool {
+    matches!(c, '│' | '┃' | '║' | '┆' | '┇' | '┊' | '┋')
+}
+
+// `├` and friends: `┣` (heavy), `┠` (heavy stem, light branch), `┝`, `╟`, `╠`
+fn is_branch(c: char) -> bool {
+    matches!(c, '├' | '┣' | '┠' | '┝' | '┞' | '┟' | '╞' | '╟' | '╠')
+}
+
+fn is_corner(c: char) -> bool {
+    matches!(c, '└' | '┗' | '┖' | '┕' | '╘' | '╙' | '╚' | '╰')
+}
+
+fn is_horizontal(c: char) -> bool {
+    matches!(c, '─' | '━' | '═' | '╌' | '╍' | '┄' | '┅' | '┈' | '┉')
+}
+
+// Junctions that open a subtree (`npm ls` prints `├─┬ name`)
+fn is_tee(c: char) -> bool {
+    matches!(c, '┬' | '┳' | '┰' | '┯' | '╤' | '╥' | '╦' | '┼' | '╋' | '╂' | '┿' | '╪' | '╫' | '╬')
+}
+
+// Any glyph that draws tree structure
+pub fn is_box_glyph(c: char) -> bool {
+    is_vertical(c) || is_branch(c) || is_corner(c) || is_horizontal(c) || is_tee(c)
+}
+
+// Indentation that is not a glyph: spaces, tabs, the ideographic space and the
+// full-width dots some Asian tree tools pad with. An ASCII '.' is never
+// indentation, it starts dotfiles.
+fn is_filler(c: char) -> bool {
+    matches!(c, ' ' | '\t' | '\u{3000}' | '・' | '．' | '･')
+}
+
+// Everything that may stand before a name on a tree line
+pub fn is_indent(c: char) -> bool {
+    is_box_glyph(c) || is_filler(c) || c == '|'
+}
+
+// The connector in front of a name
+pub struct Marker {
+    // Byte offset where the name starts
+    pub name_start: usize,
+    // Drawn with a corner, so the entry is the last child
+    pub last: bool,
+    // A single horizontal (`├─name`): the glyphs were laid out as double
+    // width, as Windows `tree` does on Chinese, Japanese and Korean consoles
+    pub compact: bool,
+}
+
+// A branch or corner glyph followed by horizontals, then optional padding:
+// `├── `, `┣━━ `, `┠─`, `├───` (Windows `tree`) or `├─┬ ` (`npm ls`)
+pub fn find_marker(line: &str) -> Option<Marker> {
+    let mut chars = line.char_indices().peekable();
+    while let Some((_, c)) = chars.next() {
+        if !is_branch(c) && !is_corner(c) {
+            continue;
+        }
+        let mut horizontals = 0;
+        while let Some(&(_, next)) = chars.peek() {
+            if is_horizontal(next) {
+                horizontals += 1;
+            } else if !is_tee(next) || horizontals == 0 {
+                break;
+            }
+            chars.next();
+        }
+        if horizontals == 0 {
+            continue;
+        }
+        while chars.peek().is_some_and(|&(_, next)| is_filler(next)) {
+            chars.next();
+        }
+        let name_start = chars.peek().map_or(line.len(), |&(pos, _)| pos);
+        return Some(Marker { name_start, last: is_corner(c), compact: horizontals == 1 });
+    }
+    None
+}
+
+// Display columns of the text in front of a name. Tabs advance to the next
+// multiple of `tab`; full-width padding takes two columns, and so do box
+// glyphs in a compact tree. Anything else (icons, leading words) counts one
+// column per character, as it always has.
+pub fn columns(prefix: &str, compact: bool, tab: usize) -> usize {
+    prefix.chars().fold(0, |col, c| match c {
+        '\t' => (col / tab + 1) * tab,
+        c if compact && is_box_glyph(c) => col + 2,
+        c if is_filler(c) => col + c.width().unwrap_or(1),
+        _ => col + 1,
+    })
+}
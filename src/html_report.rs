@@ -0,0 +1,141 @@
+// File: src\html_report.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: `--report-html out.html` - the dry-run preview as a standalone, collapsible page
+// License: MIT
+
+use std::{fmt::Write as _, fs, path::Path};
+
+use chrono::Local;
+
+use crate::{
+    dry_run::{self, OnDisk},
+    i18n::{self, Lang},
+    NodeKind, PlannedEntry,
+};
+
+// Inline, so the page can be mailed or attached to a ticket on its own
+const STYLE: &str = "
+body { font: 14px/1.5 system-ui, sans-serif; margin: 2em; color: #222; }
+h1 { font-size: 1.4em; }
+code { background: #f3f3f3; padding: 0 .3em; border-radius: 3px; }
+.counts span { display: inline-block; margin-right: 1.2em; }
+.tree { font-family: ui-monospace, monospace; margin-top: 1.5em; }
+.tree ul { list-style: none; margin: 0; padding-left: 1.4em; border-left: 1px dotted #bbb; }
+.tree > ul { border-left: none; padding-left: 0; }
+summary { cursor: pointer; }
+.new { color: #1a7f37; }
+.existing { color: #6e7781; }
+.conflict { color: #cf222e; font-weight: bold; }
+.note { font-size: .85em; margin-left: .6em; }
+footer { margin-top: 2em; font-size: .85em; color: #6e7781; }
+";
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum State {
+    Existing,
+    New,
+    Conflict,
+}
+
+impl State {
+    fn class(self) -> &'static str {
+        match self {
+            State::Existing => "existing",
+            State::New => "new",
+            State::Conflict => "conflict",
+        }
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+// The worst state over every target, with what is in the way for conflicts
+fn state(entry: &PlannedEntry, bases: &[&Path]) -> (State, String) {
+    let mut worst = (State::Existing, t!("exists").to_string());
+    for base in bases {
+        let found = match dry_run::on_disk(&dry_run::resolve(base, &entry.path), entry.kind) {
+            OnDisk::Missing => (State::New, t!("new").to_string()),
+            OnDisk::Matches => (State::Existing, t!("exists").to_string()),
+            OnDisk::Conflict(kind) => (State::Conflict, t!("a {} is in the way", i18n::tr(kind.label()))),
+        };
+        if found.0 > worst.0 {
+            worst = found;
+        }
+    }
+    worst
+}
+
+fn node(i: usize, plan: &[PlannedEntry], children: &[Vec<usize>], states: &[(State, String)], out: &mut String) {
+    let entry = &plan[i];
+    let name = escape(entry.path.rsplit('/').next().unwrap_or(&entry.path));
+    let (state, note) = &states[i];
+    let suffix = if entry.kind == NodeKind::Dir { "/" } else { "" };
+    let label = format!(
+        "<span class=\"{}\" title=\"{}\">{}{}</span><span class=\"note {}\">{}</span>",
+        state.class(),
+        escape(&entry.path),
+        name,
+        suffix,
+        state.class(),
+        escape(note)
+    );
+    if children[i].is_empty() {
+        let _ = writeln!(out, "<li>{}</li>", label);
+        return;
+    }
+    let _ = writeln!(out, "<li><details open><summary>{}</summary><ul>", label);
+    for &child in &children[i] {
+        node(child, plan, children, states, out);
+    }
+    out.push_str("</ul></details></li>\n");
+}
+
+// Write the page for a plan that would go into `base`, or into every one of
+// `targets` when it is None
+pub fn write(plan: &[PlannedEntry], base: Option<&Path>, targets: &[String], file: &str, posix: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let bases: Vec<&Path> = match base {
+        Some(base) => vec![base],
+        None => targets.iter().map(Path::new).collect(),
+    };
+    let states: Vec<(State, String)> = plan.iter().map(|entry| state(entry, &bases)).collect();
+    let count = |wanted: State| states.iter().filter(|(state, _)| *state == wanted).count();
+    let files = plan.iter().filter(|e| e.kind == NodeKind::File).count();
+    let dirs = plan.iter().filter(|e| e.kind == NodeKind::Dir).count();
+
+    let mut out = String::new();
+    let lang = if i18n::lang() == Lang::Id { "id" } else { "en" };
+    let _ = writeln!(out, "<!DOCTYPE html>\n<html lang=\"{}\">\n<head>\n<meta charset=\"utf-8\">", lang);
+    let _ = writeln!(out, "<title>{}</title>\n<style>{}</style>\n</head>\n<body>", escape(&t!("mks plan ({} entries)", plan.len())), STYLE);
+    let _ = writeln!(out, "<h1>📋 {}</h1>", escape(&t!("mks plan ({} entries)", plan.len())));
+    for base in &bases {
+        let shown = dry_run::shown(&std::path::absolute(base)?, posix);
+        let _ = writeln!(out, "<p>📂 {} <code>{}</code></p>", escape(t!("Target directory:")), escape(&shown));
+    }
+    let _ = writeln!(
+        out,
+        "<p class=\"counts\"><span>{}</span><span class=\"new\">{}</span><span class=\"existing\">{}</span><span class=\"conflict\">{}</span></p>",
+        escape(&t!("{} file(s), {} dir(s)", files, dirs)),
+        escape(&t!("{} new", count(State::New))),
+        escape(&t!("{} already exist", count(State::Existing))),
+        escape(&t!("{} in conflict", count(State::Conflict)))
+    );
+
+    out.push_str("<div class=\"tree\"><ul>\n");
+    let (roots, children) = dry_run::layout(plan);
+    for root in roots {
+        node(root, plan, &children, &states, &mut out);
+    }
+    out.push_str("</ul></div>\n");
+    let generated = t!("Generated by mks {} on {}", env!("CARGO_PKG_VERSION"), Local::now().format("%Y-%m-%d %H:%M"));
+    let _ = writeln!(out, "<footer>{}</footer>\n</body>\n</html>", escape(&generated));
+
+    fs::write(file, out).map_err(|e| t!("could not write '{}': {}", file, e))?;
+    Ok(())
+}
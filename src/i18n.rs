@@ -0,0 +1,459 @@
+// File: src\i18n.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: Gettext-style message catalogs for user-facing output
+// License: MIT
+
+use std::{env, fmt::Display, sync::OnceLock};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Lang {
+    En,
+    Id,
+}
+
+static LANG: OnceLock<Lang> = OnceLock::new();
+
+impl Lang {
+    fn from_code(code: &str) -> Option<Lang> {
+        let code = code.trim().to_lowercase();
+        // "in" is the legacy ISO 639 code for Indonesian
+        if code.starts_with("id") || code.starts_with("in") {
+            Some(Lang::Id)
+        } else if code.starts_with("en") || code == "c" || code == "posix" {
+            Some(Lang::En)
+        } else {
+            None
+        }
+    }
+}
+
+// Pick the language from `--lang`, then the usual locale variables.
+pub fn init(flag: Option<&str>) {
+    let from_env = || {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Lang::from_code(&value))
+    };
+    let lang = flag.and_then(Lang::from_code).or_else(from_env).unwrap_or(Lang::En);
+    let _ = LANG.set(lang);
+}
+
+pub fn lang() -> Lang {
+    *LANG.get().unwrap_or(&Lang::En)
+}
+
+// Translate an English message id; unknown ids fall back to English.
+pub fn tr(msgid: &'static str) -> &'static str {
+    match lang() {
+        Lang::En => msgid,
+        Lang::Id => indonesian(msgid).unwrap_or(msgid),
+    }
+}
+
+// Substitute positional `{}` placeholders in a translated template.
+pub fn fill(template: &str, args: &[&dyn Display]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut args = args.iter();
+    let mut rest = template;
+    while let Some(pos) = rest.find("{}") {
+        out.push_str(&rest[..pos]);
+        if let Some(arg) = args.next() {
+            out.push_str(&arg.to_string());
+        }
+        rest = &rest[pos + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+// Crate-private: `#[macro_use] mod i18n;` comes first in lib.rs, so every
+// module below it sees `t!`
+macro_rules! t {
+    ($msgid:literal) => {
+        $crate::i18n::tr($msgid)
+    };
+    ($msgid:literal, $($arg:expr),+ $(,)?) => {
+        $crate::i18n::fill($crate::i18n::tr($msgid), &[$(&$arg as &dyn std::fmt::Display),+])
+    };
+}
+
+fn indonesian(msgid: &str) -> Option<&'static str> {
+    Some(match msgid {
+        "file" => "berkas",
+        "clipboard" => "papan klip",
+        "Clipboard" => "Papan klip",
+        "Terminal" => "Terminal",
+        "Filesystem" => "Sistem berkas",
+        "Configuration" => "Konfigurasi",
+        "running under WSL" => "berjalan di WSL",
+        "mks talks to the clipboard through X11: enable XWayland, or save the tree to a file and run 'mks tree.txt'" => "mks mengakses papan klip melalui X11: aktifkan XWayland, atau simpan pohon ke berkas dan jalankan 'mks tree.txt'",
+        "update to a WSL version with WSLg (DISPLAY=:0), or save the tree to a file and run 'mks tree.txt'" => "perbarui ke versi WSL dengan WSLg (DISPLAY=:0), atau simpan pohon ke berkas dan jalankan 'mks tree.txt'",
+        "no graphical session: save the tree to a file and run 'mks tree.txt'" => "tidak ada sesi grafis: simpan pohon ke berkas dan jalankan 'mks tree.txt'",
+        "DISPLAY is not set, the clipboard cannot be reached" => "DISPLAY tidak diatur, papan klip tidak dapat dijangkau",
+        "Wayland session, using the XWayland clipboard" => "sesi Wayland, memakai papan klip XWayland",
+        "clipboard backend is available" => "backend papan klip tersedia",
+        "clipboard backend is unavailable: {}" => "backend papan klip tidak tersedia: {}",
+        "save the tree to a file and run 'mks tree.txt'" => "simpan pohon ke berkas dan jalankan 'mks tree.txt'",
+        "output is not a terminal, glyphs are not checked" => "keluaran bukan terminal, glyph tidak diperiksa",
+        "Windows Terminal renders Unicode and emoji" => "Windows Terminal menampilkan Unicode dan emoji",
+        "the classic console may show tree lines and emoji as '?'" => "konsol klasik mungkin menampilkan garis pohon dan emoji sebagai '?'",
+        "use Windows Terminal, or run 'chcp 65001' first" => "gunakan Windows Terminal, atau jalankan 'chcp 65001' dahulu",
+        "UTF-8 locale ({})" => "locale UTF-8 ({})",
+        "locale '{}' is not UTF-8, tree lines and emoji may be garbled" => "locale '{}' bukan UTF-8, garis pohon dan emoji mungkin rusak",
+        "export LANG=en_US.UTF-8 (or another UTF-8 locale)" => "export LANG=id_ID.UTF-8 (atau locale UTF-8 lain)",
+        "the Linux console cannot draw emoji" => "konsol Linux tidak dapat menampilkan emoji",
+        "use a terminal emulator for emoji output" => "gunakan emulator terminal untuk keluaran emoji",
+        "cannot read the current directory: {}" => "tidak dapat membaca direktori saat ini: {}",
+        "cd into an existing directory" => "pindah (cd) ke direktori yang ada",
+        "current directory: {}" => "direktori saat ini: {}",
+        "case-sensitive: 'README' and 'readme' are different entries" => "peka huruf besar/kecil: 'README' dan 'readme' adalah entri berbeda",
+        "case-insensitive: names differing only in case end up as one entry" => "tidak peka huruf besar/kecil: nama yang hanya berbeda huruf besar/kecil menjadi satu entri",
+        "the current directory is not writable: {}" => "direktori saat ini tidak dapat ditulisi: {}",
+        "run mks in a directory you own, or pass -o <dir>" => "jalankan mks di direktori milik Anda, atau berikan -o <dir>",
+        "limits: {} bytes per name, {} per path ({} used by this directory)" => "batas: {} byte per nama, {} per path ({} dipakai direktori ini)",
+        "only {} bytes of path left for the structure" => "hanya tersisa {} byte path untuk struktur",
+        "create the structure closer to the filesystem root" => "buat struktur lebih dekat ke root sistem berkas",
+        "set HOME (or XDG_CONFIG_HOME)" => "atur HOME (atau XDG_CONFIG_HOME)",
+        "{} preset(s) in {}" => "{} preset di {}",
+        "no preset directory yet; put <name>.txt files in {} to use --preset" => "belum ada direktori preset; taruh berkas <nama>.txt di {} untuk memakai --preset",
+        "No problems found" => "Tidak ada masalah",
+        "config file: {}" => "berkas konfigurasi: {}",
+        "no config file at {}" => "tidak ada berkas konfigurasi di {}",
+        "fix the TOML syntax or remove the file" => "perbaiki sintaks TOML atau hapus berkasnya",
+        "unknown icon theme '{}' (expected one of: {})" => "tema ikon tidak dikenal '{}' (harus salah satu dari: {})",
+        "{} problem(s) found" => "{} masalah ditemukan",
+        "primary selection" => "seleksi primer",
+        "directory" => "direktori",
+        "socket" => "soket",
+        "Read from {} ({} lines)" => "Dibaca dari {} ({} baris)",
+        "Loaded preset '{}' ({} entries)" => "Preset '{}' dimuat ({} entri)",
+        "Project name: {}" => "Nama proyek: {}",
+        "Project name:" => "Nama proyek:",
+        "cannot infer the project name: pass -o <dir> or name the root folder" => "nama proyek tidak dapat ditentukan: berikan -o <dir> atau beri nama folder root",
+        "invalid project name '{}': {}" => "nama proyek tidak valid '{}': {}",
+        "cannot locate the config directory (HOME is not set)" => "direktori konfigurasi tidak ditemukan (HOME tidak diatur)",
+        "front matter is missing its closing '+++'" => "front matter tidak memiliki penutup '+++'",
+        "preset inheritance cycle: {}" => "siklus pewarisan preset: {}",
+        "cannot read preset '{}': {}" => "tidak dapat membaca preset '{}': {}",
+        "Input is empty or invalid." => "Masukan kosong atau tidak valid.",
+        "Debug mode enabled" => "Mode debug diaktifkan",
+        "Creating structure..." => "Membuat struktur...",
+        "Error: {}" => "Galat: {}",
+        "Done!" => "Selesai!",
+        "{} path(s) were created through symlinks:" => "{} jalur dibuat melalui symlink:",
+        "{} (via {})" => "{} (melalui {})",
+        "'{}' would be created through symlink '{}' (--no-follow)" => {
+            "'{}' akan dibuat melalui symlink '{}' (--no-follow)"
+        }
+        "a {} cannot be recreated from a listing" => "{} tidak dapat dibuat ulang dari daftar",
+        "fifos are not supported on this platform" => "fifo tidak didukung di platform ini",
+        "sockets are not supported on this platform" => "soket tidak didukung di platform ini",
+        "{} created, {} already existed, {} replaced, {} skipped, {} failed" => "{} dibuat, {} sudah ada, {} diganti, {} dilewati, {} gagal",
+        "Only {} of {} planned entries exist on disk:" => "Hanya {} dari {} entri yang direncanakan ada di disk:",
+        "parent '{}' was not created" => "induk '{}' tidak dibuat",
+        "--metrics-addr and --metrics-file need --watch" => "--metrics-addr dan --metrics-file memerlukan --watch",
+        "{}: plan version {} is newer than {}; {} unknown field(s) ignored" => "{}: versi plan {} lebih baru dari {}; {} kolom tak dikenal diabaikan",
+    "line {}: '{}' renamed to '{}' (--sanitize)" => "baris {}: '{}' diganti namanya menjadi '{}' (--sanitize)",
+    "'{}' is renamed to '{}' (--sanitize)" => "'{}' diganti namanya menjadi '{}' (--sanitize)",
+    "{} entries matched by {} were left out:" => "{} entri yang cocok dengan {} dilewati:",
+    "--multi-sep expects a separator such as '&' or ',', got '{}'" => "--multi-sep mengharapkan pemisah seperti '&' atau ',', didapat '{}'",
+    "Took {}" => "Memakan waktu {}",
+    "--interval expects a duration such as 1s or 500ms, got '{}'" => "--interval mengharapkan durasi seperti 1s atau 500ms, didapat '{}'",
+    "mks clipboard --watch asks before creating; without a terminal, pass --yes" => "mks clipboard --watch bertanya sebelum membuat; tanpa terminal, gunakan --yes",
+    "Watching the clipboard, creating into {}; Ctrl-C to stop" => "Memantau clipboard, membuat ke {}; Ctrl-C untuk berhenti",
+    "A tree was copied ({} lines)" => "Sebuah pohon disalin ({} baris)",
+    "Waiting for the next tree..." => "Menunggu pohon berikutnya...",
+    "Skipped; nothing was created" => "Dilewati; tidak ada yang dibuat",
+    "{} file(s) start with their description as a comment" => "{} file diawali dengan deskripsinya sebagai komentar",
+    "line {}: '{}' is a file but has children (line {} is indented below it); write it as '{}/' or pass --auto-dir" => "baris {}: '{}' adalah file tetapi memiliki anak (baris {} menjorok di bawahnya); tulis sebagai '{}/' atau gunakan --auto-dir",
+    "line {}: folder '{}' is already listed at line {}; the two are merged" => "baris {}: folder '{}' sudah tercantum di baris {}; keduanya digabung",
+    "line {}: '{}' is already listed at line {}; the later line wins" => "baris {}: '{}' sudah tercantum di baris {}; baris yang belakangan menang",
+    "{} path(s) are listed more than once:" => "{} path tercantum lebih dari sekali:",
+    "{} duplicate path(s) in the tree (--strict)" => "{} path ganda di dalam pohon (--strict)",
+    "Pass --strict to stop on duplicate paths." => "Gunakan --strict untuk berhenti pada path ganda.",
+    "--jobs expects a number of threads, got '{}'" => "--jobs mengharapkan jumlah thread, didapat '{}'",
+    "--jobs cannot be combined with -i, which asks one question at a time" => "--jobs tidak dapat digabung dengan -i, yang bertanya satu per satu",
+    "[after=...] sets an order, so --jobs {} creates the entries one by one" => "[after=...] menetapkan urutan, jadi --jobs {} membuat entri satu per satu",
+        "'{}' is not a template name; use letters, digits, '-' and '_'" => "'{}' bukan nama templat; gunakan huruf, angka, '-' dan '_'",
+        "the tree for '{}' has no entries" => "pohon untuk '{}' tidak berisi entri",
+        "template '{}' already exists; --force replaces it" => "templat '{}' sudah ada; --force menggantinya",
+        "Saved template '{}' to {}" => "Templat '{}' disimpan ke {}",
+        "Apply it with `mks template apply {} -C <dir>`" => "Terapkan dengan `mks template apply {} -C <dir>`",
+        "{} lines, extends {}" => "{} baris, memperluas {}",
+        "{} lines" => "{} baris",
+        "unreadable: {}" => "tidak terbaca: {}",
+        "No templates in {}" => "Tidak ada templat di {}",
+        "{} template(s) in {}:" => "{} templat di {}:",
+        "Deleted template '{}'" => "Templat '{}' dihapus",
+        "there is no template '{}' (see `mks template list`)" => "tidak ada templat '{}' (lihat `mks template list`)",
+        "could not delete '{}': {}" => "tidak dapat menghapus '{}': {}",
+        "`mks template apply` names the template itself; drop --preset" => "`mks template apply` sudah menyebut templatnya; hapus --preset",
+        "--git-init works on one target folder; it cannot be combined with --into, --into-archive, --to-zip, --to-tar or --emit-script" => "--git-init bekerja pada satu folder tujuan; tidak dapat digabung dengan --into, --into-archive, --to-zip, --to-tar atau --emit-script",
+        "--git-init could not run git: {}" => "--git-init tidak dapat menjalankan git: {}",
+        "git {} failed in '{}': {}" => "git {} gagal di '{}': {}",
+        "{} is already a git repository; --git-init left it as it is" => "{} sudah merupakan repositori git; --git-init membiarkannya",
+        "Initialized a git repository in {} with commit \"{}\"" => "Repositori git dibuat di {} dengan commit \"{}\"",
+        "--to-zip and --to-tar cannot be combined" => "--to-zip dan --to-tar tidak dapat digabung",
+        "--to-zip and --to-tar cannot be combined with -C, --into, --into-archive or --emit-script" => "--to-zip dan --to-tar tidak dapat digabung dengan -C, --into, --into-archive atau --emit-script",
+        "--output-format=json and an archive written to stdout cannot share it" => "--output-format=json dan arsip yang ditulis ke stdout tidak dapat berbagi stdout",
+        "'{}' already exists; remove it first, or add to a zip with --into-archive" => "'{}' sudah ada; hapus dulu, atau tambahkan ke zip dengan --into-archive",
+        "a {} cannot be stored in an archive" => "{} tidak dapat disimpan dalam arsip",
+        "'{}' is too long for a tar archive" => "'{}' terlalu panjang untuk arsip tar",
+        "the link target of '{}' is too long for a tar archive" => "tujuan tautan '{}' terlalu panjang untuk arsip tar",
+        "Wrote {}" => "Menulis {}",
+        "{}: TLS certificates are not checked (--insecure)" => "{}: sertifikat TLS tidak diperiksa (--insecure)",
+        "{}: the tree is not UTF-8 text" => "{}: pohon bukan teks UTF-8",
+        "could not read stdin: {}" => "tidak dapat membaca stdin: {}",
+        "stdin is empty" => "stdin kosong",
+        "--stdin reads the tree from standard input; the file '{}' cannot be given too" => "--stdin membaca pohon dari masukan standar; berkas '{}' tidak dapat diberikan juga",
+        "{} has no entries yet" => "{} belum berisi entri",
+        "nothing new" => "tidak ada yang baru",
+        "creating {}" => "membuat {}",
+        "Watching {}, creating into {}; Ctrl-C to stop" => "Memantau {}, membuat di {}; Ctrl-C untuk berhenti",
+        "--output-format expects text or json, got '{}'" => "--output-format mengharapkan text atau json, didapat '{}'",
+        "--output-format=json reports a run that creates entries; it cannot be combined with --dry-run, --report-html, --emit-script or mks plan" => "--output-format=json melaporkan proses yang membuat entri; tidak dapat digabung dengan --dry-run, --report-html, --emit-script atau mks plan",
+        "inside a link; it lives at the link's target" => "di dalam tautan; isinya ada di tujuan tautan",
+        "'{}' already links to '{}'" => "'{}' sudah menaut ke '{}'",
+        "symlinks are not supported on this platform" => "symlink tidak didukung di platform ini",
+        "Wrote {} description(s) to {}" => "{} deskripsi ditulis ke {}",
+        "Path" => "Jalur",
+        "Description" => "Deskripsi",
+        "could not read '{}': {}" => "tidak dapat membaca '{}': {}",
+        "placeholder for .{}: could not read '{}': {}" => "placeholder untuk .{}: tidak bisa membaca '{}': {}",
+        "--merge keeps existing files; it cannot be combined with --on-conflict={}" => "--merge mempertahankan file yang ada; tidak bisa digabung dengan --on-conflict={}",
+        "Merged: {} created, {} already there and left as they were, {} conflicting, {} skipped, {} failed" => "Digabung: {} dibuat, {} sudah ada dan dibiarkan, {} bentrok, {} dilewati, {} gagal",
+        "{} pattern '{}': {}" => "pola {} '{}': {}",
+        "--include '{}' matches nothing in the tree" => "--include '{}' tidak cocok dengan apa pun di pohon",
+        "Kept {} of {} entries (--include/--exclude)" => "{} dari {} entri dipertahankan (--include/--exclude)",
+        "new" => "baru",
+        "Target directory:" => "Direktori target:",
+        "mks plan ({} entries)" => "rencana mks ({} entri)",
+        "{} file(s), {} dir(s)" => "{} berkas, {} direktori",
+        "{} new" => "{} baru",
+        "{} already exist" => "{} sudah ada",
+        "{} in conflict" => "{} bentrok",
+        "Generated by mks {} on {}" => "Dibuat oleh mks {} pada {}",
+        "could not write '{}': {}" => "tidak dapat menulis '{}': {}",
+        "Wrote the plan to {}; nothing was created" => "Rencana ditulis ke {}; tidak ada yang dibuat",
+        "indented below '{}' (line {}), which is a file; write it as '{}/' or pass --promote-root-to-dir" => "menjorok di bawah '{}' (baris {}), yang merupakan berkas; tulis sebagai '{}/' atau gunakan --promote-root-to-dir",
+        "invalid name '{}': {}" => "nama tidak valid '{}': {}",
+        "no valid names after splitting on '&'" => "tidak ada nama valid setelah dipisah pada '&'",
+        "heredoc without a file node above it" => "heredoc tanpa berkas di atasnya",
+        "skipped, {} bytes is too long for a tree line" => "dilewati, {} byte terlalu panjang untuk baris pohon",
+        "skipped {} [{}]: {}" => "{} dilewati [{}]: {}",
+        "indentation jumps {} levels below line {}; placed directly under '{}'" => "indentasi melompat {} tingkat di bawah baris {}; ditempatkan langsung di bawah '{}'",
+        "indented below '{}' (line {}), which is a file; write it as '{}/' or pass --auto-dir" => "menjorok di bawah '{}' (baris {}), yang merupakan berkas; tulis sebagai '{}/' atau gunakan --auto-dir",
+        "'{}' differs only in case from '{}' (line {})" => "'{}' hanya berbeda huruf besar/kecil dari '{}' (baris {})",
+        "folder '{}' is listed again (line {}); the two are merged" => "folder '{}' tercantum lagi (baris {}); keduanya digabung",
+        "'{}' is already listed at line {}" => "'{}' sudah tercantum di baris {}",
+        "Checking {} ({} lines)" => "Memeriksa {} ({} baris)",
+        "{} error(s), {} warning(s)" => "{} galat, {} peringatan",
+        "No errors, {} warning(s)" => "Tidak ada galat, {} peringatan",
+        "No problems found ({} entries)" => "Tidak ada masalah ditemukan ({} entri)",
+        "--metrics-addr {}: {}" => "--metrics-addr {}: {}",
+        "Metrics at http://{}/metrics" => "Metrik di http://{}/metrics",
+        "--metrics-file {}: {}" => "--metrics-file {}: {}",
+        "could not write metrics: {}" => "metrik tidak dapat ditulis: {}",
+        "'{}' does not exist; --into-archive adds to an existing zip" => "'{}' tidak ada; --into-archive menambahkan ke zip yang sudah ada",
+        "'{}' is not a readable zip: {}" => "'{}' bukan zip yang dapat dibaca: {}",
+        "Dry run: nothing is written" => "Uji coba: tidak ada yang ditulis",
+        "a {} cannot be stored in a zip archive" => "{} tidak dapat disimpan di arsip zip",
+        "could not update '{}': {}" => "tidak dapat memperbarui '{}': {}",
+        "--into-archive cannot be combined with -C or --into" => "--into-archive tidak dapat digabung dengan -C atau --into",
+        "Updated {}" => "{} diperbarui",
+        "{} expects a duration such as 90s, 5m or 1500ms, got '{}'" => "{} membutuhkan durasi seperti 90s, 5m atau 1500ms, didapat '{}'",
+        "timeout expects a duration such as 90s, 5m or 1500ms, got '{}'" => "timeout membutuhkan durasi seperti 90s, 5m atau 1500ms, didapat '{}'",
+        "An operation was still running {} after --timeout {}; giving up" => "Sebuah operasi masih berjalan {} setelah --timeout {}; menyerah",
+        "not started: --timeout {} had passed" => "tidak dimulai: --timeout {} sudah lewat",
+        "{} entries from '{}' on: {}" => "{} entri mulai dari '{}': {}",
+        "{}: stopped by --timeout, {} entries not started" => "{}: dihentikan oleh --timeout, {} entri tidak dimulai",
+        "Tabs mixed with spaces: a tab counts as {} spaces, {} columns per level" => "Tab bercampur spasi: satu tab dihitung {} spasi, {} kolom per tingkat",
+        "the name is empty" => "namanya kosong",
+        "{} bytes is over the {}-byte limit" => "{} byte melebihi batas {} byte",
+        "'.' and '..' cannot be created" => "'.' dan '..' tidak dapat dibuat",
+        "only tree drawing characters" => "hanya berisi karakter gambar pohon",
+        "'{}' is a reserved name on Windows" => "'{}' adalah nama yang dicadangkan di Windows",
+        "control character U+{} at position {}" => "karakter kontrol U+{} di posisi {}",
+        "'{}' at position {} is not allowed" => "'{}' di posisi {} tidak diizinkan",
+        "ends with a space" => "diakhiri spasi",
+        "ends with a dot" => "diakhiri titik",
+        "--interactive needs a terminal to answer on; stdin is not one" => "--interactive membutuhkan terminal untuk menjawab; stdin bukan terminal",
+        "Review the plan before anything is created" => "Periksa rencana sebelum apa pun dibuat",
+        "Create it? [y/N]" => "Buat sekarang? [y/N]",
+        "'{}' already exists. Overwrite? [y]es/[N]o/[a]ll/n[o]ne" => "'{}' sudah ada. Timpa? [y]a/[N] tidak/[a] semua/[o] tidak satu pun",
+        "Cancelled: nothing was created" => "Dibatalkan: tidak ada yang dibuat",
+        "--var expects name=value, got '{}'" => "--var mengharapkan nama=nilai, didapat '{}'",
+        "line {}: no value for {{{}}}; pass --var {}=... or --vars <file>" => "baris {}: tidak ada nilai untuk {{{}}}; berikan --var {}=... atau --vars <berkas>",
+        "line {}: '{}' is not a valid name after filling in variables: {}" => "baris {}: '{}' bukan nama yang valid setelah variabel diisi: {}",
+        "Written: {} folder(s), {} file(s), {}B, {} level(s) deep" => "Ditulis: {} folder, {} berkas, {}B, sedalam {} tingkat",
+        "no extension" => "tanpa ekstensi",
+        "other" => "lainnya",
+        "--max-download expects a size such as 512K or 10M, got '{}'" => "--max-download mengharapkan ukuran seperti 512K atau 10M, didapat '{}'",
+        "--download-timeout expects seconds, got '{}'" => "--download-timeout mengharapkan detik, didapat '{}'",
+        "{}: sha256 is {}, expected {}" => "{}: sha256 adalah {}, diharapkan {}",
+        "{}: only http:// and https:// sources are supported" => "{}: hanya sumber http:// dan https:// yang didukung",
+        "{}: larger than {} bytes (--max-download)" => "{}: lebih besar dari {} byte (--max-download)",
+        "{}: no complete response within {}s (--download-timeout)" => "{}: tidak ada respons lengkap dalam {} detik (--download-timeout)",
+        "did you mean `{}`?" => "maksud Anda `{}`?",
+        "{} (not found)" => "{} (tidak ditemukan)",
+        "{} config file(s) have errors" => "{} berkas konfigurasi memiliki kesalahan",
+        "Configuration is valid" => "Konfigurasi valid",
+        "no config files; these are the defaults" => "tidak ada berkas konfigurasi; ini nilai bawaan",
+        "merged from: {}" => "digabung dari: {}",
+        "`mks config check` lists every problem" => "`mks config check` menampilkan setiap masalah",
+        "--fence-index expects a block number from 1, got '{}'" => "--fence-index mengharapkan nomor blok mulai dari 1, didapat '{}'",
+        "--fence-index {}: the input has {} fenced block(s)" => "--fence-index {}: masukan memiliki {} blok berpagar",
+        "Reading fenced block {} (lines {}-{})" => "Membaca blok berpagar {} (baris {}-{})",
+        "no empty folders in '{}'" => "tidak ada folder kosong di '{}'",
+        "{} kept: marked in the tree" => "{} dipertahankan: ditandai di pohon",
+        "{} empty folder(s) removed, {} kept" => "{} folder kosong dihapus, {} dipertahankan",
+        "{} empty folder(s) would be removed, {} kept" => "{} folder kosong akan dihapus, {} dipertahankan",
+        "Run again with --apply to remove them" => "Jalankan lagi dengan --apply untuk menghapusnya",
+        "--format expects text, json or yaml, got '{}'" => "--format mengharapkan text, json atau yaml, didapat '{}'",
+        "object keys must be names" => "kunci objek harus berupa nama",
+        "invalid name '{}' below '{}': {}" => "nama tidak valid '{}' di bawah '{}': {}",
+        "'{}': expected an object, array, string or null, got {}" => "'{}': diharapkan objek, array, string atau null, didapat {}",
+        "'{}': unknown type '{}'" => "'{}': tipe tidak dikenal '{}'",
+        "expected a name or an object in a list, got {}" => "diharapkan nama atau objek dalam daftar, didapat {}",
+        "expected a name or an object in a list, got a nested list" => "diharapkan nama atau objek dalam daftar, didapat daftar bersarang",
+        "a tree must be an object or an array" => "pohon harus berupa objek atau array",
+        "invalid JSON: {}" => "JSON tidak valid: {}",
+        "invalid YAML: {}" => "YAML tidak valid: {}",
+        "Read from {} ({} entries, {})" => "Dibaca dari {} ({} entri, {})",
+        "--shard expects i/n with 1 <= i <= n, got '{}'" => "--shard mengharapkan i/n dengan 1 <= i <= n, didapat '{}'",
+        "Shard {}/{}: {} of {} entries, plus all {} folder(s)" => "Shard {}/{}: {} dari {} entri, ditambah semua {} folder",
+        "--on-conflict expects skip, overwrite, error or backup, got '{}'" => "--on-conflict mengharapkan skip, overwrite, error atau backup, didapat '{}'",
+        "'{}' already exists (--on-conflict=error)" => "'{}' sudah ada (--on-conflict=error)",
+        "{} existing file(s) were backed up first:" => "{} berkas yang sudah ada dicadangkan terlebih dahulu:",
+        "{} file(s) already exist (--on-conflict=error): {}" => "{} berkas sudah ada (--on-conflict=error): {}",
+        "Remove the {} entries this run created? [y/N]" => "Hapus {} entri yang dibuat proses ini? [y/N]",
+        "Left in place; `mks undo` removes it later, --rollback-on-error does it right away" => "Dibiarkan; `mks undo` menghapusnya nanti, --rollback-on-error langsung menghapusnya",
+        "could not roll back {}: {}" => "tidak dapat membatalkan {}: {}",
+        "Rolled back {} of {} created entries" => "{} dari {} entri yang dibuat telah dibatalkan",
+        "two target directories given: '{}' and '{}'" => "dua direktori tujuan diberikan: '{}' dan '{}'",
+        "cannot create in '{}': '{}' is a file" => "tidak dapat membuat di '{}': '{}' adalah berkas",
+        "line {}: skipped {} [{}]: {}" => "baris {}: {} dilewati [{}]: {}",
+        "shell prompt" => "prompt shell",
+        "truncation marker" => "penanda pemotongan",
+        "pager artifact" => "sisa pager",
+        "tree banner" => "kepala tree",
+        "tree summary" => "ringkasan tree",
+        "a {} is in the way" => "ada {} yang menghalangi",
+        "{} entries conflict with what is on disk" => "{} entri bertentangan dengan isi disk",
+        "{} entries would be created" => "{} entri akan dibuat",
+        "Target already matches the plan" => "Target sudah sesuai dengan rencana",
+        "line {}: [after={}] matches no entry" => "baris {}: [after={}] tidak cocok dengan entri mana pun",
+        "line {}: [after={}] is ambiguous: {}" => "baris {}: [after={}] ambigu: {}",
+        "line {}: [after={}] names the entry itself" => "baris {}: [after={}] menunjuk entri itu sendiri",
+        "line {} '{}'" => "baris {} '{}'",
+        "[after=...] cycle: {}" => "siklus [after=...]: {}",
+        "exists" => "sudah ada",
+        "Example trees (mks demo <name> prints one, --apply creates it in a temporary folder):" => "Contoh pohon (mks demo <nama> mencetaknya, --apply membuatnya di folder sementara):",
+        "Every example above" => "Semua contoh di atas",
+        "unknown demo '{}' (available: {}, all)" => "demo '{}' tidak dikenal (tersedia: {}, all)",
+        "Created in {}" => "Dibuat di {}",
+        "Plain indentation with spaces or tabs" => "Indentasi biasa dengan spasi atau tab",
+        "`tree` output with icons, comments and '&'" => "Keluaran `tree` dengan ikon, komentar dan '&'",
+        "`tree -p -u -g -s -D` attribute columns" => "Kolom atribut `tree -p -u -g -s -D`",
+        "`ls -F` suffixes and link entries" => "Akhiran `ls -F` dan entri tautan",
+        "Heavy glyphs and the CJK console layout" => "Glyph tebal dan tata letak konsol CJK",
+        "File contents in heredoc blocks" => "Isi berkas dalam blok heredoc",
+        "[tag=...] and [protect] annotations" => "Anotasi [tag=...] dan [protect]",
+        "The {{project}} placeholder" => "Placeholder {{project}}",
+        "Several trees separated by a blank line" => "Beberapa pohon dipisahkan baris kosong",
+        "Org-mode or Markdown headings as levels" => "Judul Org-mode atau Markdown sebagai tingkat",
+        "'{}' is held by another mks run (pid {})" => "'{}' sedang dipegang oleh proses mks lain (pid {})",
+        "nothing to clean in '{}'" => "tidak ada yang perlu dibersihkan di '{}'",
+        "removed {} from '{}'" => "{} dihapus dari '{}'",
+        "'{}': '{}' cannot be written in latin1 (.editorconfig charset)" => "'{}': '{}' tidak dapat ditulis dalam latin1 (charset .editorconfig)",
+        "clipboard is not a tree-structure: line {} is {} bytes long (minified data or an encoded blob?)" => "clipboard bukan struktur pohon: baris {} panjangnya {} byte (data terminifikasi atau blob terenkode?)",
+        "line {}: skipped, {} bytes is over the {}-byte limit for a tree line ({})" => "baris {}: dilewati, {} byte melebihi batas {} byte untuk baris pohon ({})",
+        "the tree has no file sizes to compare with (export it with 'mks dump --size')" => "pohon tidak memiliki ukuran berkas untuk dibandingkan (ekspor dengan 'mks dump --size')",
+        "Disk usage of '{}' compared with the tree:" => "Pemakaian disk '{}' dibandingkan dengan pohon:",
+        "{} file(s) from the tree are no longer on disk" => "{} berkas dari pohon sudah tidak ada di disk",
+        "simulated failure (--simulate-failure)" => "kegagalan simulasi (--simulate-failure)",
+        "--simulate-failure needs a build with the 'test-utils' feature" => "--simulate-failure memerlukan build dengan fitur 'test-utils'",
+        "--simulate-failure pattern '{}': {}" => "pola --simulate-failure '{}': {}",
+        "usage: mks schema plan|report|result" => "penggunaan: mks schema plan|report|result",
+        "usage: mks apply <plan.json>" => "penggunaan: mks apply <plan.json>",
+        "Loaded plan '{}' ({} entries)" => "Rencana '{}' dimuat ({} entri)",
+        "empty path" => "jalur kosong",
+        "'{}' must be a relative path with '/' separators" => "'{}' harus berupa jalur relatif dengan pemisah '/'",
+        "'{}' has an empty, '.' or '..' component" => "'{}' memiliki komponen kosong, '.' atau '..'",
+        "{}: schema_version '{}' is not supported; this mks reads {}.x plans (run `mks schema plan`)" => "{}: schema_version '{}' tidak didukung; mks ini membaca rencana {}.x (jalankan `mks schema plan`)",
+        "{}: entries[{}]: {}" => "{}: entries[{}]: {}",
+        "unknown kind '{}'" => "jenis '{}' tidak dikenal",
+        "'{}' is listed twice" => "'{}' tercantum dua kali",
+        "line {}: root '{}' is a file, but line {} is indented below it; write it as '{}/' or pass --promote-root-to-dir" => "baris {}: akar '{}' adalah berkas, tetapi baris {} menjorok di bawahnya; tulis sebagai '{}/' atau gunakan --promote-root-to-dir",
+        "{}: '{}' must be a string, number or boolean" => "{}: '{}' harus berupa string, angka, atau boolean",
+        "Checking preset '{}' ({} entries)" => "Memeriksa preset '{}' ({} entri)",
+        "{}: unresolved variable '{}'" => "{}: variabel '{}' tidak terisi",
+        "{}: invalid name '{}': {}" => "{}: nama tidak valid '{}': {}",
+        "{} collides with {}" => "{} bertabrakan dengan {}",
+        "size {} -> {}" => "ukuran {} -> {}",
+        "modified since creation" => "diubah sejak dibuat",
+        "no manifest in '{}'; nothing to undo" => "tidak ada manifes di '{}'; tidak ada yang dibatalkan",
+        "Dry run: nothing is deleted" => "Uji coba: tidak ada yang dihapus",
+        "{} (already gone)" => "{} (sudah tidak ada)",
+        "{} kept: {}" => "{} dipertahankan: {}",
+        "{} kept: holds files mks did not create" => "{} dipertahankan: berisi berkas yang tidak dibuat mks",
+        "{} would be removed, {} kept" => "{} akan dihapus, {} dipertahankan",
+        "{} removed, {} kept" => "{} dihapus, {} dipertahankan",
+        "Could not update {}: {}" => "Tidak dapat memperbarui {}: {}",
+        "--only '{}' matches nothing in the tree" => "--only '{}' tidak cocok dengan apa pun di pohon",
+        "--progress-fd '{}': {}" => "--progress-fd '{}': {}",
+        "--progress-fd {}: descriptor is not open" => "--progress-fd {}: deskriptor tidak terbuka",
+        "--progress-fd {}: numbered descriptors need a Unix system; pass a named pipe path instead" => "--progress-fd {}: deskriptor bernomor memerlukan sistem Unix; berikan jalur named pipe sebagai gantinya",
+        "several tree files given; combine them with --under <dir>" => "beberapa berkas pohon diberikan; gabungkan dengan --under <dir>",
+        "--under needs at least one tree file" => "--under memerlukan setidaknya satu berkas pohon",
+        "'{}' and '{}' both create '{}/{}'" => "'{}' dan '{}' sama-sama membuat '{}/{}'",
+        "recreating {}" => "membuat ulang {}",
+        "Watching {} ({} entries); Ctrl-C to stop" => "Memantau {} ({} entri); Ctrl-C untuk berhenti",
+        "watch error: {}" => "kesalahan pemantauan: {}",
+        "no input given: pass a tree file, or --clipboard (-c) to read the clipboard" => "tidak ada masukan: berikan berkas pohon, atau --clipboard (-c) untuk membaca papan klip",
+        "{} file(s)" => "{} berkas",
+        "{} dir(s)" => "{} direktori",
+        "{} other" => "{} lainnya",
+        "empty" => "kosong",
+        "(top level)" => "(tingkat atas)",
+        "Dry run: nothing is created" => "Uji coba: tidak ada yang dibuat",
+        "Plan: {} file(s), {} dir(s), {} entries in total" => "Rencana: {} berkas, {} direktori, total {} entri",
+        "Would be applied into {} target(s):" => "Akan diterapkan ke {} target:",
+        "Target directory: {}" => "Direktori target: {}",
+        "the tree" => "pohon ini",
+        "Where should {} be created? (Tab completes, Enter for the current directory)" => "Di mana {} dibuat? (Tab melengkapi, Enter untuk direktori saat ini)",
+        "cancelled" => "dibatalkan",
+        "--emit-script expects 'sh' or 'powershell', got '{}'" => "--emit-script membutuhkan 'sh' atau 'powershell', didapat '{}'",
+        "--emit-script cannot be combined with --into" => "--emit-script tidak dapat digabung dengan --into",
+        "--into-glob '{}' matches no directories" => "--into-glob '{}' tidak cocok dengan direktori mana pun",
+        "Results for {} target(s):" => "Hasil untuk {} target:",
+        "{}: {} created, {} already existed" => "{}: {} dibuat, {} sudah ada",
+        "{}: {} failed" => "{}: {} gagal",
+        "{} of {} target(s) had failures" => "{} dari {} target mengalami kegagalan",
+        "{} protected file(s) already existed and were left untouched:" => "{} berkas terlindungi sudah ada dan tidak diubah:",
+        "protect pattern '{}': {}" => "pola protect '{}': {}",
+        "clipboard init failed" => "gagal menginisialisasi papan klip",
+        "clipboard read failed" => "gagal membaca papan klip",
+        "clipboard is empty" => "papan klip kosong",
+        "primary selection read failed" => "gagal membaca seleksi primer",
+        "the primary selection is only available on X11" => "seleksi primer hanya tersedia di X11",
+        "--selection expects 'primary' or 'clipboard', got '{}'" => "--selection membutuhkan 'primary' atau 'clipboard', didapat '{}'",
+        "clipboard is not a tree-structure" => "isi papan klip bukan struktur pohon",
+        "'{}' is not a directory" => "'{}' bukan direktori",
+        "Could not apply {} to '{}'" => "Tidak dapat menerapkan {} ke '{}'",
+        "--assume expects 'dir' or 'file', got '{}'" => "--assume membutuhkan 'dir' atau 'file', bukan '{}'",
+        "{} node(s) have no '/', extension or children; created as {}:" => {
+            "{} simpul tanpa '/', ekstensi, atau anak; dibuat sebagai {}:"
+        }
+        "line {}: {}" => "baris {}: {}",
+        "Use --assume dir or --assume file to choose explicitly." => {
+            "Gunakan --assume dir atau --assume file untuk memilih secara eksplisit."
+        }
+        "Inferred indent levels:" => "Tingkat indentasi yang disimpulkan:",
+        "{} column(s) -> level {}" => "{} kolom -> tingkat {}",
+        _ => return None,
+    })
+}
@@ -0,0 +1,121 @@
+// File: src\icons.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: Icon themes for status output and reverse mode
+// License: MIT
+
+use std::collections::BTreeMap;
+
+use crate::{config::IconConfig, NodeKind};
+
+pub const THEMES: &[&str] = &["plain", "emoji", "nerd"];
+
+// Default is a theme without icons
+#[derive(Clone, Debug, Default)]
+pub struct IconTheme {
+    pub dir: String,
+    pub file: String,
+    pub executable: String,
+    pub symlink: String,
+    // FIFOs, sockets and doors
+    pub special: String,
+    pub extensions: BTreeMap<String, String>,
+}
+
+fn theme(icons: [&str; 5], extensions: &[(&str, &str)]) -> IconTheme {
+    IconTheme {
+        dir: icons[0].to_string(),
+        file: icons[1].to_string(),
+        executable: icons[2].to_string(),
+        symlink: icons[3].to_string(),
+        special: icons[4].to_string(),
+        extensions: extensions.iter().map(|(e, i)| (e.to_string(), i.to_string())).collect(),
+    }
+}
+
+pub fn builtin(name: &str) -> Option<IconTheme> {
+    match name {
+        // `ls -l` type letters, safe for any terminal and font
+        "plain" => Some(theme(["d", "-", "x", "l", "p"], &[])),
+        "emoji" => Some(theme(
+            ["📁", "📄", "⚙️", "🔗", "🔌"],
+            &[
+                ("rs", "🦀"),
+                ("py", "🐍"),
+                ("md", "📝"),
+                ("png", "🖼️"),
+                ("jpg", "🖼️"),
+                ("svg", "🖼️"),
+                ("lock", "🔒"),
+            ],
+        )),
+        // Nerd Font glyphs (private use area)
+        "nerd" => Some(theme(
+            ["\u{f115}", "\u{f15b}", "\u{f489}", "\u{f0c1}", "\u{f1e6}"],
+            &[
+                ("rs", "\u{e7a8}"),
+                ("py", "\u{e606}"),
+                ("js", "\u{e74e}"),
+                ("ts", "\u{e628}"),
+                ("json", "\u{e60b}"),
+                ("toml", "\u{e615}"),
+                ("md", "\u{f48a}"),
+                ("html", "\u{e736}"),
+                ("css", "\u{e749}"),
+                ("sh", "\u{f489}"),
+            ],
+        )),
+        _ => None,
+    }
+}
+
+impl IconTheme {
+    // Start from the configured built-in theme (or `--theme`), then apply overrides
+    pub fn from_config(config: &IconConfig, name: Option<&str>) -> Result<IconTheme, Box<dyn std::error::Error>> {
+        let name = name.or(config.theme.as_deref()).unwrap_or("emoji");
+        let mut theme = builtin(name)
+            .ok_or_else(|| t!("unknown icon theme '{}' (expected one of: {})", name, THEMES.join(", ")))?;
+        let fields = [
+            (&mut theme.dir, &config.dir),
+            (&mut theme.file, &config.file),
+            (&mut theme.executable, &config.executable),
+            (&mut theme.symlink, &config.symlink),
+            (&mut theme.special, &config.special),
+        ];
+        for (slot, value) in fields {
+            if let Some(value) = value {
+                *slot = value.clone();
+            }
+        }
+        theme.extensions.extend(config.extensions.clone());
+        Ok(theme)
+    }
+
+    pub fn icon(&self, name: &str, kind: NodeKind, executable: bool) -> &str {
+        match kind {
+            NodeKind::Dir => &self.dir,
+            NodeKind::Symlink => &self.symlink,
+            NodeKind::Fifo | NodeKind::Socket | NodeKind::Door => &self.special,
+            NodeKind::File if executable => &self.executable,
+            NodeKind::File => {
+                let ext = name.rsplit_once('.').map(|(_, ext)| ext.to_lowercase());
+                ext.and_then(|ext| self.extensions.get(&ext))
+                    .unwrap_or(&self.file)
+            }
+        }
+    }
+
+    // Every glyph, longest first, so the parser can strip them from names
+    pub fn glyphs(&self) -> Vec<String> {
+        let mut glyphs: Vec<String> = [&self.dir, &self.file, &self.executable, &self.symlink, &self.special]
+            .into_iter()
+            .chain(self.extensions.values())
+            .filter(|g| !g.is_empty())
+            .cloned()
+            .collect();
+        glyphs.sort();
+        glyphs.dedup();
+        glyphs.sort_by_key(|g| std::cmp::Reverse(g.len()));
+        glyphs
+    }
+}
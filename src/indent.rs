@@ -0,0 +1,110 @@
+// File: src\indent.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: Levels for trees written by hand, indented with spaces and/or tabs
+// License: MIT
+
+use std::collections::BTreeMap;
+
+use crate::{glyphs, heredoc_blocks, parse_tree_line_traced, LineTrace, ParseOptions};
+
+// How a plain tree is indented, as detected from the lines themselves
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Style {
+    // Columns per level
+    pub unit: usize,
+    // Columns one tab stands for: the space unit when both are used
+    pub tab: usize,
+    // Which characters the lines are indented with
+    pub tabs: bool,
+    pub spaces: bool,
+}
+
+// Leading whitespace of a node line (None for other lines), or None for the
+// whole input when the line is drawn with tree glyphs
+fn plain_prefix<'a>(line: &'a str, popts: &ParseOptions) -> Option<Option<&'a str>> {
+    let mut trace = LineTrace::default();
+    if parse_tree_line_traced(line, popts, &mut trace).is_err() {
+        return Some(None);
+    }
+    let drawn = trace.marker.is_some()
+        || line
+            .chars()
+            .take(trace.chars_before_name)
+            .any(|c| c != ' ' && c != '\t' && glyphs::is_indent(c));
+    let prefix = &line[..line.len() - line.trim_start_matches([' ', '\t']).len()];
+    (!drawn).then_some(Some(prefix))
+}
+
+fn columns(prefix: &str, tab: usize) -> usize {
+    prefix.chars().map(|c| if c == '\t' { tab } else { 1 }).sum()
+}
+
+// The most common step from one line to a deeper next one; ties go to the
+// smaller step
+fn common_step(widths: impl Iterator<Item = usize>) -> Option<usize> {
+    let mut steps: BTreeMap<usize, usize> = BTreeMap::new();
+    let mut previous = None;
+    for width in widths {
+        if let Some(prev) = previous.filter(|&prev| width > prev) {
+            *steps.entry(width - prev).or_default() += 1;
+        }
+        previous = Some(width);
+    }
+    steps.into_iter().max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0))).map(|(step, _)| step)
+}
+
+// The prefix of every tree line (None for lines that are not nodes or are
+// heredoc contents), when the whole input is indented with plain whitespace
+fn prefixes<'a>(lines: &'a [String], popts: &ParseOptions) -> Option<Vec<Option<&'a str>>> {
+    let mut in_heredoc = vec![false; lines.len()];
+    for block in heredoc_blocks(lines, popts) {
+        in_heredoc[block.opener..=block.closer].fill(true);
+    }
+    lines
+        .iter()
+        .zip(in_heredoc)
+        .map(|(line, in_heredoc)| if in_heredoc { Some(None) } else { plain_prefix(line, popts) })
+        .collect()
+}
+
+// None when some line is drawn with tree glyphs or nothing is indented;
+// such input keeps the column-based levels
+pub fn detect(lines: &[String], popts: &ParseOptions) -> Option<Style> {
+    let prefixes = prefixes(lines, popts)?;
+    let used: Vec<&str> = prefixes.iter().flatten().copied().collect();
+    let tabs = used.iter().any(|p| p.contains('\t'));
+    let spaces = used.iter().any(|p| p.contains(' '));
+    if !tabs && !spaces {
+        return None;
+    }
+    let space_unit = common_step(used.iter().filter(|p| !p.contains('\t')).map(|p| p.len()));
+    let tab = if spaces { space_unit.unwrap_or(4) } else { 1 };
+    let unit = common_step(used.iter().map(|p| columns(p, tab))).unwrap_or(tab);
+    Some(Style { unit, tab, tabs, spaces })
+}
+
+// One level per line (None for lines that are not nodes). A line is one
+// level below the closest shallower line before it; widths less than half a
+// step away from an open level (a stray space) share that level.
+pub fn levels(lines: &[String], popts: &ParseOptions, style: Style) -> Vec<Option<usize>> {
+    let Some(prefixes) = prefixes(lines, popts) else {
+        return Vec::new();
+    };
+    let slack = (style.unit - 1) / 2;
+    let mut open: Vec<usize> = Vec::new();
+    prefixes
+        .into_iter()
+        .map(|prefix| {
+            let width = columns(prefix?, style.tab);
+            while open.last().is_some_and(|&top| top > width + slack) {
+                open.pop();
+            }
+            match open.last() {
+                Some(&top) if width.abs_diff(top) <= slack => {}
+                _ => open.push(width),
+            }
+            Some(open.len() - 1)
+        })
+        .collect()
+}
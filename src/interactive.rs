@@ -0,0 +1,82 @@
+// File: src\interactive.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: `-i/--interactive` - review the plan and confirm before anything is created
+// License: MIT
+
+use std::{
+    io::{self, BufRead, IsTerminal, Write},
+    path::Path,
+    sync::{Mutex, PoisonError},
+};
+
+use crate::{dry_run, icons::IconTheme, PlannedEntry};
+
+// The answer typed after `question`, lowercased; None without a terminal
+// or when stdin is closed
+pub fn ask(question: &str) -> Option<String> {
+    if !io::stdin().is_terminal() {
+        return None;
+    }
+    print!("{} ", question);
+    let _ = io::stdout().flush();
+    let mut line = String::new();
+    match io::stdin().lock().read_line(&mut line) {
+        Ok(0) | Err(_) => None,
+        Ok(_) => Some(line.trim().to_lowercase()),
+    }
+}
+
+// `[y/N]`: anything but yes is no
+pub fn yes(question: &str) -> bool {
+    matches!(ask(question).as_deref(), Some("y" | "yes"))
+}
+
+// `--interactive` reads answers from the terminal, so the tree itself cannot
+// come through a pipe
+pub fn check_terminal() -> Result<(), Box<dyn std::error::Error>> {
+    if !io::stdin().is_terminal() {
+        return Err(t!("--interactive needs a terminal to answer on; stdin is not one").into());
+    }
+    Ok(())
+}
+
+// Show what would be created and ask once for the whole plan
+pub fn confirm_plan(plan: &[PlannedEntry], icons: &IconTheme, base: Option<&Path>, targets: &[String], posix: bool) -> bool {
+    println!("🔍 {}\n", t!("Review the plan before anything is created"));
+    dry_run::report(plan, icons, base, targets, posix);
+    println!();
+    yes(&format!("❓ {}", t!("Create it? [y/N]")))
+}
+
+// Per-file answers for existing files; "all" and "none" stick for the rest
+// of the run
+#[derive(Default)]
+pub struct Conflicts {
+    remembered: Mutex<Option<bool>>,
+}
+
+impl Conflicts {
+    // Whether the existing file at `path` may be overwritten
+    pub fn overwrite(&self, path: &str) -> bool {
+        if let Some(answer) = *self.remembered.lock().unwrap_or_else(PoisonError::into_inner) {
+            return answer;
+        }
+        loop {
+            let answer = ask(&format!("⚠️ {}", t!("'{}' already exists. Overwrite? [y]es/[N]o/[a]ll/n[o]ne", path)));
+            match answer.as_deref() {
+                Some("y" | "yes") => return true,
+                None | Some("" | "n" | "no") => return false,
+                Some("a" | "all") => {
+                    *self.remembered.lock().unwrap_or_else(PoisonError::into_inner) = Some(true);
+                    return true;
+                }
+                Some("o" | "none") => {
+                    *self.remembered.lock().unwrap_or_else(PoisonError::into_inner) = Some(false);
+                    return false;
+                }
+                Some(_) => {}
+            }
+        }
+    }
+}
@@ -0,0 +1,3670 @@
+// File: src\lib.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2025-12-13
+// Description: Create Directory Structures from Tree-like Text; the parser and
+//              builder behind the `mks` binary, usable from other Rust tools
+// License: MIT
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    env,
+    fs::{self, File},
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+};
+
+use clap_version_flag::colorful_version;
+
+// Set while stdout carries machine output (`--emit-script`); status lines
+// then go to stderr
+static STDOUT_IS_DATA: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+macro_rules! status {
+    ($($arg:tt)*) => {
+        if crate::STDOUT_IS_DATA.load(std::sync::atomic::Ordering::Relaxed) {
+            eprintln!($($arg)*)
+        } else {
+            println!($($arg)*)
+        }
+    };
+}
+
+#[macro_use]
+mod i18n;
+mod after;
+mod archive;
+mod args;
+mod attrs;
+mod breakdown;
+mod check;
+mod clip;
+mod clipboard_watch;
+mod config;
+mod demo;
+mod descriptions;
+mod doctor;
+mod dry_run;
+mod dump;
+mod editorconfig;
+mod escape;
+mod explain;
+mod fence;
+mod fetch;
+mod git;
+mod glyphs;
+mod html_report;
+mod icons;
+mod indent;
+mod interactive;
+mod manifest;
+mod metrics;
+mod noise;
+mod outline;
+mod picker;
+mod placeholder;
+mod plan_file;
+mod presets;
+mod progress;
+mod project;
+mod prune;
+mod rollback;
+mod run_report;
+mod watch;
+mod watch_dir;
+mod script;
+mod state;
+mod stats;
+mod structured;
+mod tarball;
+mod templates;
+mod timeout;
+mod tree;
+mod validate;
+mod vars;
+
+use attrs::NodeAttrs;
+use serde::{Deserialize, Serialize};
+
+
+/// What an entry is. Besides files and folders, `ls -F` / `tree -F`
+/// classification suffixes mark links and special files.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum NodeKind {
+    File,
+    Dir,
+    // `@`
+    Symlink,
+    // `|`
+    Fifo,
+    // `=`
+    Socket,
+    // `>` (Solaris doors)
+    Door,
+}
+
+impl NodeKind {
+    const ALL: [NodeKind; 6] = [
+        NodeKind::File,
+        NodeKind::Dir,
+        NodeKind::Symlink,
+        NodeKind::Fifo,
+        NodeKind::Socket,
+        NodeKind::Door,
+    ];
+
+    fn from_label(label: &str) -> Option<NodeKind> {
+        NodeKind::ALL.into_iter().find(|kind| kind.label() == label)
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            NodeKind::File => "file",
+            NodeKind::Dir => "directory",
+            NodeKind::Symlink => "symlink",
+            NodeKind::Fifo => "fifo",
+            NodeKind::Socket => "socket",
+            NodeKind::Door => "door",
+        }
+    }
+}
+
+// Trailing `[key]` / `[key=value]` marker on a node, e.g. `old/ [remove]`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Annotation {
+    key: String,
+    value: Option<String>,
+}
+
+impl Annotation {
+    fn parse(inner: &str) -> Annotation {
+        // A bare mode, `[0700]`, is short for `[mode=0700]`
+        if attrs::parse_octal_mode(inner.trim()).is_some() {
+            return Annotation { key: "mode".to_string(), value: Some(inner.trim().to_string()) };
+        }
+        match inner.split_once('=') {
+            Some((key, value)) => Annotation {
+                key: key.trim().to_string(),
+                value: Some(value.trim().to_string()),
+            },
+            None => Annotation {
+                key: inner.trim().to_string(),
+                value: None,
+            },
+        }
+    }
+}
+
+struct TreeNode {
+    indent: usize,
+    // Columns before the name, tabs expanded to 4-column stops
+    width: usize,
+    name: String,
+    kind: NodeKind,
+    // Marked with a trailing `*` (as printed by `tree -F` / `ls -F`)
+    executable: bool,
+    // Bracketed columns from `tree -p -u -g -s -D`
+    attrs: Option<NodeAttrs>,
+    // The line itself said what kind this is (`/`, `-F` suffix, permission column)
+    kind_explicit: bool,
+    annotations: Vec<Annotation>,
+    // Verbatim file contents from a following `<<EOF` … `EOF` block
+    contents: Option<String>,
+}
+
+#[derive(Default)]
+struct ParseOptions {
+    // `--raw-names`: take names verbatim, only the host OS rules apply
+    raw_names: bool,
+    // `--infer-hierarchy`: derive levels from the indent widths actually used
+    infer_hierarchy: bool,
+    // `--assume dir|file`: kind for childless names with no '/' and no extension
+    assume: Option<NodeKind>,
+    // Glyphs of the configured icon theme, stripped from the start of names
+    icons: Vec<String>,
+    // `--promote-root-to-dir`: a root file with indented children becomes a folder
+    promote_root: bool,
+    // `--keep-comments`: a trailing comment becomes a `[description=...]` annotation
+    keep_comments: bool,
+    // `--auto-dir`: any file with indented children becomes a folder
+    auto_dir: bool,
+    // `--strict`: a path listed twice is an error rather than a warning
+    strict: bool,
+    // `--multi-sep`: what separates several names on one line; None is '&'
+    multi_sep: Option<String>,
+    // `--no-split`: one name per line
+    no_split: bool,
+    // `--sanitize`: rename invalid names to the nearest valid one instead of skipping them
+    sanitize: bool,
+}
+
+impl ParseOptions {
+    // What splits a line into several names, if anything does
+    fn separator(&self) -> Option<&str> {
+        match self.raw_names || self.no_split {
+            true => None,
+            false => Some(self.multi_sep.as_deref().unwrap_or("&")),
+        }
+    }
+}
+
+// The names on one line: `a.rs & b.rs` is two files. A backslash before the
+// separator makes it part of the name (`Q\&A.md`); without a separator the
+// line is a single name, where `\&` still stands for '&'.
+fn split_names(name: &str, separator: Option<&str>) -> Vec<String> {
+    let Some(separator) = separator else {
+        return vec![name.replace("\\&", "&")];
+    };
+    let escaped = format!("\\{}", separator);
+    let (mut names, mut current, mut rest) = (Vec::new(), String::new(), name);
+    while let Some(c) = rest.chars().next() {
+        if let Some(tail) = rest.strip_prefix(escaped.as_str()) {
+            current.push_str(separator);
+            rest = tail;
+        } else if let Some(tail) = rest.strip_prefix(separator) {
+            names.push(std::mem::take(&mut current));
+            rest = tail;
+        } else {
+            current.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    names.push(current);
+    names.into_iter().map(|name| name.trim().to_string()).filter(|name| !name.is_empty()).collect()
+}
+
+// What the parser did with a single line, reported by `mks explain`
+#[derive(Default)]
+struct LineTrace {
+    // Connector the name was found after; None when the fallback was used
+    marker: Option<&'static str>,
+    chars_before_name: usize,
+    // Everything removed from the line on the way to the name
+    stripped: Vec<String>,
+    // The name and the rule it broke, when it was rejected as invalid
+    rejected: Option<(String, validate::Invalid)>,
+}
+
+fn parse_tree_line(line: &str, popts: &ParseOptions) -> Result<TreeNode, &'static str> {
+    parse_tree_line_traced(line, popts, &mut LineTrace::default())
+}
+
+fn parse_tree_line_traced(
+    line: &str,
+    popts: &ParseOptions,
+    trace: &mut LineTrace,
+) -> Result<TreeNode, &'static str> {
+    // Checked on the byte length first, so a pasted blob is never walked char by char
+    if line.len() > MAX_LINE_BYTES {
+        return Err("line too long");
+    }
+    let raw = popts.raw_names;
+    let line = line.trim_end();
+    if line.is_empty() {
+        return Err("empty line");
+    }
+    // Before comments are cut, so `me@box:~# tree` is still seen whole
+    if !raw {
+        if let Some(reason) = noise::classify(line) {
+            return Err(reason);
+        }
+    }
+
+    // Delete comment - FIXED: proper multi-byte character detection
+    let mut comment = None;
+    let line = if raw {
+        line
+    } else {
+        let mut result = line;
+        for (i, c) in line.char_indices() {
+            if c == '#' || c == '✅' || c == '←' {
+                result = &line[..i];
+                trace.stripped.push(format!("comment '{}'", &line[i..]));
+                comment = Some(line[i..].trim_start_matches(['#', '✅', '←']).trim());
+                break;
+            }
+        }
+        result.trim_end()
+    };
+
+    if line.is_empty() {
+        return Err("empty after comment");
+    }
+
+    // Trailing annotations, separated from the name by whitespace
+    let mut annotations = Vec::new();
+    let mut line = line;
+    while !raw && line.ends_with(']') {
+        let Some(open) = line.rfind('[') else { break };
+        let before = &line[..open];
+        if !before.ends_with(char::is_whitespace) || before.trim().is_empty() {
+            break;
+        }
+        let inner = &line[open + 1..line.len() - 1];
+        trace.stripped.push(format!("annotation '[{}]'", inner));
+        annotations.insert(0, Annotation::parse(inner));
+        line = before.trim_end();
+    }
+    // `script.sh (755)`: a mode in parentheses, the same as `[mode=755]`
+    if !raw {
+        let mode = line.rsplit_once(" (").and_then(|(before, rest)| Some((before, rest.strip_suffix(')')?)));
+        if let Some((before, digits)) = mode.filter(|(before, digits)| !before.trim().is_empty() && attrs::parse_octal_mode(digits).is_some()) {
+            trace.stripped.push(format!("mode '({})'", digits));
+            annotations.push(Annotation { key: "mode".to_string(), value: Some(digits.to_string()) });
+            line = before.trim_end();
+        }
+    }
+    if let Some(text) = comment.filter(|text| popts.keep_comments && !text.is_empty()) {
+        annotations.push(Annotation { key: "description".to_string(), value: Some(text.to_string()) });
+    }
+
+    // `name -> target` from `tree -l`, `ls -l` and `mks dump`
+    let mut linked = false;
+    if !raw {
+        if let Some(pos) = line.find(" -> ") {
+            let target = line[pos + 4..].trim();
+            trace.stripped.push(format!("link target '{}'", target));
+            if !target.is_empty() {
+                annotations.push(Annotation { key: "target".to_string(), value: Some(target.to_string()) });
+            }
+            linked = true;
+            line = line[..pos].trim_end();
+        }
+    }
+
+    // `spec.yaml <= https://…`: contents downloaded when the file is created,
+    // kept as a `[src=…]` annotation (which may also be written directly)
+    if !raw {
+        if let Some(pos) = line.find(" <= ") {
+            let url = line[pos + 4..].trim();
+            trace.stripped.push(format!("content source '{}'", url));
+            annotations.push(Annotation { key: "src".to_string(), value: Some(url.to_string()) });
+            line = line[..pos].trim_end();
+        }
+    }
+    let sourced = annotations.iter().any(|a| a.key == "src");
+
+    // FIXED: Check if line only contains tree characters without actual name
+    // Remove all tree drawing characters and whitespace to see if there's content
+    let content_check = line
+        .chars()
+        .filter(|&c| !glyphs::is_indent(c))
+        .collect::<String>();
+    
+    if content_check.is_empty() {
+        return Err("only tree characters, no name");
+    }
+
+    // Extract the name by searching for the tree marker: a branch/corner glyph,
+    // its horizontals and any padding ("├── ", "┣━━ ", "┠─", "├───")
+    let marker = glyphs::find_marker(line);
+    let name_part = if let Some(found) = &marker {
+        trace.marker = Some(if found.last { "└── " } else { "├── " });
+        &line[found.name_start..]
+    } else {
+        // Fallback for root or other formats
+        // But first check if it's just tree characters
+        let remaining = line.trim_start_matches(glyphs::is_indent);
+        
+        if remaining.is_empty() {
+            return Err("no name after tree characters");
+        }
+        
+        if raw || remaining.starts_with('[') {
+            remaining
+        } else {
+            let last_word = line.split_whitespace().last().unwrap_or(line);
+            // Words joined by an unescaped separator (`a.rs & b.rs`, `x.md,
+            // y.md`) belong to the name as well
+            let mut start = remaining.len() - last_word.len();
+            while let Some(head) = popts.separator().and_then(|sep| remaining[..start].trim_end().strip_suffix(sep)) {
+                match head.split_whitespace().last() {
+                    Some(word) if !head.ends_with('\\') => start = head.trim_end().len() - word.len(),
+                    _ => break,
+                }
+            }
+            if start > 0 {
+                trace.stripped.push(format!("leading words '{}'", remaining[..start].trim_end()));
+            }
+            &remaining[start..]
+        }
+    };
+
+    let name_part = name_part.trim();
+    if name_part.is_empty() {
+        return Err("no name found");
+    }
+
+    // `[drwxr-xr-x user group 4096 Jan  1 12:00]  name` from tree -p/-u/-g/-s/-D.
+    // The indent is measured up to the opening bracket.
+    let mut attrs = None;
+    let mut indent_anchor = None;
+    let name_part = match name_part.find(']') {
+        Some(close) if !raw && name_part.starts_with('[') => {
+            attrs = Some(attrs::parse_bracket_attrs(&name_part[1..close]));
+            indent_anchor = Some(name_part);
+            trace.stripped.push(format!("attributes '{}'", &name_part[..=close]));
+            name_part[close + 1..].trim()
+        }
+        _ => name_part,
+    };
+
+    // Remove emoji icons (📄, 📁, etc) from the beginning
+    let name_part = if raw {
+        name_part
+    } else {
+        let mut stripped = name_part
+            .trim_start_matches(|c: char| {
+                c == '📄' || c == '📁' || c == '📂' || c.is_whitespace()
+            })
+            .trim();
+        // A theme glyph only counts when a space separates it from the name
+        if let Some(rest) = popts.icons.iter().find_map(|glyph| stripped.strip_prefix(glyph.as_str())) {
+            if rest.starts_with(char::is_whitespace) && !rest.trim().is_empty() {
+                stripped = rest.trim();
+            }
+        }
+        if stripped.len() != name_part.len() {
+            trace.stripped.push(format!(
+                "icon prefix '{}'",
+                name_part[..name_part.len() - stripped.len()].trim_end()
+            ));
+        }
+        stripped
+    };
+
+    // FIXED: Double check after removing emojis
+    if name_part.is_empty() {
+        return Err("empty after removing emojis");
+    }
+
+    // Trailing '*' marks an executable file, it is never part of the name
+    let executable = !raw && name_part.ends_with('*');
+    let name_part = if executable {
+        trace.stripped.push("suffix '*' (executable)".to_string());
+        name_part.trim_end_matches('*').trim_end()
+    } else {
+        name_part
+    };
+
+    // The remaining classification suffixes describe the node kind
+    let last = if raw { None } else { name_part.chars().last() };
+    let (special, name_part) = match last {
+        Some('@') => (Some(NodeKind::Symlink), &name_part[..name_part.len() - 1]),
+        Some('|') => (Some(NodeKind::Fifo), &name_part[..name_part.len() - 1]),
+        Some('=') => (Some(NodeKind::Socket), &name_part[..name_part.len() - 1]),
+        Some('>') => (Some(NodeKind::Door), &name_part[..name_part.len() - 1]),
+        _ if linked => (Some(NodeKind::Symlink), name_part),
+        _ => (None, name_part),
+    };
+    if let (Some(kind), Some(suffix), false) = (special, last, linked) {
+        trace.stripped.push(format!("suffix '{}' ({})", suffix, kind.label()));
+    }
+    let name_part = name_part.trim_end();
+
+    // A permission column also tells the type when there is no suffix
+    let listed_type = attrs.as_ref().and_then(|a: &NodeAttrs| a.file_type);
+    let slashed = name_part.strip_suffix('/');
+    let is_dir = slashed.is_some() || (special.is_none() && listed_type == Some('d'));
+    if slashed.is_some() {
+        trace.stripped.push("suffix '/' (directory)".to_string());
+    }
+    let mut name = match slashed {
+        Some(stem) => stem.trim().to_string(),
+        None => name_part.to_string(),
+    };
+
+    name = name.trim().to_string();
+
+    // `\u{2502}` / `\\` escapes, as written by `mks dump` for names holding tree
+    // glyphs. `\xff` byte escapes are kept; they are decoded when creating.
+    if !raw && name.contains('\\') {
+        let decoded = escape::unescape_name(&name);
+        if decoded != name {
+            trace.stripped.push(format!("escapes in '{}'", name));
+            name = decoded;
+        }
+    } else if raw && escape::has_raw_bytes(&name) {
+        name = escape::literal_name(&name);
+    }
+
+    // FIXED: More strict validation
+    if name.is_empty() {
+        return Err("empty name after processing");
+    }
+    
+    // `--raw-names` keeps only the rules the host OS itself enforces; without
+    // it, a '/' inside the name is an inline path and each part is checked.
+    // An escaped separator (`Q\&A.md`) is checked as the character it stands for.
+    let separator = popts.separator().unwrap_or("&");
+    let unescaped = name.replace(&format!("\\{}", separator), separator);
+    let valid = if raw {
+        validate::check(&name, validate::Target::host()).map_err(|why| (name.as_str(), why))
+    } else {
+        validate::portable_path(&unescaped)
+    };
+    if let Err((part, why)) = valid {
+        // `--sanitize` renames it instead, when every name on the line has a fix
+        let fixable = popts.sanitize && split_names(&name, popts.separator()).iter().all(|n| validate::sanitize_path(n).is_some());
+        if !fixable {
+            trace.rejected = Some((part.to_string(), why));
+            return Err("invalid file name");
+        }
+    }
+
+    // Calculate indent dynamically: count CHARACTERS (not bytes) before name
+    // Look for where the name starts in character count form
+    let chars_before_name = match indent_anchor {
+        Some(anchor) => line[..anchor.as_ptr() as usize - line.as_ptr() as usize].chars().count(),
+        None => line.chars()
+            .take_while(|c| !name_part.starts_with(&c.to_string()))
+            .count(),
+    };
+    trace.chars_before_name = chars_before_name;
+
+    // Measured in display columns, so full-width spaces and double-width box
+    // glyphs line up with the ASCII layout
+    let prefix: String = line.chars().take(chars_before_name).collect();
+    let compact = marker.is_some_and(|found| found.compact);
+    let width = glyphs::columns(&prefix, compact, 4);
+
+    // Every 4 columns = 1 indent level
+    let indent = glyphs::columns(&prefix, compact, 1) / 4;
+
+    let kind = if is_dir {
+        NodeKind::Dir
+    } else {
+        special.unwrap_or(match listed_type {
+            Some('l') => NodeKind::Symlink,
+            Some('p') => NodeKind::Fifo,
+            Some('s') => NodeKind::Socket,
+            Some('D') => NodeKind::Door,
+            _ => NodeKind::File,
+        })
+    };
+
+    Ok(TreeNode {
+        indent,
+        width,
+        name,
+        kind,
+        // An annotated mode with an execute bit counts like a `*` suffix
+        executable: (executable || mode_executable(&annotations)) && kind == NodeKind::File,
+        kind_explicit: slashed.is_some() || executable || special.is_some() || listed_type.is_some() || sourced,
+        attrs,
+        annotations,
+        contents: None,
+    })
+}
+
+// A tree line is a name (at most 255 bytes) plus glyphs, attribute columns and
+// a comment. Anything far longer is data such as minified JSON or base64.
+// Heredoc bodies are file contents and may be longer.
+const MAX_LINE_BYTES: usize = 4096;
+
+// The start of an over-long line, for messages
+fn preview(line: &str) -> String {
+    match line.char_indices().nth(40) {
+        Some((pos, _)) => format!("{}…", &line[..pos]),
+        None => line.to_string(),
+    }
+}
+
+// First line over the limit, as (index, length)
+fn overlong_line(content: &str) -> Option<(usize, usize)> {
+    content.lines().enumerate().find(|(_, line)| line.len() > MAX_LINE_BYTES).map(|(i, line)| (i, line.len()))
+}
+
+/// Whether `content` looks like a tree at all: box-drawing connectors, an
+/// indented listing or an outline. The `mks` command uses it to refuse
+/// clipboard contents that are something else.
+pub fn looks_like_tree(content: &str) -> bool {
+    // A minified blob fails here, before anything scans it for glyphs
+    let usable = content.lines().filter(|line| line.len() <= MAX_LINE_BYTES && !line.trim().is_empty()).count();
+    if usable < 2 {
+        return false;
+    }
+
+    // Headings alone are an outline
+    if outline::is_outline(content) {
+        return true;
+    }
+
+    // If it has at least one Unicode character tree, OK
+    if content.chars().any(glyphs::is_box_glyph) {
+        return content.lines().count() >= 2;
+    }
+
+    // Try indentation/space based tree structure detection
+    let mut indented_lines = 0;
+    for line in content.lines().skip(1) {
+        let trimmed_start = line.trim_start();
+        if !trimmed_start.is_empty() && line.len() > trimmed_start.len() {
+            indented_lines += 1;
+        }
+    }
+
+    indented_lines >= 2 && content.lines().count() >= 2
+}
+
+// Flush a directory entry to disk so its creation survives a power loss.
+// Directories can only be opened for syncing on Unix; elsewhere this is a no-op.
+#[cfg(unix)]
+fn sync_dir(path: &Path) -> io::Result<()> {
+    File::open(path)?.sync_all()
+}
+
+#[cfg(not(unix))]
+fn sync_dir(_path: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+// Sync every directory from `path` up to the working directory, so that each
+// newly created level is durable and not just the leaf.
+fn sync_ancestors(path: &Path) -> io::Result<()> {
+    for dir in path.ancestors() {
+        if dir.as_os_str().is_empty() {
+            sync_dir(Path::new("."))?;
+        } else {
+            sync_dir(dir)?;
+        }
+    }
+    Ok(())
+}
+
+fn make_dir(path: &Path, durable: bool) -> io::Result<()> {
+    fs::create_dir_all(path)?;
+    if durable {
+        sync_ancestors(path)?;
+    }
+    Ok(())
+}
+
+fn make_file(path: &Path, contents: &[u8], durable: bool) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)?;
+        }
+    }
+    let mut file = File::create(path)?;
+    if !contents.is_empty() {
+        file.write_all(contents)?;
+    }
+    if durable {
+        file.sync_all()?;
+        sync_ancestors(path.parent().unwrap_or(Path::new("")))?;
+    }
+    Ok(())
+}
+
+/// How [`materialize`] writes. `Options::default()` behaves like a plain
+/// `mks` run.
+pub struct Options {
+    parse: ParseOptions,
+    debug: bool,
+    durable: bool,
+    // Seed executable files with an interpreter line guessed from the extension
+    shebang: bool,
+    order: Order,
+    // `--honor-attrs`: apply mode/owner/mtime from bracketed tree columns
+    honor_attrs: bool,
+    // Create through directories that are symlinks on disk (`--follow`, default)
+    // or refuse to (`--no-follow`)
+    follow_symlinks: bool,
+    // Make `name -> target` entries links; `--no-symlinks` makes plain copies
+    symlinks: bool,
+    // Where clipboard input is read from (`--selection`); None when the
+    // clipboard was not enabled with `--clipboard` or the config
+    selection: Option<clip::Selection>,
+    icons: icons::IconTheme,
+    // Config `protect` patterns; matching files are never overwritten
+    protect: globset::GlobSet,
+    // `--progress-fd`: JSON progress records for GUI wrappers
+    progress: Option<progress::Progress>,
+    // Follow `.editorconfig` when writing file contents (`--no-editorconfig` turns it off)
+    editorconfig: bool,
+    // `--posix-paths`: show resolved paths with '/' on every OS
+    posix_paths: bool,
+    // `--rollback-on-error`: remove what a partly failed run created, without asking
+    rollback_on_error: bool,
+    on_conflict: OnConflict,
+    // `--merge`: the tree is a delta; existing files are kept and entries
+    // whose path holds something of another kind are reported, not failed
+    merge: bool,
+    // `--respect-gitignore`: the target's `.gitignore` counts like its `.mksignore`
+    respect_gitignore: bool,
+    // `--placeholders`: bodies for empty files, by extension
+    placeholders: Option<placeholder::Registry>,
+    // `--format text|json|yaml`; None goes by the file extension
+    format: Option<String>,
+    // `--fence-index N`: which fenced block of a Markdown input to read
+    fence: Option<usize>,
+    // Size and time limits for `name <= url` contents
+    download: fetch::Limits,
+    // `{{name}}` values from `--vars` and `--var`
+    vars: vars::Vars,
+    // `-i/--interactive`: confirm the plan, then each file that would be overwritten
+    interactive: Option<interactive::Conflicts>,
+    // `--timeout` (or config `timeout`): no new operations once it has passed
+    deadline: Option<timeout::Deadline>,
+    // `--jobs N`: threads creating the entries of one depth; 1 keeps plan order
+    jobs: usize,
+    // Set by `materialize`: no status lines, the caller gets a `Report`
+    quiet: bool,
+    // `--simulate-failure`: entries that fail on purpose, for testing wrappers
+    #[cfg(feature = "test-utils")]
+    simulate_failure: Option<globset::GlobSet>,
+}
+
+// Find the first existing directory on the way to `path` (below `base`) that
+// is actually a symlink. For directories the path itself counts, since
+// children land in it.
+fn symlinked_ancestor(base: &Path, path: &Path, is_dir: bool) -> Option<PathBuf> {
+    let components: Vec<_> = path.components().collect();
+    let checked = if is_dir { components.len() } else { components.len().saturating_sub(1) };
+
+    let mut current = PathBuf::new();
+    for component in &components[..checked] {
+        current.push(component);
+        match fs::symlink_metadata(escape::long_path(&base.join(&current))) {
+            Ok(meta) if meta.file_type().is_symlink() => return Some(current),
+            Ok(_) => {}
+            // Nothing exists past this point, so nothing further can be a link
+            Err(_) => return None,
+        }
+    }
+    None
+}
+
+// Interpreter line for a new executable file, chosen by extension.
+// Extension-less executables are assumed to be POSIX shell scripts.
+fn shebang_for(path: &str) -> Option<&'static str> {
+    let ext = Path::new(path).extension().map(|e| e.to_string_lossy().to_lowercase());
+    match ext.as_deref() {
+        None | Some("sh") => Some("#!/bin/sh\n"),
+        Some("bash") => Some("#!/usr/bin/env bash\n"),
+        Some("zsh") => Some("#!/usr/bin/env zsh\n"),
+        Some("fish") => Some("#!/usr/bin/env fish\n"),
+        Some("py") => Some("#!/usr/bin/env python3\n"),
+        Some("rb") => Some("#!/usr/bin/env ruby\n"),
+        Some("pl") => Some("#!/usr/bin/env perl\n"),
+        Some("js") | Some("mjs") => Some("#!/usr/bin/env node\n"),
+        Some("php") => Some("#!/usr/bin/env php\n"),
+        _ => None,
+    }
+}
+
+// Add the exec bit wherever the read bit is set, like `chmod +x`.
+#[cfg(unix)]
+fn set_executable(path: &Path) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mut perms = fs::metadata(path)?.permissions();
+    let mode = perms.mode();
+    perms.set_mode(mode | ((mode & 0o444) >> 2));
+    fs::set_permissions(path, perms)
+}
+
+#[cfg(not(unix))]
+fn set_executable(_path: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+#[cfg(unix)]
+fn make_fifo(path: &Path) -> io::Result<()> {
+    use std::os::unix::ffi::OsStrExt;
+    let c_path = std::ffi::CString::new(path.as_os_str().as_bytes())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "path contains a NUL byte"))?;
+    // SAFETY: `c_path` is a valid NUL-terminated string for the duration of the call
+    if unsafe { libc::mkfifo(c_path.as_ptr(), 0o644) } != 0 {
+        let err = io::Error::last_os_error();
+        if err.kind() != io::ErrorKind::AlreadyExists {
+            return Err(err);
+        }
+    }
+    Ok(())
+}
+
+// Binding a listener leaves the socket file behind once it is dropped
+#[cfg(unix)]
+fn make_socket(path: &Path) -> io::Result<()> {
+    if fs::symlink_metadata(path).is_ok() {
+        return Ok(());
+    }
+    std::os::unix::net::UnixListener::bind(path).map(drop)
+}
+
+#[cfg(not(unix))]
+fn make_fifo(_path: &Path) -> io::Result<()> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, t!("fifos are not supported on this platform")))
+}
+
+#[cfg(not(unix))]
+fn make_socket(_path: &Path) -> io::Result<()> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, t!("sockets are not supported on this platform")))
+}
+
+// What happened to a single planned entry
+#[derive(Serialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+enum EntryOutcome {
+    Created,
+    // Already on disk and reused (directories, fifos, sockets, and files
+    // with `--on-conflict=skip`)
+    Existed,
+    // A file that was already on disk and has been rewritten
+    Replaced,
+    // Like `Replaced`, after the old file was renamed to `backup`
+    BackedUp { backup: String },
+    // An existing protected file that was left alone
+    Protected,
+    Skipped { reason: String },
+    // `--merge`: something of another kind is already there and was left alone
+    Conflict { reason: String },
+    // Created, then removed again after another entry failed
+    RolledBack,
+    // `kind` is the I/O error kind (`permission_denied`, `not_found`, ...);
+    // `source` is kept for the library's `EntryError`
+    Failed {
+        kind: String,
+        reason: String,
+        #[serde(skip)]
+        source: Option<std::sync::Arc<io::Error>>,
+    },
+}
+
+// `PermissionDenied` -> `permission_denied`; `other` for non-I/O errors
+fn error_kind(e: &(dyn std::error::Error + 'static)) -> String {
+    let Some(io_error) = e.downcast_ref::<io::Error>() else {
+        return "other".to_string();
+    };
+    let mut kind = String::new();
+    for c in format!("{:?}", io_error.kind()).chars() {
+        if c.is_uppercase() && !kind.is_empty() {
+            kind.push('_');
+        }
+        kind.push(c.to_ascii_lowercase());
+    }
+    kind
+}
+
+// What to write into a new file, shaped by `.editorconfig` unless disabled
+fn file_bytes(path: &Path, contents: Option<&str>, opts: &Options) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let text = contents.unwrap_or("");
+    if !opts.editorconfig {
+        return Ok(text.as_bytes().to_vec());
+    }
+    editorconfig::render(path, text)
+}
+
+// `--placeholders`: what a file the tree leaves empty gets instead
+fn placeholder_body<'a>(entry: &PlannedEntry, shebang: Option<&str>, opts: &'a Options) -> Option<&'a [u8]> {
+    let registry = opts.placeholders.as_ref()?;
+    if entry.contents.is_some() || shebang.is_some() {
+        return None;
+    }
+    registry.body(&entry.path)
+}
+
+fn create_entry(
+    entry: &PlannedEntry,
+    base: &Path,
+    protected: bool,
+    opts: &Options,
+    through_links: &mut Vec<(String, PathBuf)>,
+) -> Result<EntryOutcome, Box<dyn std::error::Error>> {
+    let (path, kind) = (entry.path.as_str(), entry.kind);
+    #[cfg(feature = "test-utils")]
+    simulated_failure(path, opts)?;
+    // Names with `\xHH` escapes only become real (non-UTF-8) paths here
+    let relative = escape::os_path(path);
+    let fs_path = escape::long_path(&base.join(&relative));
+    if let Some(link) = symlinked_ancestor(base, &relative, kind == NodeKind::Dir) {
+        if !opts.follow_symlinks {
+            return Err(t!(
+                "'{}' would be created through symlink '{}' (--no-follow)",
+                path,
+                link.display()
+            )
+            .into());
+        }
+        through_links.push((path.to_string(), link));
+    }
+
+    let existing = fs::symlink_metadata(&fs_path).ok();
+    let existed = existing.is_some();
+    if existed && protected && kind == NodeKind::File {
+        return Ok(EntryOutcome::Protected);
+    }
+    if let (true, dry_run::OnDisk::Conflict(found)) = (opts.merge, dry_run::on_disk(&fs_path, kind)) {
+        return Ok(EntryOutcome::Conflict { reason: t!("a {} is in the way", i18n::tr(found.label())) });
+    }
+    let mut back_up_first = false;
+    if kind == NodeKind::File && existing.is_some_and(|meta| !meta.is_dir()) {
+        match opts.on_conflict {
+            OnConflict::Overwrite => {
+                if opts.interactive.as_ref().is_some_and(|prompt| !prompt.overwrite(path)) {
+                    return Ok(EntryOutcome::Existed);
+                }
+            }
+            OnConflict::Skip => return Ok(EntryOutcome::Existed),
+            OnConflict::Error => return Err(t!("'{}' already exists (--on-conflict=error)", path).into()),
+            OnConflict::Backup => back_up_first = true,
+        }
+    }
+    // Downloaded before anything on disk changes, so a failed fetch leaves
+    // an existing file where it was
+    let downloaded = match entry.annotation_value("src") {
+        Some(url) if kind == NodeKind::File => Some(fetch::download(url, entry.annotation_value("sha256"), opts.download)?),
+        _ => None,
+    };
+    let backup = if back_up_first { Some(back_up(&fs_path, path)?) } else { None };
+    let contents = entry.contents.as_deref();
+    let special = match kind {
+        NodeKind::Fifo => make_fifo(&fs_path),
+        NodeKind::Socket => make_socket(&fs_path),
+        _ => Ok(()),
+    };
+    match special {
+        Err(e) if e.kind() == io::ErrorKind::Unsupported => return Ok(EntryOutcome::Skipped { reason: e.to_string() }),
+        Err(e) => return Err(e.into()),
+        Ok(()) => {}
+    }
+
+    match kind {
+        NodeKind::Dir => make_dir(&fs_path, opts.durable)?,
+        NodeKind::File => {
+            let shebang = if entry.executable && opts.shebang { shebang_for(path) } else { None };
+            // Downloads are written byte for byte, so their checksum still holds
+            let bytes = match (downloaded, placeholder_body(entry, shebang, opts)) {
+                (Some(bytes), _) => bytes,
+                // Binary, so .editorconfig has no say
+                (None, Some(body)) => body.to_vec(),
+                (None, None) => file_bytes(&fs_path, contents.or(shebang), opts)?,
+            };
+            make_file(&fs_path, &bytes, opts.durable)?;
+            if entry.executable {
+                set_executable(&fs_path)?;
+            }
+        }
+        NodeKind::Fifo | NodeKind::Socket => {}
+        NodeKind::Symlink if entry.annotation_value("target").is_some() => {
+            return create_link(path, entry.annotation_value("target").unwrap_or_default(), &base.join(&relative), opts);
+        }
+        NodeKind::Symlink | NodeKind::Door => {
+            let reason = t!("a {} cannot be recreated from a listing", i18n::tr(kind.label()));
+            return Ok(EntryOutcome::Skipped { reason });
+        }
+    }
+    Ok(match (existed, kind, backup) {
+        (false, _, _) => EntryOutcome::Created,
+        (true, NodeKind::File, Some(backup)) => EntryOutcome::BackedUp { backup },
+        (true, NodeKind::File, None) => EntryOutcome::Replaced,
+        (true, _, _) => EntryOutcome::Existed,
+    })
+}
+
+// `name -> target`: a symbolic link, or with `--no-symlinks` a stand-in: a
+// folder for a folder target, else a copy of the target file (empty when it
+// does not exist). Whatever is already at the path is left alone.
+fn create_link(path: &str, target: &str, link: &Path, opts: &Options) -> Result<EntryOutcome, Box<dyn std::error::Error>> {
+    let link_target = Path::new(target.trim_end_matches('/'));
+    // Joined before extending: a `\\?\` path would keep the target's `..` as a name
+    let resolved = escape::long_path(&link.parent().unwrap_or(Path::new("")).join(link_target));
+    let fs_path = &escape::long_path(link);
+    let is_dir = target.ends_with('/') || resolved.is_dir();
+    let existing = fs::symlink_metadata(fs_path).ok();
+    if !opts.symlinks {
+        if existing.is_some() {
+            return Ok(EntryOutcome::Existed);
+        }
+        if is_dir {
+            make_dir(fs_path, opts.durable)?;
+        } else {
+            let bytes = if resolved.is_file() { fs::read(&resolved)? } else { Vec::new() };
+            make_file(fs_path, &bytes, opts.durable)?;
+        }
+        return Ok(EntryOutcome::Created);
+    }
+    match existing {
+        Some(meta) if meta.file_type().is_symlink() => {
+            let current = fs::read_link(fs_path)?;
+            // Junctions always hold an absolute target
+            if current == link_target || std::path::absolute(&resolved).is_ok_and(|absolute| current == absolute) {
+                return Ok(EntryOutcome::Existed);
+            }
+            Err(t!("'{}' already links to '{}'", path, current.display()).into())
+        }
+        Some(meta) => {
+            let found = if meta.is_dir() { NodeKind::Dir } else { NodeKind::File };
+            Err(t!("a {} is in the way", i18n::tr(found.label())).into())
+        }
+        None => match make_link(target.trim_end_matches('/'), fs_path, is_dir) {
+            Ok(()) => Ok(EntryOutcome::Created),
+            Err(e) if e.kind() == io::ErrorKind::Unsupported => Ok(EntryOutcome::Skipped { reason: e.to_string() }),
+            Err(e) => Err(e.into()),
+        },
+    }
+}
+
+#[cfg(unix)]
+fn make_link(target: &str, path: &Path, _is_dir: bool) -> io::Result<()> {
+    std::os::unix::fs::symlink(target, path)
+}
+
+// Symbolic links need Developer Mode or an elevated prompt. A junction does
+// not, so folders fall back to one; it has to point at an absolute path.
+#[cfg(windows)]
+fn make_link(target: &str, path: &Path, is_dir: bool) -> io::Result<()> {
+    use std::os::windows::fs::{symlink_dir, symlink_file};
+    let target = PathBuf::from(target.replace('/', "\\"));
+    if !is_dir {
+        return symlink_file(&target, path);
+    }
+    symlink_dir(&target, path).or_else(|e| {
+        let absolute = std::path::absolute(path.parent().unwrap_or(Path::new("")).join(&target))?;
+        let status = std::process::Command::new("cmd")
+            .args(["/C", "mklink", "/J"])
+            .arg(path)
+            .arg(&absolute)
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(e)
+        }
+    })
+}
+
+#[cfg(not(any(unix, windows)))]
+fn make_link(_target: &str, _path: &Path, _is_dir: bool) -> io::Result<()> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, t!("symlinks are not supported on this platform")))
+}
+
+// Rename an existing file to the first free `name.bak.N`; returns the new
+// plan path for the report
+fn back_up(fs_path: &Path, path: &str) -> io::Result<String> {
+    for n in 1.. {
+        let mut name = fs_path.file_name().unwrap_or_default().to_os_string();
+        name.push(format!(".bak.{}", n));
+        let target = fs_path.with_file_name(name);
+        if fs::symlink_metadata(&target).is_err() {
+            fs::rename(fs_path, &target)?;
+            return Ok(format!("{}.bak.{}", path, n));
+        }
+    }
+    unreachable!()
+}
+
+// Leave out the entries that the `.mksignore` in `base` (and its `.gitignore`
+// with --respect-gitignore) matches, so they are neither created nor
+// overwritten. Only the topmost ignored entries are listed.
+fn drop_ignored(plan: &mut Vec<PlannedEntry>, base: &Path, opts: &Options) -> Result<(), Box<dyn std::error::Error>> {
+    let names: &[&str] = if opts.respect_gitignore { &[dump::IGNORE_FILE, ".gitignore"] } else { &[dump::IGNORE_FILE] };
+    let ignore = dump::load_ignore_files(base, names)?;
+    if ignore.is_empty() {
+        return Ok(());
+    }
+    let (mut dropped, mut shown): (usize, Vec<String>) = (0, Vec::new());
+    plan.retain(|entry| {
+        let is_dir = entry.kind == NodeKind::Dir;
+        if !ignore.matched_path_or_any_parents(&entry.path, is_dir).is_ignore() {
+            return true;
+        }
+        dropped += 1;
+        if !shown.iter().any(|dir| entry.path.starts_with(&format!("{}/", dir))) {
+            shown.push(entry.path.clone());
+        }
+        false
+    });
+    if dropped > 0 {
+        let files = names.iter().filter(|name| base.join(name).is_file()).copied().collect::<Vec<_>>().join(", ");
+        status!("🙈 {}", t!("{} entries matched by {} were left out:", dropped, files));
+        for path in &shown {
+            status!("   {}", path);
+        }
+    }
+    Ok(())
+}
+
+// `--on-conflict=error` refuses before anything is created: the files
+// below `base` that are already there and not protected
+fn existing_files(plan: &[PlannedEntry], base: &Path, opts: &Options) -> Vec<String> {
+    let protection = protected_entries(plan, opts);
+    plan.iter()
+        .zip(protection)
+        .filter(|(entry, protected)| entry.kind == NodeKind::File && !protected)
+        .filter(|(entry, _)| fs::symlink_metadata(escape::long_path(&base.join(escape::os_path(&entry.path)))).is_ok_and(|meta| !meta.is_dir()))
+        .map(|(entry, _)| entry.path.clone())
+        .collect()
+}
+
+// Matching paths fail the way an unwritable directory would, before anything
+// touches the disk, so the partial-failure output is the real one
+#[cfg(feature = "test-utils")]
+fn simulated_failure(path: &str, opts: &Options) -> io::Result<()> {
+    let Some(patterns) = &opts.simulate_failure else {
+        return Ok(());
+    };
+    let name = Path::new(path).file_name().unwrap_or_default();
+    if patterns.is_match(path) || patterns.is_match(name) {
+        return Err(io::Error::new(io::ErrorKind::PermissionDenied, t!("simulated failure (--simulate-failure)")));
+    }
+    Ok(())
+}
+
+// `--simulate-failure <glob>`, repeatable. Only test-utils builds have it.
+fn simulate_failure_set(patterns: &[String]) -> Result<Option<globset::GlobSet>, Box<dyn std::error::Error>> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+    if !cfg!(feature = "test-utils") {
+        return Err(t!("--simulate-failure needs a build with the 'test-utils' feature").into());
+    }
+    let mut builder = globset::GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = globset::Glob::new(pattern).map_err(|e| t!("--simulate-failure pattern '{}': {}", pattern, e))?;
+        builder.add(glob);
+    }
+    Ok(Some(builder.build()?))
+}
+
+// A parsed node placed in the hierarchy
+struct Placed {
+    node: TreeNode,
+    // Names after splitting on '&'
+    names: Vec<String>,
+    // One tree node per name
+    ids: Vec<tree::NodeId>,
+    // Folders an inline path (`src/utils/helpers.rs`) added on the way to them
+    implied: Vec<tree::NodeId>,
+    // Directory (or followed link) the names are created under
+    parent: Option<tree::NodeId>,
+    // Indent was deeper than the stack allowed, kept at the current level
+    clamped: bool,
+    // Childless, extension-less name whose kind came from `--assume` (or the file default)
+    kind_guessed: bool,
+}
+
+struct ResolvedLine {
+    idx: usize,
+    trace: LineTrace,
+    placed: Result<Placed, &'static str>,
+}
+
+// Levels for `--infer-hierarchy`, one per line (None for unparsable lines).
+// A line is one level below the closest shallower line before it, and widths
+// within one column of an open level (sloppy pastes) share that level.
+fn infer_indent_levels(lines: &[String], popts: &ParseOptions) -> Vec<Option<usize>> {
+    let mut open: Vec<usize> = Vec::new();
+    let mut in_heredoc = vec![false; lines.len()];
+    for block in heredoc_blocks(lines, popts) {
+        in_heredoc[block.opener..=block.closer].fill(true);
+    }
+    lines
+        .iter()
+        .zip(in_heredoc)
+        .map(|(line, in_heredoc)| {
+            if in_heredoc {
+                return None;
+            }
+            let width = parse_tree_line(line, popts).ok()?.width;
+            while open.last().is_some_and(|&top| top > width + 1) {
+                open.pop();
+            }
+            match open.last() {
+                Some(&top) if width.abs_diff(top) <= 1 => {}
+                _ => open.push(width),
+            }
+            Some(open.len() - 1)
+        })
+        .collect()
+}
+
+// Which levels each indent width was mapped to, for reporting
+fn indent_level_mapping(lines: &[String], popts: &ParseOptions) -> BTreeMap<usize, BTreeSet<usize>> {
+    let mut mapping: BTreeMap<usize, BTreeSet<usize>> = BTreeMap::new();
+    for (line, level) in lines.iter().zip(infer_indent_levels(lines, popts)) {
+        if let (Ok(node), Some(level)) = (parse_tree_line(line, popts), level) {
+            mapping.entry(node.width).or_default().insert(level);
+        }
+    }
+    mapping
+}
+
+// A `<<TAG` … `TAG` block, a fenced block or `>` lines holding the contents
+// of the file node above it
+struct HeredocBlock {
+    opener: usize,
+    closer: usize,
+    contents: String,
+}
+
+// Leading whitespace and `│` continuation glyphs of tree output
+fn strip_gutter(line: &str) -> &str {
+    line.trim_start_matches(|c: char| c.is_whitespace() || glyphs::is_vertical(c))
+}
+
+// `<<EOF`, `<<-EOF`, `<<'EOF'` or `<<"EOF"` on a line of its own
+fn heredoc_tag(line: &str) -> Option<&str> {
+    let rest = strip_gutter(line).trim_end().strip_prefix("<<")?;
+    let rest = rest.strip_prefix('-').unwrap_or(rest);
+    let tag = rest.trim_matches(|c| c == '\'' || c == '"');
+    let valid = tag.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && tag.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    valid.then_some(tag)
+}
+
+// "```" or "~~~" (or longer) below a file, as the fence and its length
+fn content_fence(line: &str) -> Option<(char, usize)> {
+    let rest = strip_gutter(line);
+    let marker = rest.chars().next().filter(|&c| c == '`' || c == '~')?;
+    let len = rest.chars().take_while(|&c| c == marker).count();
+    (len >= 3 && rest.len() < line.len()).then_some((marker, len))
+}
+
+// The text of an indented `> text` line; at the start of a line, `>` is a
+// shell prompt instead
+fn quoted_content(line: &str) -> Option<&str> {
+    let rest = strip_gutter(line);
+    if rest.len() == line.len() {
+        return None;
+    }
+    let text = rest.strip_prefix('>')?;
+    Some(text.strip_prefix(' ').unwrap_or(text))
+}
+
+// Columns of indentation or `│` glyphs a body line loses, up to `column`
+fn body_line(line: &str, column: usize) -> &str {
+    let gutter = line
+        .chars()
+        .take(column)
+        .take_while(|&c| c.is_whitespace() || glyphs::is_vertical(c))
+        .map(char::len_utf8)
+        .sum::<usize>();
+    &line[gutter..]
+}
+
+// Body lines lose the columns up to where `<<` (or the fence) started, as
+// long as those are indentation or `│` glyphs; anything beyond is kept
+// verbatim. Quoted lines lose the `>` and one space after it.
+fn heredoc_blocks(lines: &[String], popts: &ParseOptions) -> Vec<HeredocBlock> {
+    let mut blocks = Vec::new();
+    if popts.raw_names {
+        return blocks;
+    }
+    let mut i = 0;
+    while i < lines.len() {
+        let column = lines[i].chars().count() - strip_gutter(&lines[i]).chars().count();
+        let closer = if let Some(tag) = heredoc_tag(&lines[i]) {
+            lines[i + 1..].iter().position(|l| strip_gutter(l).trim_end() == tag).map(|offset| i + 1 + offset)
+        } else if let Some((marker, len)) = content_fence(&lines[i]) {
+            let closes = |l: &String| {
+                let fence = strip_gutter(l).trim_end();
+                fence.chars().count() >= len && fence.chars().all(|c| c == marker)
+            };
+            lines[i + 1..].iter().position(closes).map(|offset| i + 1 + offset)
+        } else if quoted_content(&lines[i]).is_some() {
+            let count = lines[i..].iter().take_while(|l| quoted_content(l).is_some()).count();
+            let contents: String = lines[i..i + count].iter().filter_map(|l| quoted_content(l)).map(|text| format!("{}\n", text)).collect();
+            blocks.push(HeredocBlock { opener: i, closer: i + count - 1, contents });
+            i += count;
+            continue;
+        } else {
+            None
+        };
+        let Some(closer) = closer else {
+            i += 1;
+            continue;
+        };
+        let contents: String = lines[i + 1..closer].iter().map(|line| format!("{}\n", body_line(line, column))).collect();
+        blocks.push(HeredocBlock { opener: i, closer, contents });
+        i = closer + 1;
+    }
+    blocks
+}
+
+// A blank line followed by a name without a connector, whose children's
+// connectors start in the same column as that name, begins a tree of its
+// own (`frontend/ ... <blank> backend/ ...`). Its lines are shifted so the
+// name is a root, however far the block is indented. Returns the shift per line.
+fn separate_tree_offsets(lines: &[String], parsed: &[(LineTrace, Result<TreeNode, &'static str>)]) -> Vec<Option<usize>> {
+    let leading = |i: usize| lines[i].len() - lines[i].trim_start().len();
+    let is_blank = |i: usize| lines[i].trim().is_empty() && !matches!(parsed[i].1, Err("heredoc content"));
+    let mut offsets = vec![None; lines.len()];
+    let mut current = None;
+    let mut after_blank = false;
+    for i in 0..lines.len() {
+        if is_blank(i) {
+            after_blank = true;
+            current = None;
+            continue;
+        }
+        let Ok(node) = &parsed[i].1 else {
+            continue;
+        };
+        if after_blank && parsed[i].0.marker.is_none() {
+            let next = (i + 1..lines.len())
+                .take_while(|&j| !is_blank(j))
+                .find(|&j| parsed[j].1.is_ok());
+            let tree_shaped = next.is_some_and(|j| parsed[j].0.marker.is_some() && leading(j) == leading(i));
+            if tree_shaped {
+                current = Some(node.indent);
+            }
+        }
+        after_blank = false;
+        offsets[i] = current;
+    }
+    offsets
+}
+
+// A file that has lines indented below it
+struct FileParent {
+    line: usize,
+    name: String,
+    // First line indented below it
+    child: usize,
+    root: bool,
+}
+
+// Every input line with its outcome, plus the tree the placed nodes form
+struct Resolved {
+    lines: Vec<ResolvedLine>,
+    tree: tree::Tree,
+    file_parents: Vec<FileParent>,
+}
+
+impl Resolved {
+    // Contradictions the parser will not guess its way around
+    fn check(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(file) = self.file_parents.first() else {
+            return Ok(());
+        };
+        if file.root {
+            return Err(t!(
+                "line {}: root '{}' is a file, but line {} is indented below it; write it as '{}/' or pass --promote-root-to-dir",
+                file.line + 1,
+                file.name,
+                file.child + 1,
+                file.name
+            )
+            .into());
+        }
+        Err(t!(
+            "line {}: '{}' is a file but has children (line {} is indented below it); write it as '{}/' or pass --auto-dir",
+            file.line + 1,
+            file.name,
+            file.child + 1,
+            file.name
+        )
+        .into())
+    }
+}
+
+// Parse every line and work out where each node lands, without touching disk
+fn resolve_lines(lines: &[String], popts: &ParseOptions) -> Resolved {
+    // An Org or Markdown outline becomes an indented tree, line for line
+    let outline = outline::to_indented(lines);
+    let lines = outline.as_deref().unwrap_or(lines);
+    let mut tree = tree::Tree::default();
+    // Containers that are still open, outermost first
+    let mut open: Vec<tree::NodeId> = Vec::new();
+    // Folders by (parent, name), for the parts of inline paths to reuse
+    let mut folders: BTreeMap<(Option<tree::NodeId>, String), tree::NodeId> = BTreeMap::new();
+    // Those of them that no line has listed itself yet
+    let mut unlisted: BTreeSet<tree::NodeId> = BTreeSet::new();
+    let mut resolved = Vec::with_capacity(lines.len());
+    let levels = if popts.infer_hierarchy {
+        infer_indent_levels(lines, popts)
+    } else if let Some(style) = indent::detect(lines, popts) {
+        indent::levels(lines, popts, style)
+    } else {
+        Vec::new()
+    };
+
+    let mut parsed: Vec<(LineTrace, Result<TreeNode, &'static str>)> = lines
+        .iter()
+        .enumerate()
+        .map(|(idx, line)| {
+            let mut trace = LineTrace::default();
+            let node = parse_tree_line_traced(line, popts, &mut trace).map(|mut node| {
+                if let Some(Some(level)) = levels.get(idx) {
+                    node.indent = *level;
+                }
+                node
+            });
+            (trace, node)
+        })
+        .collect();
+
+    // Hand each heredoc block to the file node above it
+    for block in heredoc_blocks(lines, popts) {
+        let owner = parsed[..block.opener].iter().rposition(|(_, node)| node.is_ok());
+        for line in &mut parsed[block.opener..=block.closer] {
+            line.1 = Err("heredoc content");
+        }
+        match owner.and_then(|i| parsed[i].1.as_mut().ok()) {
+            Some(node) if node.kind == NodeKind::File || !node.kind_explicit => {
+                node.kind = NodeKind::File;
+                node.kind_explicit = true;
+                node.contents = Some(block.contents);
+            }
+            _ => parsed[block.opener].1 = Err("heredoc without a file node above it"),
+        }
+    }
+
+    // Several `tree` outputs pasted one after another stay separate trees
+    for (idx, offset) in separate_tree_offsets(lines, &parsed).into_iter().enumerate() {
+        if let (Some(offset), Ok(node)) = (offset, &mut parsed[idx].1) {
+            node.indent = node.indent.saturating_sub(offset);
+        }
+    }
+
+    // A name without '/' or extension that has no children could be either
+    // kind (`bin`, `docs`, `LICENSE`, `Makefile`); settle it with `--assume`
+    let mut guessed = vec![false; parsed.len()];
+    let mut parents = vec![false; parsed.len()];
+    let mut file_parents = Vec::new();
+    for i in 0..parsed.len() {
+        let next = (i + 1..parsed.len()).find_map(|j| parsed[j].1.as_ref().ok().map(|n| (j, n.indent)));
+        let (trace, node) = &mut parsed[i];
+        if let Ok(node) = node {
+            let has_children = next.is_some_and(|(_, indent)| indent > node.indent);
+            parents[i] = has_children;
+            // A file cannot hold the lines indented below it
+            if has_children && node.kind == NodeKind::File {
+                let root = node.indent == 0;
+                if root && popts.promote_root {
+                    node.kind = NodeKind::Dir;
+                    trace.stripped.push("file kind (root promoted to a folder)".to_string());
+                } else if popts.auto_dir {
+                    node.kind = NodeKind::Dir;
+                    trace.stripped.push("file kind (has children, --auto-dir)".to_string());
+                } else if let Some((child, _)) = next {
+                    file_parents.push(FileParent { line: i, name: node.name.clone(), child, root });
+                }
+            }
+            let ambiguous = !node.kind_explicit
+                && !node.name.starts_with('.')
+                && Path::new(&node.name).extension().is_none();
+            if ambiguous && !has_children {
+                node.kind = popts.assume.unwrap_or(NodeKind::File);
+                guessed[i] = true;
+            }
+        }
+    }
+
+    for (idx, (trace, parsed)) in parsed.into_iter().enumerate() {
+        let kind_guessed = guessed[idx];
+        let has_children = parents[idx];
+        let placed = parsed.and_then(|node| {
+            // Split name by '&' (or `--multi-sep`) to handle multiple files
+            let names: Vec<String> = if popts.raw_names {
+                vec![node.name.clone()]
+            } else {
+                split_names(&node.name, popts.separator())
+                    .into_iter()
+                    .filter_map(|s| match popts.sanitize {
+                        true => validate::sanitize_path(&s),
+                        false => validate::portable_path(&s).is_ok().then_some(s),
+                    })
+                    .collect()
+            };
+
+            // FIXED: Skip if no valid names after filtering
+            if names.is_empty() {
+                return Err("no valid names after splitting on '&'");
+            }
+
+            // Close containers based on indent; the first entry is always the root
+            // indent=1 means child of root (1 open container = root)
+            // indent=2 means child of level 1 (2 open containers)
+            let mut clamped = false;
+            if !open.is_empty() {
+                if node.indent > open.len() {
+                    // Indent too deep, stay at current level
+                    clamped = true;
+                } else {
+                    open.truncate(node.indent);
+                }
+            }
+            let parent = open.last().copied();
+            let mut implied = Vec::new();
+            let ids: Vec<tree::NodeId> = names
+                .iter()
+                .map(|n| {
+                    // `src/utils/helpers.rs`: the folders on the way are
+                    // implied, and shared with a folder of that name already there
+                    let mut parts: Vec<&str> = n.split('/').collect();
+                    let leaf = parts.pop().unwrap_or(n);
+                    let mut under = parent;
+                    for part in parts {
+                        let id = *folders.entry((under, part.to_string())).or_insert_with(|| {
+                            let id = tree.add(under, part, NodeKind::Dir, idx);
+                            implied.push(id);
+                            unlisted.insert(id);
+                            id
+                        });
+                        under = Some(id);
+                    }
+                    // `src/` listed after `src/utils/helpers.rs` is the folder
+                    // that line implied, not a second one
+                    let id = match folders.get(&(under, leaf.to_string())) {
+                        Some(&id) if node.kind == NodeKind::Dir && unlisted.remove(&id) => id,
+                        _ => tree.add(under, leaf, node.kind, idx),
+                    };
+                    if node.kind == NodeKind::Dir {
+                        folders.entry((under, leaf.to_string())).or_insert(id);
+                    }
+                    id
+                })
+                .collect();
+
+            // Only the FIRST name is opened for children.
+            // A link with children is a followed directory link (`tree -l`,
+            // `mks dump --follow-junctions`); its children nest under it.
+            if node.kind == NodeKind::Dir || (node.kind == NodeKind::Symlink && has_children) {
+                open.push(ids[0]);
+            }
+
+            Ok(Placed { node, names, ids, implied, parent, clamped, kind_guessed })
+        });
+        resolved.push(ResolvedLine { idx, trace, placed });
+    }
+
+    Resolved { lines: resolved, tree, file_parents }
+}
+
+// Creation order for `--depth-first` (default) and `--breadth-first`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Order {
+    DepthFirst,
+    BreadthFirst,
+}
+
+// What happens to a file that is already on disk (`--on-conflict`)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum OnConflict {
+    Overwrite,
+    Skip,
+    Error,
+    // Rename it to `name.bak.N` first
+    Backup,
+}
+
+fn on_conflict(value: Option<&str>) -> Result<OnConflict, Box<dyn std::error::Error>> {
+    match value {
+        None | Some("overwrite") => Ok(OnConflict::Overwrite),
+        Some("skip") => Ok(OnConflict::Skip),
+        Some("error") => Ok(OnConflict::Error),
+        Some("backup") => Ok(OnConflict::Backup),
+        Some(other) => Err(t!("--on-conflict expects skip, overwrite, error or backup, got '{}'", other).into()),
+    }
+}
+
+// `--merge` never touches an existing file, so only `skip` goes with it
+fn merge_policy(merge: bool, value: Option<&str>) -> Result<OnConflict, Box<dyn std::error::Error>> {
+    match (merge, value) {
+        (false, value) => on_conflict(value),
+        (true, None | Some("skip")) => Ok(OnConflict::Skip),
+        (true, Some(other)) => Err(t!("--merge keeps existing files; it cannot be combined with --on-conflict={}", other).into()),
+    }
+}
+
+// One filesystem entry to create
+#[derive(Clone)]
+struct PlannedEntry {
+    // 0-based input line
+    line: usize,
+    path: String,
+    // Number of parent directories in `path`
+    depth: usize,
+    kind: NodeKind,
+    executable: bool,
+    attrs: Option<NodeAttrs>,
+    annotations: Vec<Annotation>,
+    // Initial contents for files; None creates them empty
+    contents: Option<String>,
+}
+
+// Whether a `[mode=...]` annotation sets any execute bit
+fn mode_executable(annotations: &[Annotation]) -> bool {
+    annotations
+        .iter()
+        .filter(|a| a.key == "mode")
+        .filter_map(|a| a.value.as_deref().and_then(attrs::parse_octal_mode))
+        .any(|mode| mode & 0o111 != 0)
+}
+
+impl PlannedEntry {
+    // `[mode=0700]`, `[0700]` or `(700)`
+    fn mode(&self) -> Option<u32> {
+        self.annotation_value("mode").and_then(attrs::parse_octal_mode)
+    }
+
+    fn has_annotation(&self, key: &str) -> bool {
+        self.annotations.iter().any(|a| a.key == key)
+    }
+
+    // Value of the first `[key=value]` annotation
+    fn annotation_value(&self, key: &str) -> Option<&str> {
+        self.annotations.iter().find(|a| a.key == key).and_then(|a| a.value.as_deref())
+    }
+
+    // `[tag=ci]`, `[tag=ci,docs]`; a line may carry several tag annotations
+    fn tags(&self) -> impl Iterator<Item = &str> {
+        self.annotations
+            .iter()
+            .filter(|a| a.key == "tag")
+            .filter_map(|a| a.value.as_deref())
+            .flat_map(|value| value.split(','))
+            .map(str::trim)
+            .filter(|tag| !tag.is_empty())
+    }
+}
+
+// Turn resolved lines into the entries to create, in document order
+fn plan_entries(resolved: Resolved, debug: bool) -> Vec<PlannedEntry> {
+    let mut plan = Vec::new();
+    let tree = &resolved.tree;
+    // Where each implied folder went, for the line that lists it later
+    let mut implied_at: BTreeMap<tree::NodeId, usize> = BTreeMap::new();
+
+    for line in resolved.lines {
+        let idx = line.idx;
+        let placed = match line.placed {
+            Ok(placed) => placed,
+            Err(err_msg) => {
+                if debug {
+                    println!("[DEBUG] Line {} skipped: {}", idx, err_msg);
+                }
+                continue;
+            }
+        };
+        let node = placed.node;
+
+        if debug {
+            println!(
+                "[DEBUG] Line {}: indent={}, name='{}', kind={}, executable={}",
+                idx, node.indent, node.name, node.kind.label(), node.executable
+            );
+            let open = placed.parent.map_or(0, |p| tree.depth(p) + 1);
+            if placed.clamped {
+                eprintln!("⚠️ Warning: indent {} > stack size {}", node.indent, open);
+            }
+            println!("[DEBUG] Parent: '{}'", placed.parent.map(|p| tree.path(p)).unwrap_or_default());
+        }
+
+        for &id in &placed.implied {
+            implied_at.insert(id, plan.len());
+            plan.push(PlannedEntry {
+                line: idx,
+                path: tree.path(id),
+                depth: tree.depth(id),
+                kind: NodeKind::Dir,
+                executable: false,
+                attrs: None,
+                annotations: Vec::new(),
+                contents: None,
+            });
+        }
+
+        // One entry per name from the split
+        for &id in &placed.ids {
+            let tree_node = tree.node(id);
+            let entry = PlannedEntry {
+                line: tree_node.line,
+                path: tree.path(id),
+                depth: tree.depth(id),
+                kind: tree_node.kind,
+                executable: node.executable,
+                attrs: node.attrs.clone(),
+                annotations: node.annotations.clone(),
+                contents: node.contents.clone(),
+            };
+            // The folder keeps its place ahead of its contents, with this line's details
+            match implied_at.get(&id) {
+                Some(&at) => plan[at] = PlannedEntry { line: idx, ..entry },
+                None => plan.push(entry),
+            }
+        }
+    }
+
+    plan
+}
+
+// Depth-first keeps document order, so anything listed earlier (e.g.
+// `Cargo.toml` above `src/`) exists before what follows it. Breadth-first
+// creates level by level, keeping document order within a level.
+// `[after=...]` annotations then move entries behind what they name.
+fn order_plan(plan: &mut Vec<PlannedEntry>, order: Order) -> Result<(), Box<dyn std::error::Error>> {
+    if order == Order::BreadthFirst {
+        // Stable, so document order is kept within each level
+        plan.sort_by_key(|entry| entry.depth);
+    }
+    after::apply(plan)
+}
+
+// Real filesystem results, one typed outcome per planned entry
+#[derive(Default)]
+struct Summary {
+    planned: usize,
+    entries: Vec<(String, EntryOutcome)>,
+    // Folders and files this run wrote, by extension
+    breakdown: breakdown::Breakdown,
+    // Entries not started because `--timeout` had passed
+    timed_out: usize,
+    // `--merge`: the totals are given as created / kept / conflicting
+    merge: bool,
+    // From the first entry to the last; zero when nothing was written
+    elapsed: std::time::Duration,
+}
+
+impl Summary {
+    fn count(&self, matches: fn(&EntryOutcome) -> bool) -> usize {
+        self.entries.iter().filter(|(_, outcome)| matches(outcome)).count()
+    }
+
+    // Paths that did not exist before
+    fn created(&self) -> Vec<String> {
+        self.entries
+            .iter()
+            .filter(|(_, outcome)| matches!(outcome, EntryOutcome::Created))
+            .map(|(path, _)| path.clone())
+            .collect()
+    }
+
+    fn failed(&self) -> usize {
+        self.count(|o| matches!(o, EntryOutcome::Failed { .. }))
+    }
+
+    // (path, reason) of every skipped or failed entry
+    fn problems(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries.iter().filter_map(|(path, outcome)| match outcome {
+            EntryOutcome::Skipped { reason } | EntryOutcome::Failed { reason, .. } => Some((path.as_str(), reason.as_str())),
+            _ => None,
+        })
+    }
+
+    fn conflicts(&self) -> usize {
+        self.count(|o| matches!(o, EntryOutcome::Conflict { .. }))
+    }
+
+    fn print(&self) {
+        if self.merge {
+            status!(
+                "🔀 {}",
+                t!(
+                    "Merged: {} created, {} already there and left as they were, {} conflicting, {} skipped, {} failed",
+                    self.count(|o| matches!(o, EntryOutcome::Created)),
+                    self.count(|o| matches!(o, EntryOutcome::Existed | EntryOutcome::Protected)),
+                    self.conflicts(),
+                    self.count(|o| matches!(o, EntryOutcome::Skipped { .. })),
+                    self.failed()
+                )
+            );
+        } else {
+            status!(
+                "📊 {}",
+                t!(
+                    "{} created, {} already existed, {} replaced, {} skipped, {} failed",
+                    self.count(|o| matches!(o, EntryOutcome::Created)),
+                    self.count(|o| matches!(o, EntryOutcome::Existed)),
+                    self.count(|o| matches!(o, EntryOutcome::Replaced | EntryOutcome::BackedUp { .. })),
+                    self.count(|o| matches!(o, EntryOutcome::Skipped { .. })),
+                    self.failed()
+                )
+            );
+        }
+        let protected: Vec<&str> = self
+            .entries
+            .iter()
+            .filter(|(_, outcome)| matches!(outcome, EntryOutcome::Protected))
+            .map(|(path, _)| path.as_str())
+            .collect();
+        if !protected.is_empty() {
+            status!("🔒 {}", t!("{} protected file(s) already existed and were left untouched:", protected.len()));
+            for path in &protected {
+                status!("   {}", path);
+            }
+        }
+        self.breakdown.print();
+        if !self.elapsed.is_zero() {
+            status!("⏱️ {}", t!("Took {}", elapsed_text(self.elapsed)));
+        }
+        let backups: Vec<(&str, &str)> = self
+            .entries
+            .iter()
+            .filter_map(|(path, outcome)| match outcome {
+                EntryOutcome::BackedUp { backup } => Some((path.as_str(), backup.as_str())),
+                _ => None,
+            })
+            .collect();
+        if !backups.is_empty() {
+            status!("💾 {}", t!("{} existing file(s) were backed up first:", backups.len()));
+            for (path, backup) in &backups {
+                status!("   {} → {}", path, backup);
+            }
+        }
+        let done = self.count(|o| {
+            !matches!(o, EntryOutcome::Skipped { .. } | EntryOutcome::Conflict { .. } | EntryOutcome::Failed { .. } | EntryOutcome::RolledBack)
+        });
+        if done < self.planned {
+            eprintln!("⚠️ {}", t!("Only {} of {} planned entries exist on disk:", done, self.planned));
+        }
+        // Everything after the limit is skipped, so it is summed up instead of listed
+        let started = self.entries.len() - self.timed_out;
+        for (path, outcome) in &self.entries[..started] {
+            match outcome {
+                EntryOutcome::Skipped { reason } => eprintln!("   ⏭️ {}", t!("{}: {}", path, reason)),
+                EntryOutcome::Conflict { reason } => eprintln!("   ⚔️ {}", t!("{}: {}", path, reason)),
+                EntryOutcome::Failed { reason, .. } => eprintln!("   ❌ {}", t!("{}: {}", path, reason)),
+                _ => {}
+            }
+        }
+        if let Some((first, EntryOutcome::Skipped { reason })) = self.entries.get(started) {
+            eprintln!("   ⏱️ {}", t!("{} entries from '{}' on: {}", self.timed_out, first, reason));
+        }
+    }
+}
+
+// `<1ms`, `850ms`, `2.41s`
+fn elapsed_text(elapsed: std::time::Duration) -> String {
+    match elapsed.as_millis() {
+        0 => "<1ms".to_string(),
+        ms if ms < 1000 => format!("{}ms", ms),
+        _ => format!("{:.2}s", elapsed.as_secs_f64()),
+    }
+}
+
+// Whether each entry is protected: `[protect]` on it or on a directory above
+// it, or a match of the config `protect` patterns
+fn protected_entries(plan: &[PlannedEntry], opts: &Options) -> Vec<bool> {
+    let protected_dirs: Vec<String> = plan
+        .iter()
+        .filter(|e| e.kind == NodeKind::Dir && e.has_annotation("protect"))
+        .map(|e| format!("{}/", e.path))
+        .collect();
+    plan.iter()
+        .map(|entry| {
+            let path = entry.path.as_str();
+            entry.has_annotation("protect")
+                || protected_dirs.iter().any(|dir| path.starts_with(dir.as_str()))
+                || opts.protect.is_match(path)
+                || Path::new(path).file_name().is_some_and(|name| opts.protect.is_match(name))
+        })
+        .collect()
+}
+
+// What happens to one entry, given what earlier entries left missing; true
+// when it was not started because `--timeout` had passed
+fn start_entry(
+    entry: &PlannedEntry,
+    base: &Path,
+    protected: bool,
+    opts: &Options,
+    missing_dirs: &[String],
+    made_links: &[String],
+    through_links: &mut Vec<(String, PathBuf)>,
+) -> (EntryOutcome, bool) {
+    let path = entry.path.as_str();
+    let expired = opts.deadline.as_ref().filter(|deadline| deadline.expired());
+    let outcome = match (expired, missing_dirs.iter().find(|dir| path.starts_with(&format!("{}/", dir)))) {
+        (Some(deadline), _) => {
+            let reason = t!("not started: --timeout {} had passed", timeout::shown(deadline.limit));
+            return (EntryOutcome::Skipped { reason }, true);
+        }
+        (None, Some(dir)) => EntryOutcome::Skipped { reason: t!("parent '{}' was not created", dir) },
+        (None, None) if made_links.iter().any(|link| path.starts_with(&format!("{}/", link))) => {
+            EntryOutcome::Skipped { reason: t!("inside a link; it lives at the link's target").to_string() }
+        }
+        (None, None) => timeout::guarded(|| create_entry(entry, base, protected, opts, through_links)).unwrap_or_else(|e| EntryOutcome::Failed {
+            kind: error_kind(e.as_ref()),
+            reason: t!("line {}: {}", entry.line + 1, e),
+            source: e.downcast::<io::Error>().ok().map(|e| std::sync::Arc::new(*e)),
+        }),
+    };
+    (outcome, false)
+}
+
+// The order entries are started in. One by one in plan order, or with
+// `--jobs` one wave per depth: everything in a wave only needs the folders
+// of the waves before it. `[after=...]` asks for an order, so it keeps the
+// plan's.
+fn waves(plan: &[PlannedEntry], opts: &Options) -> Vec<Vec<usize>> {
+    let ordered = plan.iter().any(|entry| entry.has_annotation("after"));
+    if opts.jobs > 1 && ordered && !opts.quiet {
+        status!("ℹ️ {}", t!("[after=...] sets an order, so --jobs {} creates the entries one by one", opts.jobs));
+    }
+    if opts.jobs < 2 || ordered {
+        return (0..plan.len()).map(|i| vec![i]).collect();
+    }
+    let mut waves: Vec<Vec<usize>> = Vec::new();
+    for (i, entry) in plan.iter().enumerate() {
+        if waves.len() <= entry.depth {
+            waves.resize_with(entry.depth + 1, Vec::new);
+        }
+        waves[entry.depth].push(i);
+    }
+    waves.retain(|wave| !wave.is_empty());
+    waves
+}
+
+// Create the plan below `base`; an empty `base` is the current directory
+fn create_structure(plan: &[PlannedEntry], base: &Path, opts: &Options) -> Summary {
+    let debug = opts.debug;
+    let mut through_links: Vec<(String, PathBuf)> = Vec::new();
+    // Attributes are applied once everything exists: creating children would
+    // bump a directory's mtime, and a read-only mode would block them
+    let mut pending_attrs: Vec<(&str, NodeAttrs)> = Vec::new();
+    let started_at = std::time::Instant::now();
+    let mut summary = Summary { planned: plan.len(), merge: opts.merge, ..Summary::default() };
+    // Directories (or followed links) that were not created; their contents are skipped
+    let mut missing_dirs: Vec<String> = Vec::new();
+    // Links made by this run; what a listing shows below them is the target's
+    let mut made_links: Vec<String> = Vec::new();
+    let protection = protected_entries(plan, opts);
+    let mut outcomes: Vec<Option<EntryOutcome>> = (0..plan.len()).map(|_| None).collect();
+    let started = std::sync::atomic::AtomicUsize::new(0);
+
+    for wave in waves(plan, opts) {
+        let start = |i: usize, through_links: &mut Vec<(String, PathBuf)>| {
+            if let Some(progress) = &opts.progress {
+                progress.entry(started.fetch_add(1, std::sync::atomic::Ordering::Relaxed), plan.len(), &plan[i].path);
+            }
+            start_entry(&plan[i], base, protection[i], opts, &missing_dirs, &made_links, through_links)
+        };
+        let results: Vec<(usize, (EntryOutcome, bool))> = if wave.len() < 2 {
+            wave.iter().map(|&i| (i, start(i, &mut through_links))).collect()
+        } else {
+            // Workers take the next entry of the wave until none is left
+            let next = std::sync::atomic::AtomicUsize::new(0);
+            let mut results: Vec<(usize, (EntryOutcome, bool))> = Vec::new();
+            std::thread::scope(|scope| {
+                let handles: Vec<_> = (0..opts.jobs.min(wave.len()))
+                    .map(|_| {
+                        scope.spawn(|| {
+                            let (mut done, mut links) = (Vec::new(), Vec::new());
+                            while let Some(&i) = wave.get(next.fetch_add(1, std::sync::atomic::Ordering::Relaxed)) {
+                                done.push((i, start(i, &mut links)));
+                            }
+                            (done, links)
+                        })
+                    })
+                    .collect();
+                for (done, links) in handles.into_iter().filter_map(|handle| handle.join().ok()) {
+                    results.extend(done);
+                    through_links.extend(links);
+                }
+            });
+            results.sort_by_key(|(i, _)| *i);
+            results
+        };
+
+        for (i, (outcome, timed_out)) in results {
+            summary.timed_out += usize::from(timed_out);
+            let entry = &plan[i];
+            let path = entry.path.as_str();
+            let written = matches!(outcome, EntryOutcome::Created | EntryOutcome::Replaced | EntryOutcome::BackedUp { .. });
+            if written {
+                let bytes = match entry.kind {
+                    NodeKind::File => fs::metadata(escape::long_path(&base.join(escape::os_path(path)))).map_or(0, |meta| meta.len()),
+                    _ => 0,
+                };
+                summary.breakdown.add(path, entry.kind, bytes);
+            }
+            let done = written || matches!(outcome, EntryOutcome::Existed);
+            let missing = match &outcome {
+                EntryOutcome::Skipped { .. } | EntryOutcome::Conflict { .. } => true,
+                EntryOutcome::Failed { .. } => matches!(entry.kind, NodeKind::Dir | NodeKind::Symlink),
+                _ => false,
+            };
+            if missing {
+                missing_dirs.push(path.to_string());
+            }
+            if entry.kind == NodeKind::Symlink && opts.symlinks && matches!(outcome, EntryOutcome::Created | EntryOutcome::Existed) {
+                made_links.push(path.to_string());
+            }
+            if let Some(progress) = &opts.progress {
+                progress.outcome(path, &outcome);
+            }
+            outcomes[i] = Some(outcome);
+            if !done {
+                continue;
+            }
+            // A mode annotation wins over a listed permission column; a link's
+            // mode would land on its target
+            let listed = entry.attrs.as_ref().filter(|_| opts.honor_attrs);
+            let mode = entry.mode().filter(|_| entry.kind != NodeKind::Symlink);
+            if listed.is_some() || mode.is_some() {
+                let mut attrs = listed.cloned().unwrap_or_default();
+                attrs.mode = mode.or(attrs.mode);
+                pending_attrs.push((path, attrs));
+            }
+            if debug {
+                let icon = opts.icons.icon(path, entry.kind, entry.executable);
+                match (entry.depth == 0, entry.kind == NodeKind::Dir) {
+                    (true, true) => status!("{} Root: {}", icon, path),
+                    (true, false) => status!("{} Root file: {}", icon, path),
+                    (false, true) => status!("{} {}", icon, path),
+                    (false, false) => status!("{} {} ({})", icon, path, entry.kind.label()),
+                }
+            }
+        }
+    }
+    summary.entries = plan.iter().zip(outcomes).filter_map(|(entry, outcome)| Some((entry.path.clone(), outcome?))).collect();
+    summary.elapsed = started_at.elapsed();
+
+    // Deepest paths first
+    for (path, attrs) in pending_attrs.iter().rev() {
+        for problem in attrs::apply_attrs(&escape::long_path(&base.join(escape::os_path(path))), attrs) {
+            eprintln!("⚠️ {}", t!("Could not apply {} to '{}'", problem, path));
+        }
+    }
+
+    if let Some(progress) = &opts.progress {
+        progress.finish(plan.len(), summary.created().len(), summary.failed(), &summary.breakdown);
+    }
+
+    if !through_links.is_empty() && !opts.quiet {
+        status!("🔗 {}", t!("{} path(s) were created through symlinks:", through_links.len()));
+        for (path, link) in &through_links {
+            status!("   {}", t!("{} (via {})", path, link.display()));
+        }
+    }
+
+    summary
+}
+
+// The input as it is, from the file (`-` for stdin, or an http(s) URL) or
+// else the clipboard. `selection` is None unless clipboard input was asked
+// for; `remote` limits a download.
+fn read_text(file_arg: Option<&str>, selection: Option<clip::Selection>, remote: fetch::Limits) -> Result<(String, &'static str), Box<dyn std::error::Error>> {
+    if file_arg == Some(STDIN) {
+        let mut content = String::new();
+        io::stdin().read_to_string(&mut content).map_err(|e| t!("could not read stdin: {}", e))?;
+        if content.trim().is_empty() {
+            return Err(t!("stdin is empty").into());
+        }
+        return Ok((content, "stdin"));
+    }
+    if let Some(url) = file_arg.filter(|file| fetch::is_url(file)) {
+        return Ok((fetch::tree(url, remote)?, "URL"));
+    }
+    if let Some(file_path) = file_arg {
+        return Ok((std::fs::read_to_string(file_path)?, "file"));
+    }
+
+    let Some(selection) = selection else {
+        return Err(t!("no input given: pass a tree file, or --clipboard (-c) to read the clipboard").into());
+    };
+
+    let (content, source) = clip::read(selection)?;
+
+    if content.trim().is_empty() {
+        return Err(t!("clipboard is empty").into());
+    }
+    Ok((content, source))
+}
+
+fn report_fence(block: &fence::Block) {
+    let end = block.start + block.lines.len();
+    status!("📑 {}", t!("Reading fenced block {} (lines {}-{})", block.number, block.start + 1, end));
+}
+
+// The tree's lines; from a Markdown document, those of the first fenced
+// block that looks like a tree, or of block `fence` (`--fence-index`)
+fn read_input(
+    file_arg: Option<&str>,
+    selection: Option<clip::Selection>,
+    fence: Option<usize>,
+    remote: fetch::Limits,
+) -> Result<(Vec<String>, &'static str), Box<dyn std::error::Error>> {
+    let (content, source) = read_text(file_arg, selection, remote)?;
+    Ok((tree_lines(&content, source, fence)?, source))
+}
+
+// The same for text already read from `source`
+fn tree_lines(content: &str, source: &str, fence: Option<usize>) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    if let Some(block) = fence::pick(content, fence, |block| looks_like_tree(&block.lines.join("\n")))? {
+        report_fence(&block);
+        return Ok(fence::in_place(&block));
+    }
+    // A file, pipe or URL was chosen on purpose; only clipboard text is sniffed
+    if source == "file" || source == "stdin" || source == "URL" {
+        return Ok(content.lines().map(|s| s.to_string()).collect());
+    }
+
+    if !looks_like_tree(content) {
+        if let Some((idx, len)) = overlong_line(content) {
+            return Err(t!("clipboard is not a tree-structure: line {} is {} bytes long (minified data or an encoded blob?)", idx + 1, len).into());
+        }
+        return Err(t!("clipboard is not a tree-structure").into());
+    }
+
+    Ok(content.lines().map(|s| s.to_string()).collect())
+}
+
+fn is_valid_structure(lines: &[String], popts: &ParseOptions) -> bool {
+    let outline = outline::to_indented(lines);
+    let lines = outline.as_deref().unwrap_or(lines);
+    lines.iter().any(|line| parse_tree_line(line, popts).is_ok())
+}
+
+// `--insecure` outside a run that creates entries, where the other limits
+// keep their defaults
+fn remote_limits(input: &args::InputArgs) -> fetch::Limits {
+    fetch::Limits { insecure: input.insecure, ..fetch::Limits::default() }
+}
+
+// The file name that stands for standard input
+const STDIN: &str = "-";
+
+// `-` or `--stdin` reads the tree from standard input
+fn input_file<'a>(file: Option<&'a str>, input: &args::InputArgs) -> Result<Option<&'a str>, Box<dyn std::error::Error>> {
+    match (file, input.stdin) {
+        (Some(file), true) if file != STDIN => Err(t!("--stdin reads the tree from standard input; the file '{}' cannot be given too", file).into()),
+        (_, true) => Ok(Some(STDIN)),
+        _ => Ok(file),
+    }
+}
+
+// Clipboard input is opt-in: `--clipboard`/`-c`, `--selection`, or
+// `clipboard = true` in the config
+fn clipboard_selection(input: &args::InputArgs, config: &config::Config) -> Result<Option<clip::Selection>, Box<dyn std::error::Error>> {
+    let selection = input.selection.as_deref();
+    let enabled = input.clipboard || selection.is_some() || config.clipboard == Some(true);
+    if !enabled {
+        return Ok(None);
+    }
+    Ok(Some(clip::parse_selection(selection)?))
+}
+
+fn parse_options(flags: &args::ParseArgs, icons: &icons::IconTheme) -> Result<ParseOptions, Box<dyn std::error::Error>> {
+    let assume = match flags.assume.as_deref() {
+        None => None,
+        Some("dir") => Some(NodeKind::Dir),
+        Some("file") => Some(NodeKind::File),
+        Some(other) => return Err(t!("--assume expects 'dir' or 'file', got '{}'", other).into()),
+    };
+    let multi_sep = match flags.multi_sep.as_deref() {
+        Some(sep) if sep.trim().is_empty() || sep.contains(['/', '\\']) => {
+            return Err(t!("--multi-sep expects a separator such as '&' or ',', got '{}'", sep).into())
+        }
+        sep => sep.map(String::from),
+    };
+    Ok(ParseOptions {
+        raw_names: flags.raw_names,
+        infer_hierarchy: flags.infer_hierarchy,
+        assume,
+        icons: icons.glyphs(),
+        promote_root: flags.promote_root,
+        auto_dir: flags.auto_dir,
+        keep_comments: flags.keep_comments,
+        strict: flags.strict,
+        multi_sep,
+        no_split: flags.no_split,
+        sanitize: flags.sanitize,
+    })
+}
+
+// Over-long lines are skipped rather than cut into an absurd name
+fn report_long_lines(resolved: &[ResolvedLine], lines: &[String]) {
+    for line in resolved.iter().filter(|line| matches!(line.placed, Err("line too long"))) {
+        let text = &lines[line.idx];
+        status!(
+            "⚠️ {}",
+            t!("line {}: skipped, {} bytes is over the {}-byte limit for a tree line ({})", line.idx + 1, text.len(), MAX_LINE_BYTES, preview(text))
+        );
+    }
+}
+
+// Prompts, pager lines and the like are dropped, but never silently
+fn report_noise(resolved: &[ResolvedLine], lines: &[String]) {
+    for line in resolved {
+        let Err(reason) = line.placed else { continue };
+        let Some(code) = noise::code(reason) else { continue };
+        status!(
+            "⚠️ {}",
+            t!("line {}: skipped {} [{}]: {}", line.idx + 1, i18n::tr(reason), code, preview(lines[line.idx].trim()))
+        );
+    }
+}
+
+// Names `--sanitize` changed, so nothing is renamed silently
+fn report_sanitized(resolved: &[ResolvedLine], popts: &ParseOptions) {
+    if !popts.sanitize {
+        return;
+    }
+    for line in resolved {
+        let Ok(placed) = &line.placed else { continue };
+        for (written, name) in split_names(&placed.node.name, popts.separator()).iter().zip(&placed.names) {
+            if written != name {
+                status!("✏️ {}", t!("line {}: '{}' renamed to '{}' (--sanitize)", line.idx + 1, written, name));
+            }
+        }
+    }
+}
+
+// List the nodes whose kind could not be read from the input
+fn report_guessed_kinds(resolved: &[ResolvedLine], popts: &ParseOptions) {
+    let guessed: Vec<(usize, &Placed)> = resolved
+        .iter()
+        .filter_map(|line| line.placed.as_ref().ok().map(|p| (line.idx, p)))
+        .filter(|(_, placed)| placed.kind_guessed)
+        .collect();
+    if guessed.is_empty() {
+        return;
+    }
+
+    let kind = i18n::tr(popts.assume.unwrap_or(NodeKind::File).label());
+    status!("❔ {}", t!("{} node(s) have no '/', extension or children; created as {}:", guessed.len(), kind));
+    for (idx, placed) in guessed {
+        status!("   {}", t!("line {}: {}", idx + 1, placed.node.name));
+    }
+    if popts.assume.is_none() {
+        status!("   {}", t!("Use --assume dir or --assume file to choose explicitly."));
+    }
+}
+
+// Paths listed more than once. A folder listed twice is merged; a file
+// listed twice is written twice and the later line wins.
+fn report_duplicates(resolved: &Resolved, popts: &ParseOptions) -> Result<(), Box<dyn std::error::Error>> {
+    let tree = &resolved.tree;
+    let mut first: BTreeMap<String, tree::NodeId> = BTreeMap::new();
+    let mut repeats: Vec<String> = Vec::new();
+    for &id in resolved.lines.iter().filter_map(|line| line.placed.as_ref().ok()).flat_map(|placed| &placed.ids) {
+        let path = tree.path(id);
+        let Some(&earlier) = first.get(&path) else {
+            first.insert(path, id);
+            continue;
+        };
+        let (node, earlier) = (tree.node(id), tree.node(earlier));
+        repeats.push(match node.kind == NodeKind::Dir && earlier.kind == NodeKind::Dir {
+            true => t!("line {}: folder '{}' is already listed at line {}; the two are merged", node.line + 1, path, earlier.line + 1),
+            false => t!("line {}: '{}' is already listed at line {}; the later line wins", node.line + 1, path, earlier.line + 1),
+        });
+    }
+    if repeats.is_empty() {
+        return Ok(());
+    }
+    let icon = if popts.strict { "❌" } else { "⚠️" };
+    status!("{} {}", icon, t!("{} path(s) are listed more than once:", repeats.len()));
+    for repeat in &repeats {
+        status!("   {}", repeat);
+    }
+    if popts.strict {
+        return Err(t!("{} duplicate path(s) in the tree (--strict)", repeats.len()).into());
+    }
+    status!("   {}", t!("Pass --strict to stop on duplicate paths."));
+    Ok(())
+}
+
+// The `mks` command line; src/main.rs only calls this
+#[doc(hidden)]
+pub fn cli() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = match args::parse() {
+        Ok(cli) => cli,
+        // `--help` is not a failure; exit code 2 is taken by `--dry-run`
+        Err(e) => {
+            let _ = e.print();
+            std::process::exit(if e.use_stderr() { 1 } else { 0 });
+        }
+    };
+    i18n::init(cli.global.lang.as_deref());
+    if cli.version {
+        println!("{}", colorful_version!());
+        return Ok(());
+    }
+    let command = cli.command.unwrap_or(args::Command::Create(cli.create));
+
+    // `mks config check|show` must work while the config is broken
+    if let args::Command::Config { action } = &command {
+        return match action {
+            args::ConfigAction::Check => config::check(),
+            args::ConfigAction::Show { effective } => config::show(*effective),
+        };
+    }
+    let config = match config::load() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            eprintln!("💡 {}", t!("`mks config check` lists every problem"));
+            std::process::exit(1);
+        }
+    };
+    let icons = icons::IconTheme::from_config(&config.icons, cli.global.theme.as_deref())?;
+
+    match command {
+        args::Command::Create(create) => run_create(create, None, &config, icons),
+        // `mks plan [file]` prints the plan as JSON instead of creating it;
+        // `mks apply plan.json` creates a plan read from such a file
+        args::Command::Plan(create) => run_create(create, Some("plan"), &config, icons),
+        args::Command::Apply(create) => run_create(create, Some("apply"), &config, icons),
+        // Reverse mode: `mks dump [dir]` prints an existing directory as a tree
+        args::Command::Dump(cmd) => {
+            let opts = dump::DumpOptions {
+                icons: cmd.icons.then_some(&icons),
+                size: if cmd.si {
+                    Some(dump::SizeStyle::Si)
+                } else if cmd.human {
+                    Some(dump::SizeStyle::Binary)
+                } else {
+                    cmd.size.then_some(dump::SizeStyle::Bytes)
+                },
+                date: if cmd.iso {
+                    Some(dump::DateStyle::Iso)
+                } else {
+                    cmd.date.then_some(dump::DateStyle::Tree)
+                },
+                follow_links: cmd.follow_junctions,
+            };
+            dump::run(cmd.dir.as_deref(), &opts)
+        }
+        args::Command::Doctor => doctor::run(),
+        args::Command::Schema { name } => {
+            println!("{}", plan_file::schema(name.as_deref())?);
+            Ok(())
+        }
+        args::Command::Check(cmd) => {
+            let popts = parse_options(&cmd.parse, &icons)?;
+            if let Some(name) = cmd.template.as_deref() {
+                return check::run_template(name, &vars::from_args(cmd.vars.vars.as_deref(), &cmd.vars.var)?, &popts);
+            }
+            let file = input_file(cmd.file.as_deref(), &cmd.input)?;
+            let fence = fence::index(cmd.input.fence_index.as_deref())?;
+            let selection = clipboard_selection(&cmd.input, &config)?;
+            let format = structured::format_for(None, file)?;
+            if format != structured::Format::Text {
+                // JSON and YAML are checked as they are loaded
+                let plan = structured::load(&read_text(file, selection, remote_limits(&cmd.input))?.0, format)?;
+                println!("✅ {}", t!("No problems found ({} entries)", plan.len()));
+                return Ok(());
+            }
+            let (lines, source) = read_input(file, selection, fence, remote_limits(&cmd.input))?;
+            check::run_tree(&lines, file.filter(|_| source == "file" || source == "URL").unwrap_or(i18n::tr(source)), &popts)
+        }
+        args::Command::Undo { dir, dry_run } => manifest::undo(&dir, dry_run),
+        args::Command::Stats(cmd) => {
+            let popts = parse_options(&cmd.parse, &icons)?;
+            let selection = clipboard_selection(&cmd.input, &config)?;
+            let (lines, _) = read_input(
+                input_file(cmd.file.as_deref(), &cmd.input)?,
+                selection,
+                fence::index(cmd.input.fence_index.as_deref())?,
+                remote_limits(&cmd.input),
+            )?;
+            stats::run(&lines, &cmd.dir, &popts)
+        }
+        args::Command::PruneEmpty(cmd) => {
+            let popts = parse_options(&cmd.parse, &icons)?;
+            let lines = match cmd.file.as_deref() {
+                Some(file) => Some(read_input(Some(file), None, fence::index(cmd.fence_index.as_deref())?, fetch::Limits::default())?.0),
+                None => None,
+            };
+            prune::run(&cmd.dir, lines.as_deref(), &popts, cmd.apply)
+        }
+        args::Command::Clean { dir } => state::clean(&dir),
+        args::Command::Explain(cmd) => {
+            let popts = parse_options(&cmd.parse, &icons)?;
+            let selection = clipboard_selection(&cmd.input, &config)?;
+            let (lines, _) = read_input(
+                input_file(cmd.file.as_deref(), &cmd.input)?,
+                selection,
+                fence::index(cmd.input.fence_index.as_deref())?,
+                remote_limits(&cmd.input),
+            )?;
+            explain::run(&lines, &popts)
+        }
+        args::Command::Demo(cmd) => {
+            let opts = create_options(&cmd.options, &config, icons)?;
+            demo::run(cmd.name.as_deref(), cmd.apply, &opts)
+        }
+        args::Command::Watch(cmd) => {
+            let opts = create_options(&cmd.options, &config, icons)?;
+            let target = dest_flag(&cmd.options.dest)?.unwrap_or(".");
+            check_dest(target)?;
+            let exporter = metrics::Exporter::from_args(&cmd.metrics, 0)?;
+            watch::run(&cmd.tree, target, &opts, exporter)
+        }
+        args::Command::WatchDir(cmd) => {
+            let opts = create_options(&cmd.options, &config, icons)?;
+            let target = match cmd.dir.as_deref() {
+                Some(dir) => dir,
+                None => dest_flag(&cmd.options.dest)?.unwrap_or("."),
+            };
+            check_dest(target)?;
+            let (mut plan, _) = plan_from_input(Some(&cmd.tree), &opts)?;
+            vars::apply(&mut plan, &opts.vars)?;
+            let exporter = metrics::Exporter::from_args(&cmd.metrics, plan.len())?;
+            watch_dir::run(plan, target, &opts, exporter)
+        }
+        args::Command::Clipboard(cmd) => {
+            let args::ClipboardArgs { watch, yes, interval, metrics, mut options } = *cmd;
+            if !watch && (metrics.metrics_addr.is_some() || metrics.metrics_file.is_some()) {
+                return Err(t!("--metrics-addr and --metrics-file need --watch").into());
+            }
+            if !watch {
+                options.input.clipboard = true;
+                return run_create(args::CreateArgs { files: Vec::new(), options }, None, &config, icons);
+            }
+            let interval = match interval.as_deref() {
+                None => clipboard_watch::DEFAULT_INTERVAL,
+                Some(value) => timeout::parse(value).ok_or_else(|| t!("--interval expects a duration such as 1s or 500ms, got '{}'", value))?,
+            };
+            let opts = create_options(&options, &config, icons)?;
+            let target = dest_flag(&options.dest)?.unwrap_or(".");
+            check_dest(target)?;
+            let exporter = metrics::Exporter::from_args(&metrics, 0)?;
+            clipboard_watch::run(target, yes, interval, &opts, exporter)
+        }
+        args::Command::Template { action } => match action {
+            args::TemplateAction::Save(cmd) => {
+                let popts = parse_options(&cmd.parse, &icons)?;
+                let selection = clipboard_selection(&cmd.input, &config)?;
+                let file = input_file(cmd.file.as_deref(), &cmd.input)?;
+                let (lines, _) = read_input(file, selection, fence::index(cmd.input.fence_index.as_deref())?, remote_limits(&cmd.input))?;
+                templates::save(&cmd.name, &lines.join("\n"), cmd.force, &popts)
+            }
+            args::TemplateAction::Apply(cmd) => {
+                let args::TemplateApplyArgs { name, mut options } = *cmd;
+                if options.preset.is_some() {
+                    return Err(t!("`mks template apply` names the template itself; drop --preset").into());
+                }
+                options.preset = Some(name);
+                run_create(args::CreateArgs { files: Vec::new(), options }, None, &config, icons)
+            }
+            args::TemplateAction::List => templates::list(),
+            args::TemplateAction::Delete { name } => templates::delete(&name),
+        },
+        args::Command::Config { .. } => unreachable!(),
+    }
+}
+
+// The options of a run that creates entries, from its flags and the config
+fn create_options(flags: &args::CreateOptions, config: &config::Config, icons: icons::IconTheme) -> Result<Options, Box<dyn std::error::Error>> {
+    #[cfg(not(feature = "test-utils"))]
+    simulate_failure_set(&flags.simulate_failure)?;
+    let mut parse = parse_options(&flags.parse, &icons)?;
+    parse.keep_comments |= flags.write_descriptions.is_some() || flags.description_headers;
+    Ok(Options {
+        parse,
+        debug: flags.debug,
+        durable: flags.durable,
+        shebang: flags.shebang,
+        honor_attrs: flags.honor_attrs,
+        order: if flags.breadth_first {
+            Order::BreadthFirst
+        } else {
+            Order::DepthFirst
+        },
+        follow_symlinks: flags.follow || !flags.no_follow,
+        symlinks: !flags.no_symlinks,
+        selection: clipboard_selection(&flags.input, config)?,
+        icons,
+        protect: config.protect_set()?,
+        progress: flags.progress_fd.as_deref().map(progress::Progress::open).transpose()?,
+        editorconfig: !flags.no_editorconfig,
+        posix_paths: flags.posix_paths,
+        rollback_on_error: flags.rollback_on_error,
+        on_conflict: merge_policy(flags.merge, flags.on_conflict.as_deref())?,
+        merge: flags.merge,
+        respect_gitignore: flags.respect_gitignore,
+        placeholders: flags.placeholders.then(|| placeholder::Registry::with_samples(&config.placeholders)).transpose()?,
+        format: flags.format.clone(),
+        fence: fence::index(flags.input.fence_index.as_deref())?,
+        download: fetch::limits(flags.max_download.as_deref(), flags.download_timeout.as_deref(), flags.input.insecure)?,
+        vars: vars::from_args(flags.vars.vars.as_deref(), &flags.vars.var)?,
+        interactive: flags.interactive.then(interactive::Conflicts::default),
+        deadline: timeout::from_args(flags.timeout.as_deref(), config)?,
+        jobs: jobs_flag(flags.jobs.as_deref(), flags.interactive)?,
+        quiet: false,
+        #[cfg(feature = "test-utils")]
+        simulate_failure: simulate_failure_set(&flags.simulate_failure)?,
+    })
+}
+
+// `--jobs N`; answers to `-i` prompts cannot come from several threads
+fn jobs_flag(value: Option<&str>, interactive: bool) -> Result<usize, Box<dyn std::error::Error>> {
+    let Some(value) = value else {
+        return Ok(1);
+    };
+    let jobs = value.parse::<usize>().ok().filter(|&jobs| jobs > 0).ok_or_else(|| t!("--jobs expects a number of threads, got '{}'", value))?;
+    if jobs > 1 && interactive {
+        return Err(t!("--jobs cannot be combined with -i, which asks one question at a time").into());
+    }
+    Ok(jobs)
+}
+
+// `mks [create]`, `mks plan` and `mks apply`: build the plan, then print it,
+// create it, or hand it to one of the other targets
+fn run_create(create: args::CreateArgs, mode: Option<&str>, config: &config::Config, icons: icons::IconTheme) -> Result<(), Box<dyn std::error::Error>> {
+    let flags = &create.options;
+    let positional: Vec<&str> = create.files.iter().map(String::as_str).collect();
+    let emit_shell = script::requested_shell(flags.emit_script.as_deref())?;
+    let json = run_report::wants_json(flags.output_format.as_deref())?;
+    if json && (emit_shell.is_some() || mode == Some("plan") || flags.dry_run || flags.report_html.is_some()) {
+        return Err(t!("--output-format=json reports a run that creates entries; it cannot be combined with --dry-run, --report-html, --emit-script or mks plan").into());
+    }
+    let to_archive = archive::requested(flags.to_zip.as_deref(), flags.to_tar.as_deref())?;
+    let archive_to_stdout = to_archive.is_some_and(|(file, _)| file == "-");
+    if json && archive_to_stdout {
+        return Err(t!("--output-format=json and an archive written to stdout cannot share it").into());
+    }
+    STDOUT_IS_DATA.store(json || archive_to_stdout || emit_shell.is_some() || mode == Some("plan"), std::sync::atomic::Ordering::Relaxed);
+    let started = std::time::Instant::now();
+
+    let debug = flags.debug;
+    let opts = create_options(flags, config, icons)?;
+
+    if debug {
+        println!("🪲 {}\n", t!("Debug mode enabled"));
+    }
+
+    let (mut plan, source) = match flags.preset.as_deref() {
+        Some(name) => {
+            let plan = presets::load_plan(name, &opts.parse, debug)?;
+            status!("📋 {}", t!("Loaded preset '{}' ({} entries)", name, plan.len()));
+            (plan, "preset")
+        }
+        None if mode == Some("apply") => {
+            let Some(file) = positional.first().copied() else {
+                return Err(t!("usage: mks apply <plan.json>").into());
+            };
+            let plan = plan_file::load(file)?;
+            status!("📋 {}", t!("Loaded plan '{}' ({} entries)", file, plan.len()));
+            (plan, "plan")
+        }
+        None => match flags.under.as_deref() {
+            Some(parent) => (plan_under(&positional, parent, &opts)?, "file"),
+            None if positional.len() > 1 => {
+                return Err(t!("several tree files given; combine them with --under <dir>").into());
+            }
+            None => plan_from_input(input_file(positional.first().copied(), &flags.input)?, &opts)?,
+        },
+    };
+
+    vars::apply(&mut plan, &opts.vars)?;
+    // `[remove]` only has a meaning when overlaying presets
+    plan.retain(|entry| !entry.has_annotation("remove"));
+    order_plan(&mut plan, opts.order)?;
+    let tags = comma_values(&flags.tags);
+    let skip_tags = comma_values(&flags.skip_tags);
+    if !tags.is_empty() || !skip_tags.is_empty() {
+        plan = select_tags(plan, &tags, &skip_tags);
+    }
+    if !flags.only.is_empty() {
+        let only: Vec<&str> = flags.only.iter().map(String::as_str).collect();
+        plan = select_subtrees(plan, &only)?;
+    }
+    if !flags.include.is_empty() || !flags.exclude.is_empty() {
+        plan = select_globs(plan, &flags.include, &flags.exclude)?;
+    }
+    if let Some(spec) = flags.shard.as_deref() {
+        plan = select_shard(plan, spec)?;
+    }
+    if let Some(file) = flags.write_descriptions.as_deref() {
+        let rows = descriptions::write(&plan, file)?;
+        status!("📝 {}", t!("Wrote {} description(s) to {}", rows, file));
+    }
+    if flags.description_headers {
+        let headers = descriptions::add_headers(&mut plan, opts.shebang);
+        status!("📝 {}", t!("{} file(s) start with their description as a comment", headers));
+    }
+    if mode == Some("plan") {
+        println!("{}", plan_file::to_json(&plan)?);
+        return Ok(());
+    }
+
+    let targets = batch_targets(&flags.into, &flags.into_glob)?;
+    let mut output = dest_flag(&flags.dest)?;
+
+    // A clipboard tree has no obvious home, so ask where it goes
+    let picked;
+    let dry_run = flags.dry_run;
+    let into_archive = flags.into_archive.as_deref();
+    if source != "file"
+        && source != "preset"
+        && source != "plan"
+        && output.is_none()
+        && targets.is_empty()
+        && emit_shell.is_none()
+        && !dry_run
+        && flags.report_html.is_none()
+        && into_archive.is_none()
+        && to_archive.is_none()
+    {
+        picked = picker::pick_base(project::single_root(&plan).as_deref())?;
+        output = picked.as_deref();
+    }
+
+    // Print the equivalent script instead of touching the filesystem
+    if let Some(shell) = emit_shell {
+        if !targets.is_empty() {
+            return Err(t!("--emit-script cannot be combined with --into").into());
+        }
+        if let Some(project) = project::inject(&mut plan, output, opts.vars.get("project").map(String::as_str))? {
+            status!("🏷️ {}", t!("Project name: {}", project));
+        }
+        if opts.shebang {
+            for entry in plan.iter_mut().filter(|e| e.executable && e.contents.is_none()) {
+                entry.contents = shebang_for(&entry.path).map(String::from);
+            }
+        }
+        let protection = protected_entries(&plan, &opts);
+        print!("{}", script::emit(&plan, &protection, output, shell));
+        return Ok(());
+    }
+
+    let git_message = flags.git_init.then(|| flags.commit_message.clone().or_else(|| config.commit_message.clone()).unwrap_or_else(|| git::DEFAULT_MESSAGE.to_string()));
+    if git_message.is_some() && (!targets.is_empty() || into_archive.is_some() || to_archive.is_some() || emit_shell.is_some()) {
+        return Err(t!("--git-init works on one target folder; it cannot be combined with --into, --into-archive, --to-zip, --to-tar or --emit-script").into());
+    }
+
+    if let Some((file, format)) = to_archive {
+        if output.is_some() || !targets.is_empty() || into_archive.is_some() || emit_shell.is_some() {
+            return Err(t!("--to-zip and --to-tar cannot be combined with -C, --into, --into-archive or --emit-script").into());
+        }
+        // The archive's name stands in for the target folder's
+        let stem = archive::stem(file);
+        if let Some(project) = project::inject(&mut plan, stem.as_deref(), opts.vars.get("project").map(String::as_str))? {
+            status!("🏷️ {}", t!("Project name: {}", project));
+        }
+        let summary = archive::create(&plan, file, format, &opts, !dry_run)?;
+        summary.print();
+        if json {
+            run_report::print(vec![run_report::RunReport::new(file, &plan, &summary)], started.elapsed())?;
+        }
+        if summary.failed() > 0 {
+            std::process::exit(1);
+        }
+        if !dry_run && file != "-" {
+            status!("📦 {}", t!("Wrote {}", file));
+        }
+        return Ok(());
+    }
+
+    if let Some(zip_path) = into_archive {
+        if output.is_some() || !targets.is_empty() {
+            return Err(t!("--into-archive cannot be combined with -C or --into").into());
+        }
+        // The archive's name stands in for the target folder's
+        let stem = Path::new(zip_path).file_stem().map(|s| s.to_string_lossy().into_owned());
+        if let Some(project) = project::inject(&mut plan, stem.as_deref(), opts.vars.get("project").map(String::as_str))? {
+            status!("🏷️ {}", t!("Project name: {}", project));
+        }
+        let summary = archive::merge(&plan, zip_path, &opts, !dry_run)?;
+        summary.print();
+        if json {
+            run_report::print(vec![run_report::RunReport::new(zip_path, &plan, &summary)], started.elapsed())?;
+        }
+        if summary.failed() > 0 {
+            std::process::exit(1);
+        }
+        if !dry_run {
+            status!("📦 {}", t!("Updated {}", zip_path));
+        }
+        if summary.conflicts() > 0 {
+            std::process::exit(3);
+        }
+        return Ok(());
+    }
+
+    if dry_run || flags.report_html.is_some() {
+        if let Some(project) = project::inject(&mut plan, output, opts.vars.get("project").map(String::as_str))? {
+            status!("🏷️ {}", t!("Project name: {}", project));
+        }
+        if let Some(dir) = output {
+            status!("📂 {}", t!("Target directory: {}", dir));
+        }
+        if let Some(dir) = output {
+            check_dest(dir)?;
+        }
+        let base = if targets.is_empty() { Some(std::path::absolute(output.unwrap_or("."))?) } else { None };
+        if let Some(file) = flags.report_html.as_deref() {
+            html_report::write(&plan, base.as_deref(), &targets, file, opts.posix_paths)?;
+            status!("🌐 {}", t!("Wrote the plan to {}; nothing was created", file));
+        }
+        if !dry_run {
+            return Ok(());
+        }
+        // 0: nothing to do, 2: only additions, 3: conflicts, for CI to gate on
+        let verdict = dry_run::print(&plan, &opts.icons, base.as_deref(), &targets, opts.posix_paths);
+        if verdict != dry_run::Verdict::Matches {
+            std::process::exit(verdict.exit_code());
+        }
+        return Ok(());
+    }
+
+    if opts.interactive.is_some() {
+        interactive::check_terminal()?;
+        if let Some(project) = project::inject(&mut plan, output, opts.vars.get("project").map(String::as_str))? {
+            status!("🏷️ {}", t!("Project name: {}", project));
+        }
+        let base = if targets.is_empty() { Some(std::path::absolute(output.unwrap_or("."))?) } else { None };
+        if !interactive::confirm_plan(&plan, &opts.icons, base.as_deref(), &targets, opts.posix_paths) {
+            status!("🚫 {}", t!("Cancelled: nothing was created"));
+            return Ok(());
+        }
+    }
+
+    if !targets.is_empty() {
+        return run_batch(&plan, &targets, &opts, json.then_some(started));
+    }
+
+    let target = output.unwrap_or(".");
+    let summary = match apply_plan(plan.clone(), output, &opts) {
+        Ok(summary) => summary,
+        Err(e) if json => {
+            run_report::print(vec![run_report::RunReport::failed(target, plan.len(), Some(e.to_string()))], started.elapsed())?;
+            return Err(e);
+        }
+        Err(e) => return Err(e),
+    };
+    if json {
+        run_report::print(vec![run_report::RunReport::new(target, &plan, &summary)], started.elapsed())?;
+    }
+    if summary.timed_out > 0 {
+        std::process::exit(timeout::EXIT_CODE);
+    }
+    if summary.failed() > 0 {
+        std::process::exit(1);
+    }
+    // Like `--dry-run`, 3 tells a script that something was in the way
+    if summary.conflicts() > 0 {
+        std::process::exit(3);
+    }
+    if let Some(message) = git_message.as_deref() {
+        let paths: Vec<&str> = summary.entries.iter().map(|(path, _)| path.as_str()).collect();
+        git::init(Path::new(target), &paths, message)?;
+    }
+
+    status!("✅ {}", t!("Done!"));
+    Ok(())
+}
+
+// `-C/--dest <dir>`, also spelled `-o/--output`. Giving two different
+// directories is an error rather than a guess.
+fn dest_flag(values: &[String]) -> Result<Option<&str>, Box<dyn std::error::Error>> {
+    let mut dest: Option<&str> = None;
+    for value in values {
+        match dest {
+            Some(first) if first != value => {
+                return Err(t!("two target directories given: '{}' and '{}'", first, value).into());
+            }
+            _ => dest = Some(value),
+        }
+    }
+    Ok(dest)
+}
+
+// The target directory may be missing, but nothing on the way to it may be a file
+fn check_dest(dir: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let absolute = std::path::absolute(dir)?;
+    for ancestor in absolute.ancestors() {
+        match fs::metadata(ancestor) {
+            Ok(meta) if meta.is_dir() => return Ok(()),
+            Ok(_) => return Err(t!("cannot create in '{}': '{}' is a file", dir, ancestor.display()).into()),
+            Err(_) => {}
+        }
+    }
+    Ok(())
+}
+
+// Create the plan inside `output` (or the current directory) and report it
+fn apply_plan(mut plan: Vec<PlannedEntry>, output: Option<&str>, opts: &Options) -> Result<Summary, Box<dyn std::error::Error>> {
+    if let Some(dir) = output {
+        check_dest(dir)?;
+    }
+    if let Some(project) = project::inject(&mut plan, output, opts.vars.get("project").map(String::as_str))? {
+        status!("🏷️ {}", t!("Project name: {}", project));
+    }
+    drop_ignored(&mut plan, Path::new(output.unwrap_or("")), opts)?;
+    if opts.on_conflict == OnConflict::Error {
+        let existing = existing_files(&plan, Path::new(output.unwrap_or("")), opts);
+        if !existing.is_empty() {
+            return Err(t!("{} file(s) already exist (--on-conflict=error): {}", existing.len(), existing.join(", ")).into());
+        }
+    }
+    let start_dir = env::current_dir()?;
+    // A target made by this run goes again if a rollback leaves it empty
+    let fresh_dest = output.filter(|dir| !Path::new(dir).exists());
+    if let Some(dir) = output {
+        make_dir(Path::new(dir), opts.durable)?;
+        env::set_current_dir(dir)?;
+    }
+
+    status!("✅ {}\n", t!("Creating structure..."));
+
+    let mut summary = create_structure(&plan, Path::new(""), opts);
+    status!();
+    summary.print();
+    if summary.failed() > 0 {
+        rollback::after_failure(&mut summary, opts.rollback_on_error);
+    }
+    if let Err(e) = state::record_run(&plan, &summary, opts.durable) {
+        eprintln!("⚠️ {}", t!("Could not update {}: {}", state::DIR, e));
+    }
+    env::set_current_dir(start_dir)?;
+    if let Some(dir) = fresh_dest {
+        let _ = fs::remove_dir(dir);
+    }
+    Ok(summary)
+}
+
+// `mks a.txt b.txt --under monorepo/`: every tree goes below one new parent
+// directory. A tree keeps its single root folder; loose entries are wrapped in
+// a folder named after the file. Two trees may not claim the same folder.
+fn plan_under(files: &[&str], parent: &str, opts: &Options) -> Result<Vec<PlannedEntry>, Box<dyn std::error::Error>> {
+    if files.is_empty() {
+        return Err(t!("--under needs at least one tree file").into());
+    }
+    let parent = parent.trim_end_matches(['/', '\\']).to_string();
+    let mut plan = vec![PlannedEntry {
+        line: 0,
+        path: parent.clone(),
+        depth: 0,
+        kind: NodeKind::Dir,
+        executable: false,
+        attrs: None,
+        annotations: Vec::new(),
+        contents: None,
+    }];
+    let mut claimed: BTreeMap<String, &str> = BTreeMap::new();
+
+    for file in files {
+        let (mut entries, _) = plan_from_input(Some(file), opts)?;
+        let root = match project::single_root(&entries) {
+            Some(root) => root,
+            None => {
+                let stem = Path::new(file)
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .unwrap_or_else(|| file.to_string());
+                for entry in &mut entries {
+                    entry.path = format!("{}/{}", stem, entry.path);
+                    entry.depth += 1;
+                }
+                entries.insert(0, PlannedEntry { path: stem.clone(), ..plan[0].clone() });
+                stem
+            }
+        };
+        if let Some(other) = claimed.insert(root.clone(), file) {
+            return Err(t!("'{}' and '{}' both create '{}/{}'", other, file, parent, root).into());
+        }
+        for mut entry in entries {
+            entry.path = format!("{}/{}", parent, entry.path);
+            entry.depth += 1;
+            plan.push(entry);
+        }
+    }
+    Ok(plan)
+}
+
+// `--tags ci,docs` (repeatable) as one list
+fn comma_values(values: &[String]) -> Vec<&str> {
+    values
+        .iter()
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .collect()
+}
+
+// Tags apply to the tagged entry and everything below it. Untagged entries are
+// always kept; with `--tags`, tagged ones only when one of their tags is
+// selected. `--skip-tags` drops anything carrying one of the given tags.
+fn select_tags(plan: Vec<PlannedEntry>, wanted: &[&str], skipped: &[&str]) -> Vec<PlannedEntry> {
+    let tagged: Vec<(String, Vec<String>)> = plan
+        .iter()
+        .filter(|e| e.tags().next().is_some())
+        .map(|e| (e.path.clone(), e.tags().map(String::from).collect()))
+        .collect();
+    plan.into_iter()
+        .filter(|entry| {
+            let tags: Vec<&str> = tagged
+                .iter()
+                .filter(|(path, _)| entry.path == *path || entry.path.starts_with(&format!("{}/", path)))
+                .flat_map(|(_, tags)| tags.iter().map(String::as_str))
+                .collect();
+            let selected = wanted.is_empty() || tags.is_empty() || tags.iter().any(|t| wanted.contains(t));
+            selected && !tags.iter().any(|t| skipped.contains(t))
+        })
+        .collect()
+}
+
+// `--shard i/n`: every shard creates all folders, so each one can run on its
+// own, and a contiguous 1/n of everything else in plan order. The split only
+// depends on the plan, so jobs given the same tree never overlap.
+fn select_shard(plan: Vec<PlannedEntry>, spec: &str) -> Result<Vec<PlannedEntry>, Box<dyn std::error::Error>> {
+    let parsed = spec
+        .split_once('/')
+        .and_then(|(i, n)| Some((i.trim().parse::<usize>().ok()?, n.trim().parse::<usize>().ok()?)));
+    let (index, count) = match parsed {
+        Some((i, n)) if i >= 1 && i <= n => (i - 1, n),
+        _ => return Err(t!("--shard expects i/n with 1 <= i <= n, got '{}'", spec).into()),
+    };
+    let nodes = plan.iter().filter(|e| e.kind != NodeKind::Dir).count();
+    let (start, end) = (index * nodes / count, (index + 1) * nodes / count);
+    let dirs = plan.len() - nodes;
+    status!("🧩 {}", t!("Shard {}/{}: {} of {} entries, plus all {} folder(s)", index + 1, count, end - start, nodes, dirs));
+
+    let mut seen = 0;
+    Ok(plan
+        .into_iter()
+        .filter(|entry| {
+            if entry.kind == NodeKind::Dir {
+                return true;
+            }
+            seen += 1;
+            (start..end).contains(&(seen - 1))
+        })
+        .collect())
+}
+
+// `--only src/logic/` (repeatable): keep the subtrees rooted at the given
+// paths plus the folders above them. A path may also leave out the tree's
+// single root folder.
+fn select_subtrees(plan: Vec<PlannedEntry>, only: &[&str]) -> Result<Vec<PlannedEntry>, Box<dyn std::error::Error>> {
+    let root = project::single_root(&plan);
+    let mut selected = Vec::new();
+    for wanted in only {
+        let wanted = wanted.trim_end_matches(['/', '\\']).replace('\\', "/");
+        let full = match &root {
+            Some(root) if !plan.iter().any(|e| e.path == wanted) => format!("{}/{}", root, wanted),
+            _ => wanted.clone(),
+        };
+        if !plan.iter().any(|e| e.path == full) {
+            return Err(t!("--only '{}' matches nothing in the tree", wanted).into());
+        }
+        selected.push(full);
+    }
+
+    let inside = |path: &str| {
+        selected.iter().any(|sel| {
+            path == sel
+                || path.starts_with(&format!("{}/", sel))
+                || sel.starts_with(&format!("{}/", path))
+        })
+    };
+    Ok(plan.into_iter().filter(|e| inside(&e.path)).collect())
+}
+
+// One `--include`/`--exclude` pattern. Without a '/' it matches a name at
+// any depth, as in .gitignore; with one, the whole path, which may leave out
+// the tree's single root folder. A trailing '/' only matches folders.
+struct PathGlob {
+    pattern: String,
+    matcher: globset::GlobMatcher,
+    anywhere: bool,
+    dirs_only: bool,
+}
+
+impl PathGlob {
+    fn new(pattern: &str, flag: &str) -> Result<PathGlob, Box<dyn std::error::Error>> {
+        let trimmed = pattern.trim_start_matches("./").trim_end_matches('/');
+        let glob = globset::GlobBuilder::new(trimmed)
+            .literal_separator(true)
+            .build()
+            .map_err(|e| t!("{} pattern '{}': {}", flag, pattern, e))?;
+        Ok(PathGlob {
+            pattern: pattern.to_string(),
+            matcher: glob.compile_matcher(),
+            anywhere: !trimmed.contains('/'),
+            dirs_only: pattern.ends_with('/'),
+        })
+    }
+
+    fn matches(&self, path: &str, is_dir: bool, root: Option<&str>) -> bool {
+        if self.dirs_only && !is_dir {
+            return false;
+        }
+        if self.anywhere {
+            return self.matcher.is_match(path.rsplit('/').next().unwrap_or(path));
+        }
+        let below_root = root.and_then(|root| path.strip_prefix(root)).and_then(|rest| rest.strip_prefix('/'));
+        self.matcher.is_match(path) || below_root.is_some_and(|rest| self.matcher.is_match(rest))
+    }
+
+    // The entry itself or one of the folders above it
+    fn covers(&self, entry: &PlannedEntry, root: Option<&str>) -> bool {
+        let mut path = entry.path.as_str();
+        if self.matches(path, entry.kind == NodeKind::Dir, root) {
+            return true;
+        }
+        while let Some((parent, _)) = path.rsplit_once('/') {
+            path = parent;
+            if self.matches(path, true, root) {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+// `--include`/`--exclude <glob>` (repeatable), on the resolved paths: with
+// includes, keep what they cover plus the folders above it; then drop what
+// an exclude covers, along with everything below it
+fn select_globs(mut plan: Vec<PlannedEntry>, include: &[String], exclude: &[String]) -> Result<Vec<PlannedEntry>, Box<dyn std::error::Error>> {
+    let root = project::single_root(&plan);
+    let root = root.as_deref();
+    let include: Vec<PathGlob> = include.iter().map(|p| PathGlob::new(p, "--include")).collect::<Result<_, _>>()?;
+    let exclude: Vec<PathGlob> = exclude.iter().map(|p| PathGlob::new(p, "--exclude")).collect::<Result<_, _>>()?;
+    let before = plan.len();
+
+    if !include.is_empty() {
+        if let Some(unused) = include.iter().find(|glob| !plan.iter().any(|e| glob.covers(e, root))) {
+            return Err(t!("--include '{}' matches nothing in the tree", unused.pattern).into());
+        }
+        let mut kept: BTreeSet<String> = BTreeSet::new();
+        for entry in plan.iter().filter(|e| include.iter().any(|glob| glob.covers(e, root))) {
+            let mut path = entry.path.as_str();
+            kept.insert(path.to_string());
+            while let Some((parent, _)) = path.rsplit_once('/') {
+                path = parent;
+                kept.insert(path.to_string());
+            }
+        }
+        plan.retain(|e| kept.contains(&e.path));
+    }
+    plan.retain(|e| !exclude.iter().any(|glob| glob.covers(e, root)));
+    status!("🔍 {}", t!("Kept {} of {} entries (--include/--exclude)", plan.len(), before));
+    Ok(plan)
+}
+
+// `--into dir` (repeatable) and `--into-glob 'students/*/'`, in that order
+fn batch_targets(into: &[String], into_glob: &[String]) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let mut targets = into.to_vec();
+    for pattern in into_glob {
+        let pattern_dirs = pattern.trim_end_matches(['/', '\\']);
+        let mut matched: Vec<String> = glob::glob(pattern_dirs)
+            .map_err(|e| t!("--into-glob '{}': {}", pattern, e))?
+            .filter_map(Result::ok)
+            .filter(|path| path.is_dir())
+            .map(|path| path.display().to_string())
+            .collect();
+        if matched.is_empty() {
+            return Err(t!("--into-glob '{}' matches no directories", pattern).into());
+        }
+        matched.sort();
+        targets.extend(matched);
+    }
+    Ok(targets)
+}
+
+// Apply the same plan into every target, then list the result per target
+// `json` holds the start of the run when the result goes out as JSON
+fn run_batch(plan: &[PlannedEntry], targets: &[String], opts: &Options, json: Option<std::time::Instant>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut results = Vec::new();
+    for target in targets {
+        status!("\n📂 {}", target);
+        let result = apply_plan(plan.to_vec(), Some(target), opts);
+        if let Err(e) = &result {
+            eprintln!("❌ {}", t!("Error: {}", e));
+        }
+        results.push((target, result));
+    }
+
+    status!("\n📋 {}", t!("Results for {} target(s):", targets.len()));
+    let (mut failed, mut timed_out) = (0, 0);
+    for (target, result) in &results {
+        match result {
+            Ok(summary) if summary.timed_out > 0 => {
+                timed_out += 1;
+                status!("   ⏱️ {}", t!("{}: stopped by --timeout, {} entries not started", target, summary.timed_out));
+            }
+            Ok(summary) if summary.failed() == 0 => status!(
+                "   ✅ {}",
+                t!(
+                    "{}: {} created, {} already existed",
+                    target,
+                    summary.created().len(),
+                    summary.count(|o| matches!(o, EntryOutcome::Existed | EntryOutcome::Replaced))
+                )
+            ),
+            Ok(summary) => {
+                failed += 1;
+                status!("   ❌ {}", t!("{}: {} failed", target, summary.failed()));
+            }
+            Err(e) => {
+                failed += 1;
+                status!("   ❌ {}", t!("{}: {}", target, e));
+            }
+        }
+    }
+    if let Some(started) = json {
+        let reports = results
+            .iter()
+            .map(|(target, result)| match result {
+                Ok(summary) => run_report::RunReport::new(target, plan, summary),
+                Err(e) => run_report::RunReport::failed(target, plan.len(), Some(e.to_string())),
+            })
+            .collect();
+        run_report::print(reports, started.elapsed())?;
+    }
+    if failed > 0 {
+        eprintln!("⚠️ {}", t!("{} of {} target(s) had failures", failed, targets.len()));
+        std::process::exit(1);
+    }
+    if timed_out > 0 {
+        std::process::exit(timeout::EXIT_CODE);
+    }
+
+    status!("✅ {}", t!("Done!"));
+    Ok(())
+}
+
+// Read the tree from a file or the clipboard and plan it
+fn plan_from_input(
+    file_arg: Option<&str>,
+    opts: &Options,
+) -> Result<(Vec<PlannedEntry>, &'static str), Box<dyn std::error::Error>> {
+    let format = structured::format_for(opts.format.as_deref(), file_arg)?;
+    let (mut text, source) = read_text(file_arg, opts.selection, opts.download)?;
+    if format != structured::Format::Text {
+        let wanted = |block: &fence::Block| block.lang == format.label() || (format == structured::Format::Yaml && block.lang == "yml");
+        if let Some(block) = fence::pick(&text, opts.fence, wanted)? {
+            report_fence(&block);
+            text = fence::in_place(&block).join("\n");
+        }
+        let plan = structured::load(&text, format)?;
+        status!("📋 {}", t!("Read from {} ({} entries, {})", i18n::tr(source), plan.len(), format.label()));
+        return Ok((plan, source));
+    }
+    let lines = tree_lines(&text, source, opts.fence)?;
+
+    if !is_valid_structure(&lines, &opts.parse) {
+        eprintln!("❌ {}", t!("Input is empty or invalid."));
+        std::process::exit(1);
+    }
+    Ok((plan_from_lines(&lines, source, opts)?, source))
+}
+
+// The plan for tree lines already read from `source`
+fn plan_from_lines(lines: &[String], source: &'static str, opts: &Options) -> Result<Vec<PlannedEntry>, Box<dyn std::error::Error>> {
+    status!("📋 {}", t!("Read from {} ({} lines)", i18n::tr(source), lines.len()));
+
+    if opts.parse.infer_hierarchy {
+        status!("🧭 {}", t!("Inferred indent levels:"));
+        for (width, levels) in indent_level_mapping(lines, &opts.parse) {
+            let levels: Vec<String> = levels.iter().map(|l| l.to_string()).collect();
+            status!("   {}", t!("{} column(s) -> level {}", width, levels.join(", ")));
+        }
+    } else if let Some(style) = indent::detect(lines, &opts.parse).filter(|s| s.tabs && s.spaces) {
+        // Mixed indentation is a guess worth showing
+        status!("📐 {}", t!("Tabs mixed with spaces: a tab counts as {} spaces, {} columns per level", style.tab, style.unit));
+    }
+
+    let resolved = resolve_lines(lines, &opts.parse);
+    resolved.check()?;
+    report_duplicates(&resolved, &opts.parse)?;
+    report_long_lines(&resolved.lines, lines);
+    report_noise(&resolved.lines, lines);
+    report_sanitized(&resolved.lines, &opts.parse);
+    report_guessed_kinds(&resolved.lines, &opts.parse);
+
+    Ok(plan_entries(resolved, opts.debug))
+}
+
+// Library API: the same parser and builder, without the command line
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            parse: ParseOptions::default(),
+            debug: false,
+            durable: false,
+            shebang: false,
+            order: Order::DepthFirst,
+            honor_attrs: false,
+            follow_symlinks: true,
+            symlinks: true,
+            selection: None,
+            icons: icons::IconTheme::default(),
+            protect: globset::GlobSet::empty(),
+            progress: None,
+            editorconfig: true,
+            posix_paths: false,
+            rollback_on_error: false,
+            on_conflict: OnConflict::Overwrite,
+            merge: false,
+            respect_gitignore: false,
+            placeholders: None,
+            format: None,
+            fence: None,
+            download: fetch::Limits::default(),
+            vars: vars::Vars::new(),
+            interactive: None,
+            deadline: None,
+            jobs: 1,
+            quiet: false,
+            #[cfg(feature = "test-utils")]
+            simulate_failure: None,
+        }
+    }
+}
+
+impl Options {
+    /// Flush every file and folder to disk (`--durable`)
+    pub fn durable(mut self, durable: bool) -> Options {
+        self.durable = durable;
+        self
+    }
+
+    /// Start executable files with an interpreter line (`--shebang`)
+    pub fn shebang(mut self, shebang: bool) -> Options {
+        self.shebang = shebang;
+        self
+    }
+
+    /// Create through folders that are symlinks on disk; `false` is `--no-follow`
+    pub fn follow_symlinks(mut self, follow: bool) -> Options {
+        self.follow_symlinks = follow;
+        self
+    }
+
+    /// Create the entries of one depth on `jobs` threads (`--jobs`); 1, the
+    /// default, creates them one by one in plan order
+    pub fn jobs(mut self, jobs: usize) -> Options {
+        self.jobs = jobs.max(1);
+        self
+    }
+
+    /// Make `name -> target` entries symbolic links; `false` is `--no-symlinks`
+    pub fn symlinks(mut self, symlinks: bool) -> Options {
+        self.symlinks = symlinks;
+        self
+    }
+
+    /// Write contents the way `.editorconfig` asks; `false` is `--no-editorconfig`
+    pub fn editorconfig(mut self, editorconfig: bool) -> Options {
+        self.editorconfig = editorconfig;
+        self
+    }
+
+    /// Give empty files of known binary types a minimal valid body
+    /// (`--placeholders`): png, gif, bmp, pdf, zip, gz, tgz and tar
+    pub fn placeholders(mut self, placeholders: bool) -> Options {
+        self.placeholders = placeholders.then(placeholder::Registry::default);
+        self
+    }
+
+    /// Register the body empty `.{extension}` files get, replacing a
+    /// built-in one; turns placeholders on
+    pub fn placeholder(mut self, extension: &str, body: Vec<u8>) -> Options {
+        self.placeholders.get_or_insert_with(placeholder::Registry::default).add(extension, body);
+        self
+    }
+}
+
+/// A tree parsed by [`parse_tree`]
+#[derive(Clone)]
+pub struct Tree {
+    plan: Vec<PlannedEntry>,
+}
+
+/// One entry of a [`Tree`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Entry<'a> {
+    /// `/`-separated, relative to the directory the tree is created in
+    pub path: &'a str,
+    pub kind: NodeKind,
+    /// Marked with a trailing `*`
+    pub executable: bool,
+    /// Heredoc contents; `None` creates the file empty
+    pub contents: Option<&'a str>,
+    /// 1-based line of the input
+    pub line: usize,
+}
+
+impl Tree {
+    /// Entries in the order they are created
+    pub fn entries(&self) -> impl Iterator<Item = Entry<'_>> {
+        self.plan.iter().map(|entry| Entry {
+            path: &entry.path,
+            kind: entry.kind,
+            executable: entry.executable,
+            contents: entry.contents.as_deref(),
+            line: entry.line + 1,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.plan.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.plan.is_empty()
+    }
+}
+
+/// Why [`parse_tree`] rejected its input
+#[derive(Debug)]
+pub struct ParseError {
+    message: String,
+    line: Option<usize>,
+}
+
+impl ParseError {
+    /// 1-based input line the problem was found on, when it is about one line
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<Box<dyn std::error::Error>> for ParseError {
+    fn from(e: Box<dyn std::error::Error>) -> ParseError {
+        ParseError { message: e.to_string(), line: None }
+    }
+}
+
+/// Everything the library API fails with. New variants may be added in any
+/// release, so a `match` needs a `_` arm.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The input is not a tree
+    Parse(ParseError),
+    /// The target directory could not be made
+    Target { path: PathBuf, source: io::Error },
+    /// `{{project}}` could not be filled in from the target directory's name
+    Project { message: String },
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parse(e) => e.fmt(f),
+            Error::Target { path, source } => write!(f, "{}: {}", path.display(), source),
+            Error::Project { message } => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Parse(e) => Some(e),
+            Error::Target { source, .. } => Some(source),
+            Error::Project { .. } => None,
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Error {
+        Error::Parse(e)
+    }
+}
+
+/// `Result` with [`Error`] as the default error type
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// One entry [`materialize`] could not create. The other entries are still
+/// attempted, so these are collected in [`Report::failed`] rather than returned.
+#[derive(Clone, Debug)]
+pub struct EntryError {
+    path: String,
+    line: usize,
+    message: String,
+    source: Option<std::sync::Arc<io::Error>>,
+}
+
+impl EntryError {
+    /// `/`-separated, relative to the target directory
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// 1-based input line of the entry
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The I/O error kind, when the failure came from the filesystem
+    pub fn io_kind(&self) -> Option<io::ErrorKind> {
+        self.source.as_ref().map(|e| e.kind())
+    }
+}
+
+impl std::fmt::Display for EntryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+impl std::error::Error for EntryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source.as_deref().map(|e| e as &(dyn std::error::Error + 'static))
+    }
+}
+
+/// Parse a tree in any form the `mks` command reads: indentation, `tree`
+/// output, outlines, heredoc contents and annotations.
+///
+/// ```no_run
+/// let tree = mks::parse_tree("app/\n    src/\n        main.rs\n")?;
+/// mks::materialize(&tree, std::path::Path::new("out"), mks::Options::default())?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn parse_tree(text: &str) -> Result<Tree, ParseError> {
+    let lines: Vec<String> = text.lines().map(String::from).collect();
+    let popts = ParseOptions::default();
+    if !is_valid_structure(&lines, &popts) {
+        return Err(ParseError { message: t!("Input is empty or invalid.").to_string(), line: None });
+    }
+    let resolved = resolve_lines(&lines, &popts);
+    if let Err(e) = resolved.check() {
+        let line = resolved.file_parents.first().map(|file| file.child + 1);
+        return Err(ParseError { message: e.to_string(), line });
+    }
+    let mut plan = plan_entries(resolved, false);
+    plan.retain(|entry| !entry.has_annotation("remove"));
+    order_plan(&mut plan, Order::DepthFirst)?;
+    Ok(Tree { plan })
+}
+
+/// What [`materialize`] did; paths are relative to the target directory
+#[derive(Clone, Debug, Default)]
+pub struct Report {
+    /// Entries that did not exist before
+    pub created: Vec<String>,
+    /// Entries that were already there; files among them were rewritten
+    /// unless protected
+    pub existing: Vec<String>,
+    /// (path, reason) of entries left out, e.g. below a folder that failed
+    pub skipped: Vec<(String, String)>,
+    /// Entries that could not be created
+    pub failed: Vec<EntryError>,
+}
+
+/// Create `tree` below `dir`, making `dir` first. `{{project}}` becomes the
+/// name of `dir`. Unlike the `mks` command this prints nothing on success,
+/// leaves the current directory alone and records nothing for `mks undo`;
+/// only a mode or date that could not be applied is warned about on stderr.
+pub fn materialize(tree: &Tree, dir: &Path, options: Options) -> Result<Report> {
+    let options = Options { quiet: true, ..options };
+    let mut plan = tree.plan.clone();
+    project::inject(&mut plan, dir.to_str(), None).map_err(|e| Error::Project { message: e.to_string() })?;
+    make_dir(dir, options.durable).map_err(|source| Error::Target { path: dir.to_path_buf(), source })?;
+    let summary = create_structure(&plan, dir, &options);
+    let lines: BTreeMap<&str, usize> = plan.iter().map(|entry| (entry.path.as_str(), entry.line + 1)).collect();
+
+    let mut report = Report::default();
+    for (path, outcome) in summary.entries {
+        match outcome {
+            EntryOutcome::Created => report.created.push(path),
+            EntryOutcome::Existed | EntryOutcome::Replaced | EntryOutcome::BackedUp { .. } | EntryOutcome::Protected => {
+                report.existing.push(path)
+            }
+            EntryOutcome::Skipped { reason } | EntryOutcome::Conflict { reason } => report.skipped.push((path, reason)),
+            EntryOutcome::RolledBack => {}
+            EntryOutcome::Failed { reason, source, .. } => {
+                let line = lines.get(path.as_str()).copied().unwrap_or(0);
+                report.failed.push(EntryError { path, line, message: reason, source });
+            }
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn lines_of(text: &str) -> Vec<String> {
+        text.lines().map(String::from).collect()
+    }
+
+    // The (path, kind) of every planned entry, in plan order
+    fn plan(text: &str, popts: &ParseOptions) -> Vec<(String, NodeKind)> {
+        let resolved = resolve_lines(&lines_of(text), popts);
+        resolved.check().unwrap();
+        plan_entries(resolved, false).into_iter().map(|entry| (entry.path, entry.kind)).collect()
+    }
+
+    fn entries(expected: &[(&str, NodeKind)]) -> Vec<(String, NodeKind)> {
+        expected.iter().map(|(path, kind)| (path.to_string(), *kind)).collect()
+    }
+
+    #[test]
+    fn indented_and_drawn_trees_resolve_alike() {
+        let expected = entries(&[
+            ("app", NodeKind::Dir),
+            ("app/src", NodeKind::Dir),
+            ("app/src/main.rs", NodeKind::File),
+            ("app/Cargo.toml", NodeKind::File),
+        ]);
+        let popts = ParseOptions::default();
+        assert_eq!(plan("app/\n    src/\n        main.rs\n    Cargo.toml\n", &popts), expected);
+        assert_eq!(plan("app/\n├── src/\n│   └── main.rs\n└── Cargo.toml\n", &popts), expected);
+        assert_eq!(plan("app/\n|-- src/\n|   `-- main.rs\n`-- Cargo.toml\n", &popts), expected);
+    }
+
+    #[test]
+    fn dedent_closes_the_folders_below() {
+        let text = "app/\n    a/\n        b/\n            deep.txt\n    top.txt\nnext.txt\n";
+        assert_eq!(
+            plan(text, &ParseOptions::default()),
+            entries(&[
+                ("app", NodeKind::Dir),
+                ("app/a", NodeKind::Dir),
+                ("app/a/b", NodeKind::Dir),
+                ("app/a/b/deep.txt", NodeKind::File),
+                ("app/top.txt", NodeKind::File),
+                ("next.txt", NodeKind::File),
+            ])
+        );
+    }
+
+    #[test]
+    fn indent_past_the_open_folders_is_clamped() {
+        let resolved = resolve_lines(&lines_of("app/\n    a.txt\n            lost.txt\n"), &ParseOptions::default());
+        let placed = resolved.lines[2].placed.as_ref().unwrap();
+        assert!(placed.clamped);
+        assert_eq!(plan_entries(resolved, false)[2].path, "app/lost.txt");
+    }
+
+    #[test]
+    fn inline_paths_share_their_folders() {
+        let text = "app/\n├── src/utils/helpers.rs\n└── src/\n    └── main.rs\n";
+        assert_eq!(
+            plan(text, &ParseOptions::default()),
+            entries(&[
+                ("app", NodeKind::Dir),
+                ("app/src", NodeKind::Dir),
+                ("app/src/utils", NodeKind::Dir),
+                ("app/src/utils/helpers.rs", NodeKind::File),
+                ("app/src/main.rs", NodeKind::File),
+            ])
+        );
+    }
+
+    #[test]
+    fn a_file_with_children_is_reported_with_both_lines() {
+        let resolved = resolve_lines(&lines_of("app/\n    main.rs\n        inner.rs\n"), &ParseOptions::default());
+        let error = resolved.check().unwrap_err().to_string();
+        assert!(error.contains("line 2") && error.contains("line 3 is indented below it"), "{}", error);
+        let popts = ParseOptions { auto_dir: true, ..ParseOptions::default() };
+        assert_eq!(plan("app/\n    main.rs\n        inner.rs\n", &popts)[1], ("app/main.rs".to_string(), NodeKind::Dir));
+    }
+
+    // A random tree as indented text and the paths it stands for. Each line
+    // is at most one level below the previous one, and only below a folder.
+    fn random_tree() -> impl Strategy<Value = (String, Vec<(String, NodeKind)>)> {
+        prop::collection::vec((0usize..3, "[a-z]{1,6}", any::<bool>(), any::<bool>()), 1..40).prop_map(|lines| {
+            let (mut text, mut expected) = (String::new(), Vec::new());
+            let mut stack: Vec<String> = Vec::new();
+            let mut open: usize = 0;
+            for (i, (up, stem, is_dir, extension)) in lines.into_iter().enumerate() {
+                let depth = open.saturating_sub(up);
+                stack.truncate(depth);
+                let name = match (is_dir, extension) {
+                    (true, _) => format!("{}{}", stem, i),
+                    (false, true) => format!("{}{}.rs", stem, i),
+                    (false, false) => format!("{}{}", stem, i),
+                };
+                let kind = if is_dir { NodeKind::Dir } else { NodeKind::File };
+                text.push_str(&format!("{}{}{}\n", "    ".repeat(depth), name, if is_dir { "/" } else { "" }));
+                let path = stack.iter().chain([&name]).cloned().collect::<Vec<_>>().join("/");
+                expected.push((path, kind));
+                if is_dir {
+                    stack.push(name);
+                    open = depth + 1;
+                } else {
+                    open = depth;
+                }
+            }
+            (text, expected)
+        })
+    }
+
+    proptest! {
+        // Indented text -> plan -> drawn tree (with icons) -> the same plan
+        #[test]
+        fn drawn_plans_parse_back_to_the_same_plan((text, expected) in random_tree()) {
+            let parsed = plan(&text, &ParseOptions::default());
+            prop_assert_eq!(&parsed, &expected);
+
+            let icons = icons::builtin("emoji").unwrap();
+            let entries = plan_entries(resolve_lines(&lines_of(&text), &ParseOptions::default()), false);
+            let drawn: Vec<String> = dry_run::preview(&entries, &icons).into_iter().map(|(line, _)| line).collect();
+            let popts = ParseOptions { icons: icons.glyphs(), ..ParseOptions::default() };
+            let mut again = plan(&drawn.join("\n"), &popts);
+            let mut expected = expected;
+            // The drawing lists each folder's children together
+            again.sort_by(|a, b| a.0.cmp(&b.0));
+            expected.sort_by(|a, b| a.0.cmp(&b.0));
+            prop_assert_eq!(again, expected);
+        }
+    }
+
+    #[test]
+    fn names_split_on_the_separator_unless_escaped() {
+        assert_eq!(split_names("a.rs & b.rs", Some("&")), ["a.rs", "b.rs"]);
+        assert_eq!(split_names("Q\\&A.md & notes.md", Some("&")), ["Q&A.md", "notes.md"]);
+        assert_eq!(split_names("a.rs && b.rs & ", Some("&")), ["a.rs", "b.rs"]);
+        assert_eq!(split_names("a.rs, R&D.md", Some(",")), ["a.rs", "R&D.md"]);
+        assert_eq!(split_names("a\\,b.txt", Some(",")), ["a,b.txt"]);
+        // Without a separator the line is one name, `\&` still unescaped
+        assert_eq!(split_names("R&D \\& Q.md", None), ["R&D & Q.md"]);
+    }
+
+    #[test]
+    fn split_options_decide_what_a_line_holds() {
+        let text = "app/\n  a.rs & R\\&D.md\n";
+        let split = plan(text, &ParseOptions::default());
+        assert_eq!(split, entries(&[("app", NodeKind::Dir), ("app/a.rs", NodeKind::File), ("app/R&D.md", NodeKind::File)]));
+
+        let split = plan("app/\n  R&D.md\n", &ParseOptions::default());
+        assert_eq!(split, entries(&[("app", NodeKind::Dir), ("app/R", NodeKind::File), ("app/D.md", NodeKind::File)]));
+        let whole = plan("app/\n  R&D.md\n", &ParseOptions { no_split: true, ..ParseOptions::default() });
+        assert_eq!(whole, entries(&[("app", NodeKind::Dir), ("app/R&D.md", NodeKind::File)]));
+
+        // Drawn and plain lines split alike; leading words still go
+        let drawn = plan("app/\n├── a.rs & b.rs\n", &ParseOptions::default());
+        let numbered = plan("app/\n  1. a.rs & b.rs\n", &ParseOptions::default());
+        assert_eq!(drawn, numbered);
+
+        let popts = ParseOptions { multi_sep: Some(",".to_string()), ..ParseOptions::default() };
+        let listed = plan("app/\n  R&D.md, b.rs\n", &popts);
+        assert_eq!(listed, entries(&[("app", NodeKind::Dir), ("app/R&D.md", NodeKind::File), ("app/b.rs", NodeKind::File)]));
+    }
+
+    #[test]
+    fn dumped_ampersands_read_back_as_one_name() {
+        let root = env::temp_dir().join(format!("mks-amp-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("R&D")).unwrap();
+        fs::write(root.join("R&D/plan.md"), "").unwrap();
+        fs::write(root.join("a & b.txt"), "").unwrap();
+
+        let opts = dump::DumpOptions { icons: None, size: None, date: None, follow_links: false };
+        let lines = dump::dump_tree(&root, &opts).unwrap();
+        fs::remove_dir_all(&root).unwrap();
+
+        let top = root.file_name().unwrap().to_string_lossy().to_string();
+        let mut parsed = plan(&lines.join("\n"), &ParseOptions::default());
+        parsed.sort_by(|a, b| a.0.cmp(&b.0));
+        let expected = [
+            (top.clone(), NodeKind::Dir),
+            (format!("{}/R&D", top), NodeKind::Dir),
+            (format!("{}/R&D/plan.md", top), NodeKind::File),
+            (format!("{}/a & b.txt", top), NodeKind::File),
+        ];
+        assert_eq!(parsed, expected);
+    }
+}
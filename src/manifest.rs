@@ -0,0 +1,218 @@
+// File: src\manifest.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: Record what a run created (`.mks/manifest.json`) and undo it
+// License: MIT
+
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::{escape, state, NodeKind, PlannedEntry};
+
+#[derive(Serialize, Deserialize)]
+struct Record {
+    path: String,
+    kind: String,
+    // Files only: what they looked like right after creation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    size: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    modified: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct Manifest {
+    entries: Vec<Record>,
+}
+
+fn timestamp(time: SystemTime) -> String {
+    DateTime::<Local>::from(time).to_rfc3339_opts(chrono::SecondsFormat::Nanos, false)
+}
+
+// The manifest under `.mks/`, or one left at the root by an older version
+fn load(dir: &Path) -> Result<Option<Manifest>, Box<dyn std::error::Error>> {
+    for path in [state::path(dir, state::MANIFEST), dir.join(state::LEGACY_MANIFEST)] {
+        match fs::read_to_string(&path) {
+            Ok(text) => return Ok(Some(serde_json::from_str(&text).map_err(|e| t!("{}: {}", path.display(), e))?)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(t!("{}: {}", path.display(), e).into()),
+        }
+    }
+    Ok(None)
+}
+
+// Always written under `.mks/`; a legacy manifest has been merged by now
+fn save(dir: &Path, manifest: &Manifest, durable: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let legacy = dir.join(state::LEGACY_MANIFEST);
+    if legacy.exists() {
+        fs::remove_file(legacy)?;
+    }
+    let path = state::path(dir, state::MANIFEST);
+    if manifest.entries.is_empty() {
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+        return Ok(());
+    }
+    state::write_atomic(&path, (serde_json::to_string_pretty(manifest)? + "\n").as_bytes(), durable)?;
+    Ok(())
+}
+
+// Add the entries this run created to the manifest of `dir`. Earlier runs
+// stay recorded, so `mks undo` rolls back all of them. The caller holds the lock.
+pub fn record(dir: &Path, plan: &[PlannedEntry], created: &[String], durable: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let mut manifest = load(dir)?.unwrap_or_default();
+    for path in created {
+        let Some(entry) = plan.iter().find(|e| &e.path == path) else {
+            continue;
+        };
+        let meta = (entry.kind != NodeKind::Dir)
+            .then(|| fs::symlink_metadata(escape::long_path(&escape::os_path(path))).ok())
+            .flatten();
+        manifest.entries.retain(|r| &r.path != path);
+        manifest.entries.push(Record {
+            path: path.clone(),
+            kind: entry.kind.label().to_string(),
+            size: meta.as_ref().map(|m| m.len()),
+            modified: meta.and_then(|m| m.modified().ok()).map(timestamp),
+        });
+    }
+    save(dir, &manifest, durable)
+}
+
+enum Verdict {
+    Remove,
+    // Already deleted by hand
+    Gone,
+    // Edited since creation; kept
+    Changed(String),
+    // A directory that still holds something mks did not create
+    NotEmpty,
+}
+
+fn changes(record: &Record, meta: &fs::Metadata) -> Option<String> {
+    let mut changed = Vec::new();
+    if record.size.is_some_and(|size| size != meta.len()) {
+        changed.push(t!("size {} -> {}", record.size.unwrap_or(0), meta.len()));
+    }
+    let now = meta.modified().ok().map(timestamp);
+    if record.modified.is_some() && record.modified != now {
+        changed.push(t!("modified since creation").to_string());
+    }
+    (!changed.is_empty()).then(|| changed.join(", "))
+}
+
+// Would `dir` be empty once everything in `removing` is gone?
+fn empties(dir: &Path, removing: &HashSet<PathBuf>) -> bool {
+    let Ok(items) = fs::read_dir(dir) else {
+        return false;
+    };
+    items.flatten().all(|item| {
+        // Keyed like `judge`, which extends paths past MAX_PATH
+        let path = escape::long_path(&item.path());
+        let is_dir = item.file_type().map(|t| t.is_dir()).unwrap_or(false);
+        removing.contains(&path) && (!is_dir || empties(&path, removing))
+    })
+}
+
+// Decide for every recorded entry, deepest first
+fn judge(dir: &Path, manifest: &Manifest) -> Vec<(usize, Verdict)> {
+    let mut order: Vec<usize> = (0..manifest.entries.len()).collect();
+    order.sort_by_key(|&i| std::cmp::Reverse(manifest.entries[i].path.matches('/').count()));
+
+    let mut removing: HashSet<PathBuf> = HashSet::new();
+    let mut verdicts = Vec::new();
+    for i in order {
+        let record = &manifest.entries[i];
+        let path = escape::long_path(&dir.join(escape::os_path(&record.path)));
+        let verdict = match fs::symlink_metadata(&path) {
+            Err(_) => Verdict::Gone,
+            Ok(meta) if meta.is_dir() => {
+                if empties(&path, &removing) { Verdict::Remove } else { Verdict::NotEmpty }
+            }
+            Ok(meta) => match changes(record, &meta) {
+                Some(what) => Verdict::Changed(what),
+                None => Verdict::Remove,
+            },
+        };
+        if matches!(verdict, Verdict::Remove) {
+            removing.insert(path);
+        }
+        verdicts.push((i, verdict));
+    }
+    verdicts
+}
+
+// `mks undo [dir] [--dry-run]`: delete what mks created in `dir`, keeping
+// anything edited since then
+pub fn undo(dir: &str, dry_run: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let dir = Path::new(dir);
+    if load(dir)?.is_none() {
+        return Err(t!("no manifest in '{}'; nothing to undo", dir.display()).into());
+    }
+    // Another run may have recorded more in the meantime
+    let _lock = state::lock(dir)?;
+    let Some(mut manifest) = load(dir)? else {
+        return Err(t!("no manifest in '{}'; nothing to undo", dir.display()).into());
+    };
+    if dry_run {
+        println!("🧪 {}\n", t!("Dry run: nothing is deleted"));
+    }
+
+    let mut keep = vec![false; manifest.entries.len()];
+    let (mut removed, mut kept) = (0, 0);
+    for (i, verdict) in judge(dir, &manifest) {
+        let path = &manifest.entries[i].path;
+        match verdict {
+            Verdict::Remove => {
+                let target = escape::long_path(&dir.join(escape::os_path(path)));
+                let result = if dry_run {
+                    Ok(())
+                } else if target.is_dir() && !target.is_symlink() {
+                    fs::remove_dir(&target)
+                } else {
+                    fs::remove_file(&target)
+                };
+                match result {
+                    Ok(()) => {
+                        println!("🗑️ {}", path);
+                        removed += 1;
+                    }
+                    Err(e) => {
+                        eprintln!("❌ {}", t!("{}: {}", path, e));
+                        keep[i] = true;
+                        kept += 1;
+                    }
+                }
+            }
+            Verdict::Gone => println!("👻 {}", t!("{} (already gone)", path)),
+            Verdict::Changed(what) => {
+                println!("✏️ {}", t!("{} kept: {}", path, what));
+                keep[i] = true;
+                kept += 1;
+            }
+            Verdict::NotEmpty => {
+                println!("📁 {}", t!("{} kept: holds files mks did not create", path));
+                keep[i] = true;
+                kept += 1;
+            }
+        }
+    }
+
+    println!();
+    if dry_run {
+        println!("📊 {}", t!("{} would be removed, {} kept", removed, kept));
+        return Ok(());
+    }
+    println!("📊 {}", t!("{} removed, {} kept", removed, kept));
+    let mut flags = keep.into_iter();
+    manifest.entries.retain(|_| flags.next().unwrap_or(true));
+    save(dir, &manifest, false)
+}
@@ -0,0 +1,186 @@
+// File: src\metrics.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: Prometheus counters for the watch modes, served over HTTP or written to a textfile
+// License: MIT
+
+use std::{
+    fs,
+    io::{BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::args::MetricsArgs;
+
+// How long a scraper may take to send its request or read the answer
+const SCRAPE_TIMEOUT: Duration = Duration::from_secs(5);
+
+// Counters since the watcher started
+#[derive(Default)]
+struct Metrics {
+    // Entries the tree describes (the last one read, for `watch` and the
+    // clipboard)
+    entries: AtomicU64,
+    // Passes that found something missing and created it
+    applied: AtomicU64,
+    created: AtomicU64,
+    failed: AtomicU64,
+    // Unix time of the last such pass
+    last_applied: AtomicU64,
+}
+
+impl Metrics {
+    fn record(&self, created: usize, failed: usize) {
+        self.applied.fetch_add(1, Ordering::Relaxed);
+        self.created.fetch_add(created as u64, Ordering::Relaxed);
+        self.failed.fetch_add(failed as u64, Ordering::Relaxed);
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        self.last_applied.store(now, Ordering::Relaxed);
+    }
+
+    // The text exposition format
+    fn render(&self) -> String {
+        let rows = [
+            ("mks_tree_entries", "gauge", "Entries in the watched tree", &self.entries),
+            ("mks_trees_applied_total", "counter", "Passes that created missing entries", &self.applied),
+            ("mks_nodes_created_total", "counter", "Folders and files created", &self.created),
+            ("mks_failures_total", "counter", "Entries that could not be created", &self.failed),
+            ("mks_last_applied_timestamp_seconds", "gauge", "Unix time of the last pass that created entries", &self.last_applied),
+        ];
+        let mut out = String::new();
+        for (name, kind, help, value) in rows {
+            out.push_str(&format!("# HELP {} {}\n# TYPE {} {}\n{} {}\n", name, help, name, kind, name, value.load(Ordering::Relaxed)));
+        }
+        out
+    }
+}
+
+// Where the counters go: `--metrics-addr` and/or `--metrics-file`
+pub struct Exporter {
+    metrics: Arc<Metrics>,
+    file: Option<PathBuf>,
+}
+
+impl Exporter {
+    // None unless `--metrics-addr` or `--metrics-file` was given
+    pub fn from_args(args: &MetricsArgs, entries: usize) -> Result<Option<Self>, Box<dyn std::error::Error>> {
+        match (args.metrics_addr.as_deref(), args.metrics_file.as_deref()) {
+            (None, None) => Ok(None),
+            (addr, file) => Ok(Some(Exporter::start(entries, addr, file)?)),
+        }
+    }
+
+    // The file path is made absolute, as the watcher changes directory
+    pub fn start(entries: usize, addr: Option<&str>, file: Option<&str>) -> Result<Self, Box<dyn std::error::Error>> {
+        let metrics = Arc::new(Metrics::default());
+        metrics.entries.store(entries as u64, Ordering::Relaxed);
+        if let Some(addr) = addr {
+            let listener = TcpListener::bind(addr).map_err(|e| t!("--metrics-addr {}: {}", addr, e))?;
+            println!("📈 {}", t!("Metrics at http://{}/metrics", listener.local_addr()?));
+            let metrics = Arc::clone(&metrics);
+            thread::spawn(move || {
+                for stream in listener.incoming().flatten() {
+                    // One thread per scrape, so a client that never sends
+                    // its request cannot hold up the others
+                    let metrics = Arc::clone(&metrics);
+                    thread::spawn(move || {
+                        // A scraper that hangs up early is its own problem
+                        let _ = respond(stream, &metrics);
+                    });
+                }
+            });
+        }
+        let exporter = Exporter { metrics, file: file.map(std::path::absolute).transpose()? };
+        exporter.flush()?;
+        Ok(exporter)
+    }
+
+    // The tree changed (`watch`) or a new one was copied
+    pub fn set_entries(&self, entries: usize) {
+        self.metrics.entries.store(entries as u64, Ordering::Relaxed);
+    }
+
+    // One pass that created entries
+    pub fn record(&self, created: usize, failed: usize) -> Result<(), Box<dyn std::error::Error>> {
+        self.metrics.record(created, failed);
+        self.flush()
+    }
+
+    // Rewrite the textfile; node_exporter may read it at any moment, so it
+    // is written next to itself and renamed into place
+    fn flush(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(path) = &self.file else {
+            return Ok(());
+        };
+        let mut temp = path.clone().into_os_string();
+        temp.push(".tmp");
+        fs::write(&temp, self.metrics.render())?;
+        fs::rename(&temp, path).map_err(|e| t!("--metrics-file {}: {}", path.display(), e))?;
+        Ok(())
+    }
+}
+
+// Answer one scrape: GET /metrics (or /), anything else is a 404
+fn respond(mut stream: TcpStream, metrics: &Metrics) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(SCRAPE_TIMEOUT))?;
+    stream.set_write_timeout(Some(SCRAPE_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request = String::new();
+    reader.read_line(&mut request)?;
+    // Drain the headers up to the blank line
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        header.clear();
+    }
+    let path = request.split_whitespace().nth(1).unwrap_or("");
+    let (status, body) = match path {
+        "/metrics" | "/" => ("200 OK", metrics.render()),
+        _ => ("404 Not Found", "not found\n".to_string()),
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::*;
+
+    fn scrape(addr: std::net::SocketAddr, path: &str) -> String {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(stream, "GET {} HTTP/1.1\r\nHost: x\r\n\r\n", path).unwrap();
+        let mut answer = String::new();
+        stream.read_to_string(&mut answer).unwrap();
+        answer
+    }
+
+    #[test]
+    fn a_silent_client_does_not_hold_up_the_next_scrape() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+        let exporter = Exporter::start(3, Some(&addr.to_string()), None).unwrap();
+        exporter.record(2, 1).unwrap();
+
+        let _silent = TcpStream::connect(addr).unwrap();
+        let answer = scrape(addr, "/metrics");
+        assert!(answer.starts_with("HTTP/1.1 200 OK"), "{}", answer);
+        assert!(answer.contains("\nmks_tree_entries 3\n"));
+        assert!(answer.contains("\nmks_nodes_created_total 2\n"));
+        assert!(answer.contains("\nmks_failures_total 1\n"));
+        assert!(scrape(addr, "/other").starts_with("HTTP/1.1 404"));
+    }
+}
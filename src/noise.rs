@@ -0,0 +1,83 @@
+// File: src\noise.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: Terminal noise that rides along in copied output: prompts,
+//              truncation markers, pager artifacts and `tree` banners
+// License: MIT
+
+use crate::glyphs;
+
+// Skip reasons, as the parser reports them, with the code each warning carries
+const CODES: &[(&str, &str)] = &[
+    ("shell prompt", "noise/prompt"),
+    ("truncation marker", "noise/truncated"),
+    ("pager artifact", "noise/pager"),
+    ("tree banner", "noise/banner"),
+    ("tree summary", "noise/summary"),
+];
+
+pub fn code(reason: &str) -> Option<&'static str> {
+    CODES.iter().find(|(r, _)| *r == reason).map(|(_, code)| *code)
+}
+
+// `$ tree`, `> more`, `me@box:~/app$ tree`, `[me@box app]$ tree`,
+// `PS C:\app> tree`, `C:\app>tree /f`
+fn is_prompt(line: &str) -> bool {
+    if matches!(line, "$" | ">") || line.starts_with("$ ") || line.starts_with("> ") {
+        return true;
+    }
+    let first = line.split_whitespace().next().unwrap_or("");
+    let user_host = first.contains('@') && first.contains(':') && first.ends_with(['$', '#', '%', '>']);
+    let bracketed = line.starts_with('[') && (line.contains("]$") || line.contains("]#"));
+    let windows = line.strip_prefix("PS ").unwrap_or(line);
+    let mut chars = windows.chars();
+    let drive = chars.next().is_some_and(|c| c.is_ascii_alphabetic()) && chars.as_str().starts_with(":\\") && windows.contains('>');
+    user_host || bracketed || drive
+}
+
+// `…`, `...`, `… 12 more`, `[...]`, drawn where a listing was cut short
+fn is_truncation(body: &str) -> bool {
+    body.starts_with('…') || body.starts_with("...") || body == "[...]" || body == "[…]"
+}
+
+// `(END)` and `:` from less, `--More--` from more, `lines 1-40/120`
+fn is_pager(body: &str) -> bool {
+    let more = body.trim_start_matches('-').trim_start();
+    body == "(END)"
+        || body == ":"
+        || (more.starts_with("More") && body.starts_with('-'))
+        || body.strip_prefix("lines ").is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit()) && rest.contains('-'))
+}
+
+// What Windows `tree` prints above the tree
+fn is_banner(line: &str) -> bool {
+    line.starts_with("Folder PATH listing") || line.starts_with("Volume serial number is") || line == "No subfolders exist"
+}
+
+// `3 directories, 12 files` under `tree` output
+fn is_summary(line: &str) -> bool {
+    let mut words = line.split_whitespace();
+    let count = words.next().is_some_and(|w| w.chars().all(|c| c.is_ascii_digit()));
+    let unit = words.next().is_some_and(|w| w.trim_end_matches(',').starts_with("director"));
+    count && unit && words.all(|w| w.trim_end_matches(',').chars().all(|c| c.is_ascii_digit()) || w.starts_with("file"))
+}
+
+// Why `line` is noise rather than a name, if it is
+pub fn classify(line: &str) -> Option<&'static str> {
+    let line = line.trim();
+    // Truncation and pager lines may sit inside the tree's connectors
+    let body = line.trim_start_matches(|c: char| glyphs::is_indent(c) || matches!(c, '-' | '`' | '+' | '\\')).trim();
+    if is_prompt(line) {
+        Some("shell prompt")
+    } else if is_banner(line) {
+        Some("tree banner")
+    } else if is_summary(line) {
+        Some("tree summary")
+    } else if is_pager(line) || is_pager(body) {
+        Some("pager artifact")
+    } else if is_truncation(body) {
+        Some("truncation marker")
+    } else {
+        None
+    }
+}
@@ -0,0 +1,68 @@
+// File: src\outline.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: Org-mode (`*`, `**`) and Markdown (`#`, `##`) outlines as trees
+// License: MIT
+
+// `** name` -> (2, "name"). The stars or hashes start the line and a space
+// follows them; a Markdown closing sequence (`## src ##`) and backticks
+// around the name are dropped.
+fn heading(line: &str) -> Option<(char, usize, &str)> {
+    let marker = line.chars().next().filter(|&c| c == '*' || c == '#')?;
+    let level = line.len() - line.trim_start_matches(marker).len();
+    let mut text = line[level..].strip_prefix([' ', '\t'])?.trim();
+    if marker == '#' {
+        if let Some((name, closing)) = text.rsplit_once(' ') {
+            if !closing.is_empty() && closing.chars().all(|c| c == '#') {
+                text = name.trim_end();
+            }
+        }
+    }
+    let text = text.trim_matches('`').trim();
+    (!text.is_empty()).then_some((marker, level, text))
+}
+
+// An outline is nothing but headings of one kind, at least two of them.
+// A tree with a `# comment` line on top is not one.
+fn headings(lines: &[&str]) -> Option<Vec<Option<(usize, String)>>> {
+    let mut kind = None;
+    let mut count = 0;
+    let mut out = Vec::with_capacity(lines.len());
+    for line in lines {
+        if line.trim().is_empty() {
+            out.push(None);
+            continue;
+        }
+        let (marker, level, text) = heading(line)?;
+        if kind.is_some_and(|k| k != marker) {
+            return None;
+        }
+        kind = Some(marker);
+        count += 1;
+        out.push(Some((level, text.to_string())));
+    }
+    (count >= 2).then_some(out)
+}
+
+pub fn is_outline(content: &str) -> bool {
+    headings(&content.lines().collect::<Vec<_>>()).is_some()
+}
+
+// The outline as indented tree lines, one per input line so line numbers
+// stay the same. None when the input is not an outline.
+pub fn to_indented(lines: &[String]) -> Option<Vec<String>> {
+    let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+    let headings = headings(&lines)?;
+    let mut out = Vec::with_capacity(headings.len());
+    for (i, heading) in headings.iter().enumerate() {
+        let Some((level, text)) = heading else {
+            out.push(String::new());
+            continue;
+        };
+        // A heading with subheadings is a directory, slash or not
+        let next = headings[i + 1..].iter().flatten().next();
+        let slash = if next.is_some_and(|(below, _)| below > level) && !text.ends_with('/') { "/" } else { "" };
+        out.push(format!("{}{}{}", "    ".repeat(level - 1), text, slash));
+    }
+    Some(out)
+}
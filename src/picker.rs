@@ -0,0 +1,72 @@
+// File: src\picker.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: Ask where to create a clipboard tree, with path completion
+// License: MIT
+
+use std::{env, io::IsTerminal, path::PathBuf};
+
+use rustyline::{
+    completion::{Completer, FilenameCompleter, Pair},
+    error::ReadlineError,
+    highlight::Highlighter,
+    hint::Hinter,
+    validate::Validator,
+    Context, Editor, Helper,
+};
+
+struct PathHelper {
+    completer: FilenameCompleter,
+}
+
+impl Completer for PathHelper {
+    type Candidate = Pair;
+
+    fn complete(&self, line: &str, pos: usize, ctx: &Context<'_>) -> rustyline::Result<(usize, Vec<Pair>)> {
+        self.completer.complete(line, pos, ctx)
+    }
+}
+
+impl Hinter for PathHelper {
+    type Hint = String;
+}
+
+impl Highlighter for PathHelper {}
+
+impl Validator for PathHelper {}
+
+impl Helper for PathHelper {}
+
+fn expand_home(input: &str) -> String {
+    match (input.strip_prefix('~'), env::var_os("HOME")) {
+        (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with(['/', '\\']) => {
+            let mut path = PathBuf::from(home).into_os_string();
+            path.push(rest);
+            path.to_string_lossy().into_owned()
+        }
+        _ => input.to_string(),
+    }
+}
+
+// Some(dir) to create the tree in, None for the current directory. Only asks
+// on a terminal; Ctrl-C / Ctrl-D cancel the run.
+pub fn pick_base(root: Option<&str>) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    if !std::io::stdin().is_terminal() {
+        return Ok(None);
+    }
+
+    let mut editor: Editor<PathHelper, rustyline::history::DefaultHistory> = Editor::new()?;
+    editor.set_helper(Some(PathHelper { completer: FilenameCompleter::new() }));
+
+    let what = match root {
+        Some(root) => format!("'{}'", root),
+        None => t!("the tree").to_string(),
+    };
+    println!("📂 {}", t!("Where should {} be created? (Tab completes, Enter for the current directory)", what));
+    match editor.readline("   > ") {
+        Ok(line) if line.trim().is_empty() => Ok(None),
+        Ok(line) => Ok(Some(expand_home(line.trim()))),
+        Err(ReadlineError::Interrupted | ReadlineError::Eof) => Err(t!("cancelled").into()),
+        Err(e) => Err(e.into()),
+    }
+}
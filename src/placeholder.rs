@@ -0,0 +1,91 @@
+// File: src\placeholder.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: `--placeholders` - smallest valid bodies for binary formats, so fixture trees pass magic-number checks
+// License: MIT
+
+use std::{collections::BTreeMap, fs, path::Path};
+
+// A 1x1 transparent pixel
+const PNG: &[u8] = &[
+    0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44, 0x52, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01,
+    0x08, 0x06, 0x00, 0x00, 0x00, 0x1f, 0x15, 0xc4, 0x89, 0x00, 0x00, 0x00, 0x0b, 0x49, 0x44, 0x41, 0x54, 0x78, 0xda, 0x63, 0x60, 0x00, 0x02, 0x00,
+    0x00, 0x05, 0x00, 0x01, 0xe9, 0xfa, 0xdc, 0xd8, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4e, 0x44, 0xae, 0x42, 0x60, 0x82,
+];
+
+// A 1x1 transparent pixel
+const GIF: &[u8] = &[
+    0x47, 0x49, 0x46, 0x38, 0x39, 0x61, 0x01, 0x00, 0x01, 0x00, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0x21, 0xf9, 0x04, 0x01, 0x00,
+    0x00, 0x00, 0x00, 0x2c, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x01, 0x00, 0x00, 0x02, 0x01, 0x44, 0x00, 0x3b,
+];
+
+// A 1x1 white pixel, 24 bits
+const BMP: &[u8] = &[
+    0x42, 0x4d, 0x3a, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x36, 0x00, 0x00, 0x00, 0x28, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00,
+    0x00, 0x00, 0x01, 0x00, 0x18, 0x00, 0x00, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x13, 0x0b, 0x00, 0x00, 0x13, 0x0b, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0x00,
+];
+
+// One blank Letter page; the xref offsets count every byte above them
+const PDF: &[u8] = b"%PDF-1.4\n\
+1 0 obj\n<< /Type /Catalog /Pages 2 0 R >>\nendobj\n\
+2 0 obj\n<< /Type /Pages /Kids [3 0 R] /Count 1 >>\nendobj\n\
+3 0 obj\n<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] >>\nendobj\n\
+xref\n0 4\n0000000000 65535 f \n0000000009 00000 n \n0000000058 00000 n \n0000000115 00000 n \n\
+trailer\n<< /Size 4 /Root 1 0 R >>\nstartxref\n186\n%%EOF\n";
+
+// Just the end-of-central-directory record: an empty archive
+const ZIP: &[u8] = &[0x50, 0x4b, 0x05, 0x06, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+
+// An empty member
+const GZIP: &[u8] = &[0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x03, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+
+// The two zero blocks that end an archive
+const TAR: &[u8] = &[0; 1024];
+
+const BUILT_IN: &[(&str, &[u8])] = &[
+    ("bmp", BMP),
+    ("gif", GIF),
+    ("gz", GZIP),
+    ("pdf", PDF),
+    ("png", PNG),
+    ("tar", TAR),
+    ("tgz", GZIP),
+    ("zip", ZIP),
+];
+
+// Extension (lowercase, without the dot) to the body an empty file gets
+#[derive(Clone)]
+pub struct Registry {
+    bodies: BTreeMap<String, Vec<u8>>,
+}
+
+impl Default for Registry {
+    fn default() -> Registry {
+        let bodies = BUILT_IN.iter().map(|(ext, body)| (ext.to_string(), body.to_vec())).collect();
+        Registry { bodies }
+    }
+}
+
+impl Registry {
+    // The built-in formats plus the config `[placeholders]` table, whose
+    // values are sample files to copy; they win over the built-in ones
+    pub fn with_samples(samples: &BTreeMap<String, String>) -> Result<Registry, Box<dyn std::error::Error>> {
+        let mut registry = Registry::default();
+        for (ext, sample) in samples {
+            let body = fs::read(sample).map_err(|e| t!("placeholder for .{}: could not read '{}': {}", ext, sample, e))?;
+            registry.add(ext, body);
+        }
+        Ok(registry)
+    }
+
+    pub fn add(&mut self, ext: &str, body: Vec<u8>) {
+        self.bodies.insert(ext.trim_start_matches('.').to_lowercase(), body);
+    }
+
+    // The body for a file named `path`, if its extension is registered
+    pub fn body(&self, path: &str) -> Option<&[u8]> {
+        let ext = Path::new(path).extension()?.to_string_lossy().to_lowercase();
+        self.bodies.get(&ext).map(Vec::as_slice)
+    }
+}
@@ -0,0 +1,265 @@
+// File: src\plan_file.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: Versioned plan JSON (`mks plan`, `mks apply`) and its published schemas
+// License: MIT
+
+use std::{collections::HashSet, fs};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{attrs::NodeAttrs, Annotation, NodeKind, PlannedEntry};
+
+// Bump the major version for changes old readers cannot handle; a minor
+// version may add optional fields, which older readers skip
+pub const PLAN_VERSION: &str = "1.0.0";
+const SUPPORTED_MAJOR: &str = "1";
+
+// Fields this version knows, per object; anything else in a plan from a
+// newer minor version is dropped before the strict parse below
+const PLAN_FIELDS: &[&str] = &["schema_version", "entries"];
+const ENTRY_FIELDS: &[&str] = &["path", "kind", "executable", "contents", "attrs", "annotations"];
+const ATTR_FIELDS: &[&str] = &["file_type", "mode", "owner", "group", "size", "mtime"];
+const ANNOTATION_FIELDS: &[&str] = &["key", "value"];
+
+const PLAN_SCHEMA: &str = include_str!("../schema/plan.schema.json");
+const REPORT_SCHEMA: &str = include_str!("../schema/report.schema.json");
+const RESULT_SCHEMA: &str = include_str!("../schema/result.schema.json");
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PlanFile {
+    schema_version: String,
+    entries: Vec<EntryRecord>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EntryRecord {
+    path: String,
+    kind: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    executable: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    contents: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    attrs: Option<NodeAttrs>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    annotations: Vec<Annotation>,
+}
+
+// `mks schema plan|report|result`
+pub fn schema(name: Option<&str>) -> Result<&'static str, Box<dyn std::error::Error>> {
+    match name {
+        Some("plan") => Ok(PLAN_SCHEMA),
+        Some("report") => Ok(REPORT_SCHEMA),
+        Some("result") => Ok(RESULT_SCHEMA),
+        _ => Err(t!("usage: mks schema plan|report|result").into()),
+    }
+}
+
+pub fn to_json(plan: &[PlannedEntry]) -> Result<String, Box<dyn std::error::Error>> {
+    let file = PlanFile {
+        schema_version: PLAN_VERSION.to_string(),
+        entries: plan
+            .iter()
+            .map(|entry| EntryRecord {
+                path: entry.path.clone(),
+                kind: entry.kind.label().to_string(),
+                executable: entry.executable,
+                contents: entry.contents.clone(),
+                attrs: entry.attrs.clone(),
+                annotations: entry.annotations.clone(),
+            })
+            .collect(),
+    };
+    Ok(serde_json::to_string_pretty(&file)?)
+}
+
+// Relative, `/`-separated, no `..` or empty components
+fn check_path(path: &str) -> Result<(), String> {
+    if path.is_empty() {
+        return Err(t!("empty path").to_string());
+    }
+    let backslash = path.contains('\\') && !crate::escape::has_raw_bytes(path);
+    if path.starts_with('/') || backslash || path.contains(':') {
+        return Err(t!("'{}' must be a relative path with '/' separators", path));
+    }
+    if path.split('/').any(|part| part.is_empty() || part == "." || part == "..") {
+        return Err(t!("'{}' has an empty, '.' or '..' component", path));
+    }
+    Ok(())
+}
+
+fn minor(version: &str) -> u64 {
+    version.split('.').nth(1).and_then(|part| part.parse().ok()).unwrap_or(0)
+}
+
+// Remove the keys outside `known` from an object, returning how many went
+fn drop_unknown(value: &mut serde_json::Value, known: &[&str]) -> usize {
+    let Some(object) = value.as_object_mut() else {
+        return 0;
+    };
+    let before = object.len();
+    object.retain(|key, _| known.contains(&key.as_str()));
+    before - object.len()
+}
+
+fn drop_newer_fields(value: &mut serde_json::Value) -> usize {
+    let mut dropped = drop_unknown(value, PLAN_FIELDS);
+    let entries = value.get_mut("entries").and_then(|v| v.as_array_mut());
+    for entry in entries.into_iter().flatten() {
+        dropped += drop_unknown(entry, ENTRY_FIELDS);
+        if let Some(attrs) = entry.get_mut("attrs") {
+            dropped += drop_unknown(attrs, ATTR_FIELDS);
+        }
+        let annotations = entry.get_mut("annotations").and_then(|v| v.as_array_mut());
+        for annotation in annotations.into_iter().flatten() {
+            dropped += drop_unknown(annotation, ANNOTATION_FIELDS);
+        }
+    }
+    dropped
+}
+
+// Read a plan written by `mks plan` (or any tool following the schema),
+// rejecting anything this version cannot apply faithfully
+pub fn load(file: &str) -> Result<Vec<PlannedEntry>, Box<dyn std::error::Error>> {
+    let text = fs::read_to_string(file).map_err(|e| t!("{}: {}", file, e))?;
+    parse(&text, file)
+}
+
+// `file` only names the plan in messages
+fn parse(text: &str, file: &str) -> Result<Vec<PlannedEntry>, Box<dyn std::error::Error>> {
+    let mut value: serde_json::Value = serde_json::from_str(text).map_err(|e| t!("{}: {}", file, e))?;
+
+    // Check the version first, so a newer plan gets a clear message rather
+    // than a complaint about some field this version does not know
+    let version = value.get("schema_version").and_then(|v| v.as_str()).unwrap_or("");
+    if version.split('.').next() != Some(SUPPORTED_MAJOR) || version.split('.').count() != 3 {
+        return Err(t!(
+            "{}: schema_version '{}' is not supported; this mks reads {}.x plans (run `mks schema plan`)",
+            file,
+            version,
+            SUPPORTED_MAJOR
+        )
+        .into());
+    }
+    // Fields from a newer minor version are optional by definition; in a
+    // plan of this version or older, an unknown field is still a typo
+    if minor(version) > minor(PLAN_VERSION) {
+        let version = version.to_string();
+        let dropped = drop_newer_fields(&mut value);
+        if dropped > 0 {
+            status!(
+                "ℹ️ {}",
+                t!("{}: plan version {} is newer than {}; {} unknown field(s) ignored", file, version, PLAN_VERSION, dropped)
+            );
+        }
+    }
+    let plan: PlanFile = serde_json::from_value(value).map_err(|e| t!("{}: {}", file, e))?;
+
+    let mut seen = HashSet::new();
+    let mut entries = Vec::with_capacity(plan.entries.len());
+    for (i, record) in plan.entries.into_iter().enumerate() {
+        let at = |message: String| t!("{}: entries[{}]: {}", file, i, message);
+        check_path(&record.path).map_err(at)?;
+        let kind = NodeKind::from_label(&record.kind)
+            .ok_or_else(|| at(t!("unknown kind '{}'", record.kind)))?;
+        if !seen.insert(record.path.clone()) {
+            return Err(at(t!("'{}' is listed twice", record.path)).into());
+        }
+        entries.push(PlannedEntry {
+            line: i,
+            depth: record.path.matches('/').count(),
+            path: record.path,
+            kind,
+            executable: record.executable,
+            attrs: record.attrs,
+            annotations: record.annotations,
+            contents: record.contents,
+        });
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plan_of(version: &str, entries: &str) -> String {
+        format!(r#"{{ "schema_version": "{}", "entries": [{}] }}"#, version, entries)
+    }
+
+    fn error(text: &str) -> String {
+        parse(text, "plan.json").err().expect("the plan should be rejected").to_string()
+    }
+
+    #[test]
+    fn written_plans_load_back() {
+        let text = plan_of(
+            PLAN_VERSION,
+            r#"{ "path": "app", "kind": "directory" },
+               { "path": "app/run.sh", "kind": "file", "executable": true, "contents": "echo\n" }"#,
+        );
+        let entries = parse(&text, "plan.json").unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].path, "app/run.sh");
+        assert_eq!(entries[1].depth, 1);
+        assert!(entries[1].executable);
+        assert_eq!(entries[1].contents.as_deref(), Some("echo\n"));
+
+        let again = parse(&to_json(&entries).unwrap(), "plan.json").unwrap();
+        let paths: Vec<_> = again.iter().map(|entry| entry.path.as_str()).collect();
+        assert_eq!(paths, ["app", "app/run.sh"]);
+    }
+
+    #[test]
+    fn parent_and_absolute_paths_are_rejected() {
+        let dotdot = error(&plan_of("1.0.0", r#"{ "path": "app/../etc", "kind": "directory" }"#));
+        assert_eq!(dotdot, "plan.json: entries[0]: 'app/../etc' has an empty, '.' or '..' component");
+
+        let absolute = error(&plan_of("1.0.0", r#"{ "path": "/etc/passwd", "kind": "file" }"#));
+        assert_eq!(absolute, "plan.json: entries[0]: '/etc/passwd' must be a relative path with '/' separators");
+
+        for path in ["C:/x", r"app\\x", "app//x", "./app", ""] {
+            let entry = format!(r#"{{ "path": "{}", "kind": "file" }}"#, path);
+            assert!(parse(&plan_of("1.0.0", &entry), "plan.json").is_err(), "{:?} was accepted", path);
+        }
+    }
+
+    #[test]
+    fn duplicate_paths_name_the_second_entry() {
+        let text = plan_of(
+            "1.0.0",
+            r#"{ "path": "a", "kind": "directory" }, { "path": "a/b", "kind": "file" }, { "path": "a/b", "kind": "directory" }"#,
+        );
+        assert_eq!(error(&text), "plan.json: entries[2]: 'a/b' is listed twice");
+    }
+
+    #[test]
+    fn other_major_versions_are_refused_before_the_entries() {
+        // The entry would fail too; the version is what gets reported
+        for version in ["2.0.0", "0.9.0", "1.0", ""] {
+            let message = error(&plan_of(version, r#"{ "path": "/x", "kind": "widget" }"#));
+            assert!(message.contains(&format!("schema_version '{}' is not supported", version)), "{}", message);
+        }
+        let unknown_kind = error(&plan_of("1.0.0", r#"{ "path": "x", "kind": "widget" }"#));
+        assert_eq!(unknown_kind, "plan.json: entries[0]: unknown kind 'widget'");
+    }
+
+    #[test]
+    fn newer_minor_versions_may_add_fields() {
+        let entries = r#"{ "path": "a", "kind": "file", "checksum": "abc",
+                           "attrs": { "mode": 420, "xattrs": {} },
+                           "annotations": [{ "key": "protect", "value": null, "why": "x" }] }"#;
+        let text = format!(r#"{{ "schema_version": "1.4.0", "generator": "x", "entries": [{}] }}"#, entries);
+        let loaded = parse(&text, "plan.json").unwrap();
+        assert_eq!(loaded[0].path, "a");
+        assert_eq!(loaded[0].attrs.as_ref().and_then(|attrs| attrs.mode), Some(420));
+        assert_eq!(loaded[0].annotations.len(), 1);
+
+        // Within this version an unknown field is a mistake, not an extension
+        let typo = error(&plan_of("1.0.0", r#"{ "path": "a", "kind": "file", "exectuable": true }"#));
+        assert!(typo.contains("unknown field `exectuable`"), "{}", typo);
+    }
+}
@@ -0,0 +1,131 @@
+// File: src\presets.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: Preset tree files with `extends` inheritance and overlays
+// License: MIT
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+use crate::{config, plan_entries, project::single_root, resolve_lines, ParseOptions, PlannedEntry};
+
+// TOML block between `+++` lines at the top of a preset
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FrontMatter {
+    pub extends: Option<String>,
+}
+
+pub fn templates_dir() -> Option<PathBuf> {
+    config::config_dir().map(|dir| dir.join("templates"))
+}
+
+// A bare name is looked up in the template store; anything that looks like a
+// path is used as one, relative to the extending preset when there is one.
+pub fn locate(name: &str, relative_to: Option<&Path>) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let as_path = Path::new(name);
+    if name.contains('/') || name.contains('\\') || as_path.extension().is_some() {
+        return Ok(match relative_to {
+            Some(base) if as_path.is_relative() => base.join(as_path),
+            _ => as_path.to_path_buf(),
+        });
+    }
+    let dir = templates_dir().ok_or(t!("cannot locate the config directory (HOME is not set)"))?;
+    Ok(dir.join(format!("{}.txt", name)))
+}
+
+pub fn split_front_matter(content: &str) -> Result<(FrontMatter, Vec<String>), Box<dyn std::error::Error>> {
+    let mut lines = content.lines();
+    if lines.clone().next().map(str::trim) != Some("+++") {
+        return Ok((FrontMatter::default(), content.lines().map(String::from).collect()));
+    }
+    lines.next();
+
+    let mut header = String::new();
+    let mut closed = false;
+    for line in lines.by_ref() {
+        if line.trim() == "+++" {
+            closed = true;
+            break;
+        }
+        header.push_str(line);
+        header.push('\n');
+    }
+    if !closed {
+        return Err(t!("front matter is missing its closing '+++'").into());
+    }
+
+    let front: FrontMatter = toml::from_str(&header)?;
+    Ok((front, lines.map(String::from).collect()))
+}
+
+// Child entries replace base entries with the same path, `[remove]` drops a
+// base entry together with its descendants, anything else is added. When both
+// trees have a single root directory the base is moved under the child's root
+// name, so an overlay may rename the project.
+fn overlay(mut base: Vec<PlannedEntry>, child: Vec<PlannedEntry>) -> Vec<PlannedEntry> {
+    if let (Some(from), Some(to)) = (single_root(&base), single_root(&child)) {
+        if from != to {
+            let prefix = format!("{}/", from);
+            for entry in &mut base {
+                if entry.path == from {
+                    entry.path = to.clone();
+                } else if let Some(rest) = entry.path.strip_prefix(&prefix) {
+                    entry.path = format!("{}/{}", to, rest);
+                }
+            }
+        }
+    }
+
+    for entry in child {
+        if entry.has_annotation("remove") {
+            let prefix = format!("{}/", entry.path);
+            base.retain(|e| e.path != entry.path && !e.path.starts_with(&prefix));
+        } else if let Some(existing) = base.iter_mut().find(|e| e.path == entry.path) {
+            *existing = entry;
+        } else {
+            base.push(entry);
+        }
+    }
+    base
+}
+
+fn load(
+    name: &str,
+    relative_to: Option<&Path>,
+    popts: &ParseOptions,
+    debug: bool,
+    chain: &mut Vec<PathBuf>,
+) -> Result<Vec<PlannedEntry>, Box<dyn std::error::Error>> {
+    let path = locate(name, relative_to)?;
+    if chain.contains(&path) {
+        let names: Vec<String> = chain.iter().chain([&path]).map(|p| p.display().to_string()).collect();
+        return Err(t!("preset inheritance cycle: {}", names.join(" -> ")).into());
+    }
+    chain.push(path.clone());
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| t!("cannot read preset '{}': {}", path.display(), e))?;
+    let (front, lines) = split_front_matter(&content)
+        .map_err(|e| t!("{}: {}", path.display(), e))?;
+    let resolved = resolve_lines(&lines, popts);
+    resolved.check().map_err(|e| t!("{}: {}", path.display(), e))?;
+    let plan = plan_entries(resolved, debug);
+
+    match front.extends {
+        Some(parent) => {
+            let base = load(&parent, path.parent(), popts, debug, chain)?;
+            Ok(overlay(base, plan))
+        }
+        None => Ok(plan),
+    }
+}
+
+// Resolve a preset and everything it extends into a single plan
+pub fn load_plan(name: &str, popts: &ParseOptions, debug: bool) -> Result<Vec<PlannedEntry>, Box<dyn std::error::Error>> {
+    load(name, None, popts, debug, &mut Vec::new())
+}
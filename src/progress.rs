@@ -0,0 +1,92 @@
+// File: src\progress.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: Machine-readable progress records for front-ends (`--progress-fd`)
+// License: MIT
+
+use std::{
+    fs::{File, OpenOptions},
+    io::Write,
+    mem::ManuallyDrop,
+};
+
+use serde::Serialize;
+use serde_json::json;
+
+use crate::breakdown::Breakdown;
+
+// One JSON object per line, written to a numbered file descriptor or a named pipe
+pub struct Progress {
+    // The descriptor belongs to whoever started mks, so it is never closed here
+    out: ManuallyDrop<File>,
+}
+
+impl Progress {
+    // `--progress-fd 3` or `--progress-fd /tmp/mks.pipe`
+    pub fn open(target: &str) -> Result<Progress, Box<dyn std::error::Error>> {
+        let file = match target.parse::<i32>() {
+            Ok(fd) => from_fd(fd)?,
+            Err(_) => OpenOptions::new()
+                .write(true)
+                .open(target)
+                .map_err(|e| t!("--progress-fd '{}': {}", target, e))?,
+        };
+        Ok(Progress { out: ManuallyDrop::new(file) })
+    }
+
+    fn write(&self, record: serde_json::Value) {
+        // A front-end that went away must not stop the scaffold. One write
+        // per record, so records from `--jobs` threads do not interleave.
+        let _ = (&*self.out).write_all(format!("{}\n", record).as_bytes());
+    }
+
+    // About to handle `path`, the `done + 1`-th of `total` entries
+    pub fn entry(&self, done: usize, total: usize, path: &str) {
+        self.write(json!({
+            "event": "entry",
+            "done": done,
+            "total": total,
+            "percent": percent(done, total),
+            "path": path,
+        }));
+    }
+
+    // What happened to `path`: `{"event":"result","path":..,"outcome":"replaced"}`
+    pub fn outcome(&self, path: &str, outcome: &impl Serialize) {
+        let mut record = serde_json::to_value(outcome).unwrap_or_else(|_| json!({}));
+        record["event"] = json!("result");
+        record["path"] = json!(path);
+        self.write(record);
+    }
+
+    pub fn finish(&self, total: usize, created: usize, failed: usize, breakdown: &Breakdown) {
+        self.write(json!({
+            "event": "done",
+            "done": total,
+            "total": total,
+            "percent": 100,
+            "created": created,
+            "failed": failed,
+            "breakdown": breakdown,
+        }));
+    }
+}
+
+fn percent(done: usize, total: usize) -> usize {
+    (done * 100).checked_div(total).unwrap_or(100)
+}
+
+#[cfg(unix)]
+fn from_fd(fd: i32) -> Result<File, Box<dyn std::error::Error>> {
+    use std::os::unix::io::FromRawFd;
+    // Refuse descriptors that are not open rather than writing into the void
+    if unsafe { libc::fcntl(fd, libc::F_GETFD) } == -1 {
+        return Err(t!("--progress-fd {}: descriptor is not open", fd).into());
+    }
+    Ok(unsafe { File::from_raw_fd(fd) })
+}
+
+#[cfg(not(unix))]
+fn from_fd(fd: i32) -> Result<File, Box<dyn std::error::Error>> {
+    Err(t!("--progress-fd {}: numbered descriptors need a Unix system; pass a named pipe path instead", fd).into())
+}
@@ -0,0 +1,149 @@
+// File: src\project.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: Project-name inference and injection (`{{project}}`, manifests)
+// License: MIT
+
+use std::{
+    env,
+    io::{self, BufRead, IsTerminal, Write},
+    path::Path,
+};
+
+use crate::{NodeKind, PlannedEntry};
+
+pub const PLACEHOLDER: &str = "{{project}}";
+
+// Manifests at the project root that get seeded with the name
+const MANIFESTS: &[&str] = &["Cargo.toml", "package.json"];
+
+// The single top-level directory of a plan, if it has exactly one
+pub fn single_root(plan: &[PlannedEntry]) -> Option<String> {
+    let mut roots = plan.iter().filter(|e| e.depth == 0);
+    match (roots.next(), roots.next()) {
+        (Some(root), None) if root.kind == NodeKind::Dir => Some(root.path.clone()),
+        _ => None,
+    }
+}
+
+fn is_manifest(path: &str, root: &str) -> bool {
+    let name = path.strip_prefix(root).unwrap_or(path);
+    MANIFESTS.contains(&name)
+}
+
+fn manifest_contents(file: &str, project: &str) -> String {
+    if file.ends_with("package.json") {
+        let manifest = serde_json::json!({
+            "name": project.to_lowercase(),
+            "version": "0.1.0",
+            "private": true,
+        });
+        format!("{:#}\n", manifest)
+    } else {
+        format!(
+            "[package]\nname = \"{}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\n",
+            project
+        )
+    }
+}
+
+// Last component of the output directory, resolving `.` and `..`
+fn name_from_output(output: &str) -> Option<String> {
+    let path = Path::new(output);
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        env::current_dir().ok()?.join(path)
+    };
+    let mut parts: Vec<String> = Vec::new();
+    for part in absolute.components() {
+        match part {
+            std::path::Component::Normal(p) => parts.push(p.to_string_lossy().into_owned()),
+            std::path::Component::ParentDir => {
+                parts.pop();
+            }
+            _ => {}
+        }
+    }
+    parts.pop()
+}
+
+// Asked for on a terminal; piped runs take the current folder's name
+fn prompt_name() -> Result<String, Box<dyn std::error::Error>> {
+    if !io::stdin().is_terminal() {
+        return name_from_output(".").ok_or_else(|| t!("cannot infer the project name: pass -o <dir> or name the root folder").into());
+    }
+    print!("📝 {} ", t!("Project name:"));
+    io::stdout().flush()?;
+    let mut line = String::new();
+    io::stdin().lock().read_line(&mut line)?;
+    let name = line.trim();
+    if let Err(why) = crate::validate::portable(name) {
+        return Err(t!("invalid project name '{}': {}", name, why).into());
+    }
+    Ok(name.to_string())
+}
+
+// When the tree uses `{{project}}`, work out the project name (`--var
+// project=...`, else from `-o`, else by asking or from the current folder),
+// substitute it into every path and file contents and seed the empty root
+// manifests with it. With `-o`, a `{{project}}/` root is the output
+// directory itself, so it is dropped instead of nested.
+pub fn inject(plan: &mut Vec<PlannedEntry>, output: Option<&str>, given: Option<&str>) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    let root = single_root(plan);
+    let placeholder_root = root.as_deref() == Some(PLACEHOLDER);
+    let uses_placeholder = plan
+        .iter()
+        .any(|e| e.path.contains(PLACEHOLDER) || e.contents.as_deref().is_some_and(|c| c.contains(PLACEHOLDER)));
+
+    // A root folder with a real name already is the project name
+    let manifest_root = match &root {
+        Some(r) if placeholder_root => Some(format!("{}/", r)),
+        Some(_) => None,
+        None => Some(String::new()),
+    };
+    let seeds: Vec<usize> = match &manifest_root {
+        Some(dir) => plan
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.kind == NodeKind::File && e.contents.is_none() && is_manifest(&e.path, dir))
+            .map(|(i, _)| i)
+            .collect(),
+        None => Vec::new(),
+    };
+    // Trees that never mention the name are created as written
+    if !uses_placeholder {
+        return Ok(None);
+    }
+
+    let project = match given.map(String::from).or_else(|| output.and_then(name_from_output)) {
+        Some(name) => match crate::validate::portable(&name) {
+            Ok(()) => name,
+            Err(why) => return Err(t!("invalid project name '{}': {}", name, why).into()),
+        },
+        None => prompt_name()?,
+    };
+
+    for i in seeds {
+        plan[i].contents = Some(manifest_contents(&plan[i].path, &project));
+    }
+
+    if placeholder_root && output.is_some() {
+        plan.retain(|e| e.depth != 0);
+        let prefix = format!("{}/", PLACEHOLDER);
+        for entry in plan.iter_mut() {
+            if let Some(rest) = entry.path.strip_prefix(&prefix) {
+                entry.path = rest.to_string();
+            }
+            entry.depth -= 1;
+        }
+    }
+
+    for entry in plan.iter_mut() {
+        entry.path = entry.path.replace(PLACEHOLDER, &project);
+        if let Some(contents) = &mut entry.contents {
+            *contents = contents.replace(PLACEHOLDER, &project);
+        }
+    }
+    Ok(Some(project))
+}
@@ -0,0 +1,214 @@
+// File: src\prune.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: `mks prune-empty` - remove the empty folders that layout iterations leave behind
+// License: MIT
+
+use std::{collections::HashSet, fs, path::Path};
+
+use ignore::gitignore::Gitignore;
+
+use crate::{dump, escape, plan_entries, project::single_root, resolve_lines, state, NodeKind, ParseOptions};
+
+// Whether `dir` holds nothing but empty folders. Removable ones go to `out`
+// deepest first; kept ones to `kept`, and they make their parents non-empty.
+fn collect(
+    dir: &Path,
+    rel: &str,
+    ignore: &Gitignore,
+    keep: &HashSet<String>,
+    out: &mut Vec<String>,
+    kept: &mut Vec<String>,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let mut empty = true;
+    for item in fs::read_dir(dir)? {
+        let item = item?;
+        let name = escape::escape_os_name(&item.file_name());
+        let file_type = item.file_type()?;
+        let path = item.path();
+        if name == ".git" || state::is_state_name(&name) || ignore.matched_path_or_any_parents(&path, file_type.is_dir()).is_ignore() {
+            empty = false;
+            continue;
+        }
+        // Files, and symlinks even to empty folders, are content
+        if !file_type.is_dir() {
+            empty = false;
+            continue;
+        }
+        let rel = if rel.is_empty() { name } else { format!("{}/{}", rel, name) };
+        if !collect(&path, &rel, ignore, keep, out, kept)? {
+            empty = false;
+        } else if keep.contains(&rel) {
+            kept.push(rel);
+            empty = false;
+        } else {
+            out.push(rel);
+        }
+    }
+    Ok(empty)
+}
+
+// Folders a tree marks `[keep]` or `[protect]`, relative to the directory
+fn kept_folders(lines: &[String], popts: &ParseOptions) -> Result<HashSet<String>, Box<dyn std::error::Error>> {
+    let resolved = resolve_lines(lines, popts);
+    resolved.check()?;
+    let plan = plan_entries(resolved, false);
+    let tree_root = single_root(&plan);
+    Ok(plan
+        .iter()
+        .filter(|e| e.kind == NodeKind::Dir && (e.has_annotation("keep") || e.has_annotation("protect")))
+        .map(|e| match &tree_root {
+            Some(top) => e.path.strip_prefix(&format!("{}/", top)).unwrap_or(&e.path).to_string(),
+            None => e.path.clone(),
+        })
+        .collect())
+}
+
+// `mks prune-empty <dir> [tree-file] [--apply]`: list (or with `--apply`
+// remove) every folder below `dir` that holds nothing but empty folders.
+// `.mksignore` matches, `.git` and the state directory are never touched.
+pub fn run(dir: &str, lines: Option<&[String]>, popts: &ParseOptions, apply: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let root = Path::new(dir);
+    if !root.is_dir() {
+        return Err(t!("'{}' is not a directory", dir).into());
+    }
+    let keep = match lines {
+        Some(lines) => kept_folders(lines, popts)?,
+        None => HashSet::new(),
+    };
+    let (mut empty, mut kept) = (Vec::new(), Vec::new());
+    collect(root, "", &dump::load_ignore(root)?, &keep, &mut empty, &mut kept)?;
+
+    if empty.is_empty() && kept.is_empty() {
+        println!("✨ {}", t!("no empty folders in '{}'", root.display()));
+        return Ok(());
+    }
+    if !apply {
+        println!("🧪 {}\n", t!("Dry run: nothing is deleted"));
+    }
+    let mut removed = 0;
+    for rel in &empty {
+        if apply {
+            if let Err(e) = fs::remove_dir(escape::long_path(&root.join(escape::os_path(rel)))) {
+                eprintln!("❌ {}", t!("{}: {}", rel, e));
+                continue;
+            }
+        }
+        println!("🗑️ {}", rel);
+        removed += 1;
+    }
+    for rel in &kept {
+        println!("📌 {}", t!("{} kept: marked in the tree", rel));
+    }
+
+    println!();
+    if apply {
+        println!("📊 {}", t!("{} empty folder(s) removed, {} kept", removed, kept.len()));
+    } else {
+        println!("📊 {}", t!("{} empty folder(s) would be removed, {} kept", removed, kept.len()));
+        if removed > 0 {
+            println!("💡 {}", t!("Run again with --apply to remove them"));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    // A fresh folder holding `dirs` and `files`, both relative to it
+    fn scratch(name: &str, dirs: &[&str], files: &[&str]) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("mks-prune-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&root);
+        for dir in dirs {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        for file in files {
+            fs::create_dir_all(root.join(file).parent().unwrap()).unwrap();
+            fs::write(root.join(file), "").unwrap();
+        }
+        root
+    }
+
+    // (removable, kept) below `root`, with the folders `tree` marks
+    fn select(root: &Path, tree: Option<&str>) -> (Vec<String>, Vec<String>) {
+        let keep = match tree {
+            Some(text) => {
+                let lines: Vec<String> = text.lines().map(String::from).collect();
+                kept_folders(&lines, &ParseOptions::default()).unwrap()
+            }
+            None => HashSet::new(),
+        };
+        let (mut empty, mut kept) = (Vec::new(), Vec::new());
+        collect(root, "", &dump::load_ignore(root).unwrap(), &keep, &mut empty, &mut kept).unwrap();
+        empty.sort();
+        kept.sort();
+        (empty, kept)
+    }
+
+    #[test]
+    fn folders_of_empty_folders_go_and_anything_with_a_file_stays() {
+        let root = scratch("basic", &["a/b/c", "a/d", "src/empty", "src/lib"], &["src/lib/mod.rs"]);
+        let (empty, kept) = select(&root, None);
+        assert_eq!(empty, ["a", "a/b", "a/b/c", "a/d", "src/empty"]);
+        assert!(kept.is_empty());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn removal_order_is_deepest_first() {
+        let root = scratch("order", &["a/b/c"], &[]);
+        let (mut empty, mut kept) = (Vec::new(), Vec::new());
+        collect(&root, "", &dump::load_ignore(&root).unwrap(), &HashSet::new(), &mut empty, &mut kept).unwrap();
+        assert_eq!(empty, ["a/b/c", "a/b", "a"]);
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn git_state_and_ignored_folders_are_never_selected() {
+        let root = scratch("skipped", &[".git/refs", "build/out", "x/.git"], &[]);
+        fs::create_dir_all(root.join(state::DIR)).unwrap();
+        fs::write(root.join(dump::IGNORE_FILE), "build/\n").unwrap();
+        let (empty, kept) = select(&root, None);
+        // `x` holds a `.git`, which counts as content
+        assert!(empty.is_empty(), "{:?}", empty);
+        assert!(kept.is_empty());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn a_symlink_to_an_empty_folder_is_content() {
+        let root = scratch("symlink", &["target", "holder"], &[]);
+        std::os::unix::fs::symlink(root.join("target"), root.join("holder/link")).unwrap();
+        let (empty, _) = select(&root, None);
+        assert_eq!(empty, ["target"]);
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn marked_folders_stay_and_keep_their_parents() {
+        let root = scratch("marked", &["logs/old", "cache", "tmp/a"], &[]);
+        // A single-root tree names the folder itself; its paths are relative to it
+        let tree = "app/\n  logs/\n    old/ [keep]\n  cache/ [protect]\n  tmp/\n";
+        let (empty, kept) = select(&root, Some(tree));
+        assert_eq!(empty, ["tmp", "tmp/a"]);
+        assert_eq!(kept, ["cache", "logs/old"]);
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn apply_removes_what_a_dry_run_lists() {
+        let root = scratch("apply", &["a/b", "keep/me"], &["keep/me/file.txt"]);
+        let dir = root.to_str().unwrap();
+        run(dir, None, &ParseOptions::default(), false).unwrap();
+        assert!(root.join("a/b").is_dir());
+        run(dir, None, &ParseOptions::default(), true).unwrap();
+        assert!(!root.join("a").exists());
+        assert!(root.join("keep/me/file.txt").is_file());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,50 @@
+// File: src\rollback.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: Remove what a run created when part of it failed (`--rollback-on-error`)
+// License: MIT
+
+use std::{cmp::Reverse, fs};
+
+use crate::{escape, interactive, EntryOutcome, Summary};
+
+fn confirm(count: usize) -> bool {
+    interactive::yes(&format!("↩️ {}", t!("Remove the {} entries this run created? [y/N]", count)))
+}
+
+// After a partial failure, remove every entry the run created, relative to
+// the current directory: right away with `--rollback-on-error`, otherwise
+// when the user agrees at the prompt. Removed entries become `RolledBack`;
+// what could not be removed stays `Created` and is recorded as usual.
+pub fn after_failure(summary: &mut Summary, automatic: bool) {
+    let created = summary.created();
+    if created.is_empty() {
+        return;
+    }
+    if !automatic && !confirm(created.len()) {
+        eprintln!("💡 {}", t!("Left in place; `mks undo` removes it later, --rollback-on-error does it right away"));
+        return;
+    }
+
+    // Deepest first, so folders are empty by the time they are removed
+    let mut order = created;
+    order.sort_by_key(|path| Reverse(path.matches('/').count()));
+    let mut removed = 0;
+    for path in &order {
+        let target = escape::long_path(&escape::os_path(path));
+        let result = match fs::symlink_metadata(&target) {
+            Ok(meta) if meta.is_dir() => fs::remove_dir(&target),
+            Ok(_) => fs::remove_file(&target),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            eprintln!("❌ {}", t!("could not roll back {}: {}", path, e));
+            continue;
+        }
+        if let Some((_, outcome)) = summary.entries.iter_mut().find(|(p, _)| p == path) {
+            *outcome = EntryOutcome::RolledBack;
+        }
+        removed += 1;
+    }
+    status!("↩️ {}", t!("Rolled back {} of {} created entries", removed, order.len()));
+}
@@ -0,0 +1,126 @@
+// File: src\run_report.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: `--output-format json` - the result of a run as one JSON document on stdout
+// License: MIT
+
+use std::{collections::HashMap, time::Duration};
+
+use serde::Serialize;
+
+use crate::{breakdown::Breakdown, EntryOutcome, PlannedEntry, Summary};
+
+pub const RESULT_VERSION: &str = "1.0.0";
+
+// `--output-format text|json`; true for json
+pub fn wants_json(flag: Option<&str>) -> Result<bool, Box<dyn std::error::Error>> {
+    match flag {
+        None | Some("text") => Ok(false),
+        Some("json") => Ok(true),
+        Some(other) => Err(t!("--output-format expects text or json, got '{}'", other).into()),
+    }
+}
+
+#[derive(Serialize)]
+struct Problem<'a> {
+    path: &'a str,
+    reason: &'a str,
+}
+
+#[derive(Serialize)]
+struct Failure<'a> {
+    path: &'a str,
+    // 1-based input line
+    line: usize,
+    kind: &'a str,
+    reason: &'a str,
+}
+
+#[derive(Serialize)]
+struct Backup<'a> {
+    path: &'a str,
+    backup: &'a str,
+}
+
+// One target: a folder, or the zip of `--into-archive`
+#[derive(Serialize)]
+pub struct RunReport<'a> {
+    target: &'a str,
+    planned: usize,
+    created: Vec<&'a str>,
+    existed: Vec<&'a str>,
+    replaced: Vec<&'a str>,
+    backed_up: Vec<Backup<'a>>,
+    protected: Vec<&'a str>,
+    skipped: Vec<Problem<'a>>,
+    conflicts: Vec<Problem<'a>>,
+    failed: Vec<Failure<'a>>,
+    rolled_back: Vec<&'a str>,
+    // Entries not started because `--timeout` had passed
+    timed_out: usize,
+    breakdown: Option<&'a Breakdown>,
+    // Why nothing was attempted in this target at all
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl<'a> RunReport<'a> {
+    pub fn new(target: &'a str, plan: &[PlannedEntry], summary: &'a Summary) -> RunReport<'a> {
+        let lines: HashMap<&str, usize> = plan.iter().map(|entry| (entry.path.as_str(), entry.line + 1)).collect();
+        let mut report = RunReport::failed(target, summary.planned, None);
+        report.timed_out = summary.timed_out;
+        report.breakdown = Some(&summary.breakdown);
+        for (path, outcome) in &summary.entries {
+            let path = path.as_str();
+            match outcome {
+                EntryOutcome::Created => report.created.push(path),
+                EntryOutcome::Existed => report.existed.push(path),
+                EntryOutcome::Replaced => report.replaced.push(path),
+                EntryOutcome::BackedUp { backup } => report.backed_up.push(Backup { path, backup }),
+                EntryOutcome::Protected => report.protected.push(path),
+                EntryOutcome::Skipped { reason } => report.skipped.push(Problem { path, reason }),
+                EntryOutcome::Conflict { reason } => report.conflicts.push(Problem { path, reason }),
+                EntryOutcome::RolledBack => report.rolled_back.push(path),
+                EntryOutcome::Failed { kind, reason, .. } => {
+                    let line = lines.get(path).copied().unwrap_or(0);
+                    report.failed.push(Failure { path, line, kind, reason });
+                }
+            }
+        }
+        report
+    }
+
+    // A target that could not be used at all
+    pub fn failed(target: &'a str, planned: usize, error: Option<String>) -> RunReport<'a> {
+        RunReport {
+            target,
+            planned,
+            created: Vec::new(),
+            existed: Vec::new(),
+            replaced: Vec::new(),
+            backed_up: Vec::new(),
+            protected: Vec::new(),
+            skipped: Vec::new(),
+            conflicts: Vec::new(),
+            failed: Vec::new(),
+            rolled_back: Vec::new(),
+            timed_out: 0,
+            breakdown: None,
+            error,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Document<'a> {
+    schema_version: &'static str,
+    elapsed_ms: u128,
+    targets: Vec<RunReport<'a>>,
+}
+
+// Print the document; everything else the run said went to stderr
+pub fn print(targets: Vec<RunReport>, elapsed: Duration) -> Result<(), Box<dyn std::error::Error>> {
+    let document = Document { schema_version: RESULT_VERSION, elapsed_ms: elapsed.as_millis(), targets };
+    println!("{}", serde_json::to_string_pretty(&document)?);
+    Ok(())
+}
@@ -0,0 +1,145 @@
+// File: src\script.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: `--emit-script` - render the plan as a POSIX sh or PowerShell script
+// License: MIT
+
+use crate::{escape, NodeKind, PlannedEntry};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shell {
+    Sh,
+    PowerShell,
+}
+
+// `--emit-script` uses the host's shell, `--emit-script=sh|powershell` picks one
+// (given bare, the value is empty)
+pub fn requested_shell(value: Option<&str>) -> Result<Option<Shell>, Box<dyn std::error::Error>> {
+    match value {
+        None => Ok(None),
+        Some("") => Ok(Some(if cfg!(windows) { Shell::PowerShell } else { Shell::Sh })),
+        Some("sh") => Ok(Some(Shell::Sh)),
+        Some("powershell" | "pwsh") => Ok(Some(Shell::PowerShell)),
+        Some(other) => Err(t!("--emit-script expects 'sh' or 'powershell', got '{}'", other).into()),
+    }
+}
+
+fn sh_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', r"'\''"))
+}
+
+// Paths with `\xHH` byte escapes use `$'...'`, which decodes them the same way
+fn sh_path(path: &str) -> String {
+    if !escape::has_raw_bytes(path) {
+        return sh_quote(path);
+    }
+    let mut body = String::with_capacity(path.len());
+    for (i, c) in path.char_indices() {
+        match c {
+            '\\' if escape::starts_byte_escape(&path[i..]) => body.push(c),
+            '\\' | '\'' => {
+                body.push('\\');
+                body.push(c);
+            }
+            c => body.push(c),
+        }
+    }
+    format!("$'{}'", body)
+}
+
+fn ps_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
+
+fn emit_sh(plan: &[PlannedEntry], protected: &[bool], output: Option<&str>) -> String {
+    let mut out = vec!["#!/bin/sh".to_string(), "# Generated by mks --emit-script".to_string(), "set -e".to_string()];
+    if let Some(dir) = output {
+        out.push(format!("mkdir -p {0} && cd {0}", sh_quote(dir)));
+    }
+    // Modes go last and deepest first, so a read-only folder cannot block
+    // what goes into it
+    let mut modes = Vec::new();
+    for (entry, &protected) in plan.iter().zip(protected) {
+        let path = sh_path(&entry.path);
+        if let (Some(mode), false) = (entry.mode(), entry.kind == NodeKind::Symlink) {
+            modes.push(format!("chmod {:o} {}", mode, path));
+        }
+        let line = match entry.kind {
+            NodeKind::Dir => format!("mkdir -p {}", path),
+            NodeKind::File => {
+                let write = match (entry.annotation_value("src"), &entry.contents) {
+                    (Some(url), _) => format!("curl -fsSL -o {} {}", path, sh_quote(url)),
+                    (None, Some(contents)) => format!("printf '%s' {} > {}", sh_quote(contents), path),
+                    (None, None) => format!(": > {}", path),
+                };
+                let write = if protected { format!("[ -e {} ] || {}", path, write) } else { write };
+                if entry.executable {
+                    format!("{}\nchmod +x {}", write, path)
+                } else {
+                    write
+                }
+            }
+            NodeKind::Fifo => format!("[ -p {0} ] || mkfifo {0}", path),
+            NodeKind::Symlink if entry.annotation_value("target").is_some() => {
+                let target = entry.annotation_value("target").unwrap_or_default().trim_end_matches('/');
+                format!("[ -L {0} ] || ln -s {1} {0}", path, sh_quote(target))
+            }
+            other => format!("# skipped {} ({})", path, other.label()),
+        };
+        out.push(line);
+    }
+    out.extend(modes.into_iter().rev());
+    out.join("\n") + "\n"
+}
+
+fn emit_powershell(plan: &[PlannedEntry], protected: &[bool], output: Option<&str>) -> String {
+    let mut out = vec![
+        "# Generated by mks --emit-script=powershell".to_string(),
+        "$ErrorActionPreference = 'Stop'".to_string(),
+    ];
+    if let Some(dir) = output {
+        out.push(format!("New-Item -ItemType Directory -Force -Path {} | Out-Null", ps_quote(dir)));
+        out.push(format!("Set-Location -LiteralPath {}", ps_quote(dir)));
+    }
+    for (entry, &protected) in plan.iter().zip(protected) {
+        let path = ps_quote(&entry.path);
+        let line = match entry.kind {
+            NodeKind::Dir => format!("New-Item -ItemType Directory -Force -Path {} | Out-Null", path),
+            NodeKind::File => {
+                let write = match (entry.annotation_value("src"), &entry.contents) {
+                    (Some(url), _) => format!("Invoke-WebRequest -Uri {} -OutFile {}", ps_quote(url), path),
+                    (None, Some(contents)) => format!(
+                        "Set-Content -LiteralPath {} -Value {} -NoNewline -Encoding UTF8",
+                        path,
+                        ps_quote(contents)
+                    ),
+                    (None, None) => format!("New-Item -ItemType File -Force -Path {} | Out-Null", path),
+                };
+                if protected {
+                    format!("if (-not (Test-Path -LiteralPath {})) {{ {} }}", path, write)
+                } else {
+                    write
+                }
+            }
+            NodeKind::Symlink if entry.annotation_value("target").is_some() => {
+                let target = entry.annotation_value("target").unwrap_or_default().trim_end_matches('/');
+                format!(
+                    "if (-not (Test-Path -LiteralPath {0})) {{ New-Item -ItemType SymbolicLink -Path {0} -Target {1} | Out-Null }}",
+                    path,
+                    ps_quote(target)
+                )
+            }
+            other => format!("# skipped {} ({})", path, other.label()),
+        };
+        out.push(line);
+    }
+    out.join("\r\n") + "\r\n"
+}
+
+// `protected[i]` tells whether an existing file at `plan[i]` must be kept
+pub fn emit(plan: &[PlannedEntry], protected: &[bool], output: Option<&str>, shell: Shell) -> String {
+    match shell {
+        Shell::Sh => emit_sh(plan, protected, output),
+        Shell::PowerShell => emit_powershell(plan, protected, output),
+    }
+}
@@ -0,0 +1,213 @@
+// File: src\state.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: The `.mks/` state directory at a target root, its lock and `mks clean`
+// License: MIT
+
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+
+use chrono::Local;
+use serde::Serialize;
+
+use crate::{manifest, plan_file, sync_dir, PlannedEntry, Summary};
+
+// Layout, relative to the target root:
+//
+//   .mks/
+//   ├── .gitignore      `*`, so the directory never ends up in a commit
+//   ├── lock            held while state is read and rewritten
+//   ├── manifest.json   what runs created, for `mks undo`
+//   ├── history.jsonl   one line per run
+//   └── trees/          the plan each run applied, replayable with `mks apply`
+pub const DIR: &str = ".mks";
+pub const MANIFEST: &str = "manifest.json";
+const LOCK: &str = "lock";
+const HISTORY: &str = "history.jsonl";
+const TREES: &str = "trees";
+// Where the manifest lived before `.mks/`; read once and moved
+pub const LEGACY_MANIFEST: &str = ".mks-manifest.json";
+
+// How long to wait for another run, and when a lock is assumed abandoned
+const LOCK_WAIT: Duration = Duration::from_secs(10);
+const LOCK_STALE: Duration = Duration::from_secs(600);
+
+// Names `mks dump` and `mks stats` leave out
+pub fn is_state_name(name: &str) -> bool {
+    name == DIR || name == LEGACY_MANIFEST
+}
+
+pub fn path(root: &Path, name: &str) -> PathBuf {
+    root.join(DIR).join(name)
+}
+
+// Create `.mks/` with its `.gitignore` if needed
+fn ensure(root: &Path) -> io::Result<PathBuf> {
+    let dir = root.join(DIR);
+    fs::create_dir_all(&dir)?;
+    let ignore = dir.join(".gitignore");
+    if !ignore.exists() {
+        fs::write(ignore, "*\n")?;
+    }
+    Ok(dir)
+}
+
+// Replace `path` in one step, so a reader never sees half a file. With
+// `durable` (`--durable`) the new contents reach the disk before the rename,
+// and the rename itself is synced, so a crash leaves the old or the new file.
+pub fn write_atomic(path: &Path, contents: &[u8], durable: bool) -> io::Result<()> {
+    let parent = path.parent().unwrap_or(Path::new(""));
+    fs::create_dir_all(parent)?;
+    let temp = path.with_extension(format!("tmp-{}", std::process::id()));
+    let mut file = fs::File::create(&temp)?;
+    file.write_all(contents)?;
+    if durable {
+        file.sync_all()?;
+    }
+    drop(file);
+    fs::rename(&temp, path)?;
+    if durable {
+        sync_dir(if parent.as_os_str().is_empty() { Path::new(".") } else { parent })?;
+    }
+    Ok(())
+}
+
+// Exclusive access to a root's state; released when dropped
+pub struct Lock {
+    path: PathBuf,
+}
+
+impl Drop for Lock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(unix)]
+fn process_alive(pid: u32) -> bool {
+    let Ok(pid) = libc::pid_t::try_from(pid) else {
+        return false;
+    };
+    // Signal 0 only checks; EPERM still means the process exists
+    let found = unsafe { libc::kill(pid, 0) } == 0;
+    found || io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+#[cfg(not(unix))]
+fn process_alive(_pid: u32) -> bool {
+    true
+}
+
+// Left behind by a run that no longer exists, or older than any run takes
+fn is_stale(path: &Path, holder: Option<u32>) -> bool {
+    let old = fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|time| SystemTime::now().duration_since(time).ok())
+        .is_some_and(|age| age > LOCK_STALE);
+    old || holder.is_some_and(|pid| !process_alive(pid))
+}
+
+fn acquire(root: &Path, wait: Duration) -> Result<Lock, Box<dyn std::error::Error>> {
+    let path = ensure(root)?.join(LOCK);
+    let deadline = Instant::now() + wait;
+    loop {
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(mut file) => {
+                writeln!(file, "{}", std::process::id())?;
+                return Ok(Lock { path });
+            }
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                let holder = fs::read_to_string(&path).ok().and_then(|text| text.trim().parse::<u32>().ok());
+                if is_stale(&path, holder) {
+                    let _ = fs::remove_file(&path);
+                    continue;
+                }
+                if Instant::now() >= deadline {
+                    let holder = holder.map_or_else(|| "?".to_string(), |pid| pid.to_string());
+                    return Err(t!("'{}' is held by another mks run (pid {})", path.display(), holder).into());
+                }
+                thread::sleep(Duration::from_millis(50));
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
+
+// Wait for other runs on the same root, then hold its state
+pub fn lock(root: &Path) -> Result<Lock, Box<dyn std::error::Error>> {
+    acquire(root, LOCK_WAIT)
+}
+
+#[derive(Serialize)]
+struct HistoryRecord {
+    time: String,
+    planned: usize,
+    created: usize,
+    failed: usize,
+    // Saved plan, relative to `.mks/`
+    tree: String,
+}
+
+// Record a run in the current directory: the manifest for `mks undo`, the
+// plan it applied and a history line. Runs that created nothing leave no trace.
+// With `durable` all three are on disk before the run reports success.
+pub fn record_run(plan: &[PlannedEntry], summary: &Summary, durable: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let created = summary.created();
+    if created.is_empty() {
+        return Ok(());
+    }
+    let root = Path::new(".");
+    let _lock = lock(root)?;
+    manifest::record(root, plan, &created, durable)?;
+
+    let now = Local::now();
+    let tree = format!("{}/{}-{}.json", TREES, now.format("%Y%m%dT%H%M%S%.3f"), std::process::id());
+    write_atomic(&path(root, &tree), plan_file::to_json(plan)?.as_bytes(), durable)?;
+
+    let record = HistoryRecord {
+        time: now.to_rfc3339(),
+        planned: summary.planned,
+        created: created.len(),
+        failed: summary.failed(),
+        tree,
+    };
+    let mut history = OpenOptions::new().create(true).append(true).open(path(root, HISTORY))?;
+    writeln!(history, "{}", serde_json::to_string(&record)?)?;
+    if durable {
+        // `.mks/`, `trees/` and `history.jsonl` may all be new
+        history.sync_all()?;
+        sync_dir(&path(root, TREES))?;
+        sync_dir(&root.join(DIR))?;
+        sync_dir(root)?;
+    }
+    Ok(())
+}
+
+// `mks clean [dir]`: remove the state directory (and a legacy manifest).
+// Refuses while another run holds the lock.
+pub fn clean(root: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let root = Path::new(root);
+    let dir = root.join(DIR);
+    let legacy = root.join(LEGACY_MANIFEST);
+    if !dir.exists() && !legacy.exists() {
+        println!("✨ {}", t!("nothing to clean in '{}'", root.display()));
+        return Ok(());
+    }
+    if dir.exists() {
+        let lock = acquire(root, Duration::ZERO)?;
+        fs::remove_dir_all(&dir)?;
+        // The lock file went with the directory
+        std::mem::forget(lock);
+    }
+    if legacy.exists() {
+        fs::remove_file(&legacy)?;
+    }
+    println!("🧹 {}", t!("removed {} from '{}'", DIR, root.display()));
+    Ok(())
+}
@@ -0,0 +1,158 @@
+// File: src\stats.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: Compare disk usage with the sizes annotated in an exported tree
+// License: MIT
+
+use std::{collections::BTreeMap, fs, path::Path};
+
+use ignore::gitignore::Gitignore;
+
+use crate::{dump, escape, plan_entries, project::single_root, resolve_lines, state, NodeKind, ParseOptions};
+
+// `4096`, `4.0K` (`tree -h`, powers of 1024) or `4.1k` (`--si`, powers of 1000)
+pub fn parse_size(token: &str, si: bool) -> Option<u64> {
+    let token = token.trim_end_matches(['B', 'b']);
+    let (digits, unit) = token.split_at(token.find(|c: char| c.is_ascii_alphabetic()).unwrap_or(token.len()));
+    let exponent = match unit.to_ascii_uppercase().as_str() {
+        "" => 0,
+        "K" => 1,
+        "M" => 2,
+        "G" => 3,
+        "T" => 4,
+        "P" => 5,
+        "E" => 6,
+        _ => return None,
+    };
+    let base: f64 = if si { 1000.0 } else { 1024.0 };
+    let value: f64 = digits.parse().ok()?;
+    Some((value * base.powi(exponent)).round() as u64)
+}
+
+// Size of every regular file under `dir`, keyed by its path relative to the
+// root. Skips what `mks dump` skips, so an export compares with itself.
+fn disk_sizes(dir: &Path, rel: &str, ignore: &Gitignore, out: &mut BTreeMap<String, u64>) -> Result<(), Box<dyn std::error::Error>> {
+    for item in fs::read_dir(dir)? {
+        let item = item?;
+        let name = escape::escape_os_name(&item.file_name());
+        if name == ".git" || state::is_state_name(&name) {
+            continue;
+        }
+        let file_type = item.file_type()?;
+        let path = item.path();
+        if ignore.matched_path_or_any_parents(&path, file_type.is_dir()).is_ignore() {
+            continue;
+        }
+        let rel = if rel.is_empty() { name } else { format!("{}/{}", rel, name) };
+        if file_type.is_dir() {
+            disk_sizes(&path, &rel, ignore, out)?;
+        } else if file_type.is_file() {
+            out.insert(rel, item.metadata()?.len());
+        }
+    }
+    Ok(())
+}
+
+// Sizes of the files below `dir` ("" is the root)
+fn files_under<'a>(sizes: &'a BTreeMap<String, u64>, dir: &'a str) -> impl Iterator<Item = u64> + 'a {
+    sizes
+        .iter()
+        .filter(move |(path, _)| dir.is_empty() || path.strip_prefix(dir).is_some_and(|rest| rest.starts_with('/')))
+        .map(|(_, size)| *size)
+}
+
+fn human(bytes: u64) -> String {
+    dump::format_size(bytes, dump::SizeStyle::Binary)
+}
+
+fn delta(budget: u64, now: u64) -> String {
+    let (sign, diff) = if now >= budget { ('+', now - budget) } else { ('-', budget - now) };
+    if diff == 0 {
+        return "±0".to_string();
+    }
+    match budget {
+        0 => format!("{}{}", sign, human(diff)),
+        _ => format!("{}{} ({}{:.1}%)", sign, human(diff), sign, diff as f64 * 100.0 / budget as f64),
+    }
+}
+
+// `mks stats <dir> [tree]`: for every folder of the tree, the annotated size
+// of the files below it against what is on disk now. A single root folder in
+// the tree stands for `dir` itself, as in the output of `mks dump`.
+pub fn run(lines: &[String], dir: &str, popts: &ParseOptions) -> Result<(), Box<dyn std::error::Error>> {
+    let resolved = resolve_lines(lines, popts);
+    resolved.check()?;
+    let plan = plan_entries(resolved, false);
+
+    let root = Path::new(dir);
+    if !root.is_dir() {
+        return Err(t!("'{}' is not a directory", dir).into());
+    }
+    let mut on_disk = BTreeMap::new();
+    disk_sizes(root, "", &dump::load_ignore(root)?, &mut on_disk)?;
+
+    // Disk-relative path for a planned one
+    let tree_root = single_root(&plan);
+    let relative = |path: &str| -> String {
+        match &tree_root {
+            Some(top) if path == top => String::new(),
+            Some(top) => path.strip_prefix(&format!("{}/", top)).unwrap_or(path).to_string(),
+            None => path.to_string(),
+        }
+    };
+
+    // `k` only appears in `--si` listings, where `M` and up are powers of 1000 too
+    let si = plan.iter().filter_map(|e| e.attrs.as_ref()?.size.as_deref()).any(|size| size.ends_with('k'));
+    let mut annotated = BTreeMap::new();
+    let mut missing = 0;
+    for entry in plan.iter().filter(|e| e.kind == NodeKind::File) {
+        let Some(size) = entry.attrs.as_ref().and_then(|a| a.size.as_deref()).and_then(|s| parse_size(s, si)) else {
+            continue;
+        };
+        let rel = relative(&entry.path);
+        if !on_disk.contains_key(&rel) {
+            missing += 1;
+        }
+        annotated.insert(rel, size);
+    }
+    if annotated.is_empty() {
+        return Err(t!("the tree has no file sizes to compare with (export it with 'mks dump --size')").into());
+    }
+
+    let mut rows: Vec<(String, u64, u64)> = Vec::new();
+    for entry in plan.iter().filter(|e| e.kind == NodeKind::Dir) {
+        let rel = relative(&entry.path);
+        // Folders without any sized file have no budget to compare with
+        if files_under(&annotated, &rel).next().is_none() {
+            continue;
+        }
+        let budget = files_under(&annotated, &rel).sum();
+        rows.push((format!("{}/", entry.path), budget, files_under(&on_disk, &rel).sum()));
+    }
+    if tree_root.is_none() {
+        rows.insert(0, ("./".to_string(), files_under(&annotated, "").sum(), files_under(&on_disk, "").sum()));
+    }
+
+    println!("📏 {}", t!("Disk usage of '{}' compared with the tree:", dir));
+    let width = rows.iter().map(|(path, ..)| path.chars().count()).max().unwrap_or(0);
+    for (path, budget, now) in &rows {
+        let icon = match now.cmp(budget) {
+            std::cmp::Ordering::Greater => "📈",
+            std::cmp::Ordering::Less => "📉",
+            std::cmp::Ordering::Equal => "➖",
+        };
+        println!(
+            "   {} {:<width$}  {:>6} → {:<6}  {}",
+            icon,
+            path,
+            human(*budget),
+            human(*now),
+            delta(*budget, *now),
+            width = width
+        );
+    }
+    if missing > 0 {
+        println!("⚠️ {}", t!("{} file(s) from the tree are no longer on disk", missing));
+    }
+    Ok(())
+}
@@ -0,0 +1,240 @@
+// File: src\structured.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: JSON and YAML trees (`--format json|yaml`) as nested objects and arrays
+// License: MIT
+
+use std::{fmt, path::Path};
+
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+
+use crate::{validate, NodeKind, PlannedEntry};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Yaml,
+}
+
+impl Format {
+    pub fn label(self) -> &'static str {
+        match self {
+            Format::Text => "text",
+            Format::Json => "json",
+            Format::Yaml => "yaml",
+        }
+    }
+}
+
+// `--format`, else the file extension; clipboard input is text unless told
+pub fn format_for(flag: Option<&str>, file: Option<&str>) -> Result<Format, Box<dyn std::error::Error>> {
+    match flag {
+        Some("text") => return Ok(Format::Text),
+        Some("json") => return Ok(Format::Json),
+        Some("yaml" | "yml") => return Ok(Format::Yaml),
+        Some(other) => return Err(t!("--format expects text, json or yaml, got '{}'", other).into()),
+        None => {}
+    }
+    let extension = file.and_then(|f| Path::new(f).extension()).map(|e| e.to_string_lossy().to_lowercase());
+    Ok(match extension.as_deref() {
+        Some("json") => Format::Json,
+        Some("yaml" | "yml") => Format::Yaml,
+        _ => Format::Text,
+    })
+}
+
+// A JSON/YAML value that keeps object keys in document order, which is the
+// order entries are created in
+enum Value {
+    Null,
+    Scalar(String),
+    String(String),
+    List(Vec<Value>),
+    Map(Vec<(String, Value)>),
+}
+
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Value, D::Error> {
+        struct ValueVisitor;
+
+        impl<'de> Visitor<'de> for ValueVisitor {
+            type Value = Value;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a JSON or YAML value")
+            }
+
+            fn visit_unit<E: de::Error>(self) -> Result<Value, E> {
+                Ok(Value::Null)
+            }
+
+            fn visit_none<E: de::Error>(self) -> Result<Value, E> {
+                Ok(Value::Null)
+            }
+
+            fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+                Value::deserialize(deserializer)
+            }
+
+            fn visit_bool<E: de::Error>(self, v: bool) -> Result<Value, E> {
+                Ok(Value::Scalar(v.to_string()))
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Value, E> {
+                Ok(Value::Scalar(v.to_string()))
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Value, E> {
+                Ok(Value::Scalar(v.to_string()))
+            }
+
+            fn visit_f64<E: de::Error>(self, v: f64) -> Result<Value, E> {
+                Ok(Value::Scalar(v.to_string()))
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Value, E> {
+                Ok(Value::String(v.to_string()))
+            }
+
+            fn visit_string<E: de::Error>(self, v: String) -> Result<Value, E> {
+                Ok(Value::String(v))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+                let mut items = Vec::new();
+                while let Some(item) = seq.next_element()? {
+                    items.push(item);
+                }
+                Ok(Value::List(items))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Value, A::Error> {
+                let mut entries = Vec::new();
+                while let Some((key, value)) = map.next_entry::<Value, Value>()? {
+                    let key = match key {
+                        Value::String(key) | Value::Scalar(key) => key,
+                        _ => return Err(de::Error::custom(t!("object keys must be names"))),
+                    };
+                    entries.push((key, value));
+                }
+                Ok(Value::Map(entries))
+            }
+        }
+
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+impl Value {
+    fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Map(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    // `{"type": "directory", "name": "src", "contents": [...]}`, as printed
+    // by `tree -J`, rather than `{"src": {...}}`
+    fn is_node(&self) -> bool {
+        self.get("name").and_then(Value::as_str).is_some() && (self.get("type").is_some() || self.get("children").is_some())
+    }
+}
+
+struct Builder {
+    plan: Vec<PlannedEntry>,
+}
+
+impl Builder {
+    fn push(&mut self, parent: &str, name: &str, kind: NodeKind, contents: Option<String>) -> Result<String, Box<dyn std::error::Error>> {
+        let name = name.trim().trim_end_matches('/');
+        if let Err(why) = validate::portable(name) {
+            return Err(t!("invalid name '{}' below '{}': {}", name, if parent.is_empty() { "/" } else { parent }, why).into());
+        }
+        let path = if parent.is_empty() { name.to_string() } else { format!("{}/{}", parent, name) };
+        self.plan.push(PlannedEntry {
+            line: self.plan.len(),
+            path: path.clone(),
+            depth: path.matches('/').count(),
+            kind,
+            executable: false,
+            attrs: None,
+            annotations: Vec::new(),
+            contents,
+        });
+        Ok(path)
+    }
+
+    // `"name": value` in an object: objects and arrays are folders, a string
+    // is a file with that text, null is an empty file (a folder if the name
+    // ends with '/')
+    fn named(&mut self, parent: &str, name: &str, value: &Value) -> Result<(), Box<dyn std::error::Error>> {
+        match value {
+            Value::Map(_) | Value::List(_) => {
+                let path = self.push(parent, name, NodeKind::Dir, None)?;
+                self.children(&path, value)
+            }
+            Value::String(text) => self.push(parent, name, NodeKind::File, Some(text.clone())).map(|_| ()),
+            Value::Null if name.ends_with('/') => self.push(parent, name, NodeKind::Dir, None).map(|_| ()),
+            Value::Null => self.push(parent, name, NodeKind::File, None).map(|_| ()),
+            Value::Scalar(v) => Err(t!("'{}': expected an object, array, string or null, got {}", name, v).into()),
+        }
+    }
+
+    fn node(&mut self, parent: &str, node: &Value) -> Result<(), Box<dyn std::error::Error>> {
+        let name = node.get("name").and_then(Value::as_str).unwrap_or_default();
+        let children = node.get("children").or_else(|| node.get("contents").filter(|c| matches!(c, Value::List(_))));
+        let kind = match node.get("type").and_then(Value::as_str) {
+            Some("directory" | "dir") => NodeKind::Dir,
+            Some("file") => NodeKind::File,
+            Some("link") => NodeKind::Symlink,
+            Some(other) => return Err(t!("'{}': unknown type '{}'", name, other).into()),
+            None if children.is_some() => NodeKind::Dir,
+            None => NodeKind::File,
+        };
+        let contents = ["content", "contents"].iter().find_map(|key| node.get(key).and_then(Value::as_str)).map(String::from);
+        let path = self.push(parent, name, kind, contents)?;
+        match children {
+            Some(children) if kind == NodeKind::Dir => self.children(&path, children),
+            _ => Ok(()),
+        }
+    }
+
+    fn children(&mut self, parent: &str, value: &Value) -> Result<(), Box<dyn std::error::Error>> {
+        match value {
+            Value::Map(_) if value.is_node() => self.node(parent, value),
+            Value::Map(entries) => entries.iter().try_for_each(|(name, child)| self.named(parent, name, child)),
+            Value::List(items) => items.iter().try_for_each(|item| match item {
+                // `tree -J` ends with a summary record
+                Value::Map(_) if item.get("type").and_then(Value::as_str) == Some("report") => Ok(()),
+                Value::Map(_) => self.children(parent, item),
+                Value::String(name) if name.ends_with('/') => self.push(parent, name, NodeKind::Dir, None).map(|_| ()),
+                Value::String(name) => self.push(parent, name, NodeKind::File, None).map(|_| ()),
+                Value::Null => Ok(()),
+                Value::Scalar(v) => Err(t!("expected a name or an object in a list, got {}", v).into()),
+                Value::List(_) => Err(t!("expected a name or an object in a list, got a nested list").into()),
+            }),
+            Value::Null => Ok(()),
+            Value::String(_) | Value::Scalar(_) => Err(t!("a tree must be an object or an array").into()),
+        }
+    }
+}
+
+// The plan for a JSON or YAML tree, in document order
+pub fn load(text: &str, format: Format) -> Result<Vec<PlannedEntry>, Box<dyn std::error::Error>> {
+    let value: Value = if format == Format::Yaml {
+        serde_yaml_ng::from_str(text).map_err(|e| t!("invalid YAML: {}", e))?
+    } else {
+        serde_json::from_str(text).map_err(|e| t!("invalid JSON: {}", e))?
+    };
+    let mut builder = Builder { plan: Vec::new() };
+    builder.children("", &value)?;
+    Ok(builder.plan)
+}
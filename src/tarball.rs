@@ -0,0 +1,88 @@
+// File: src\tarball.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: The ustar format, just enough to write folders, files and links for `--to-tar`
+// License: MIT
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const BLOCK: usize = 512;
+
+pub enum Member<'a> {
+    Dir,
+    File(&'a [u8]),
+    Link(&'a str),
+}
+
+// `{:0width$o}` and a NUL, filling a field of `width + 1` bytes
+fn octal(field: &mut [u8], value: u64) {
+    let width = field.len() - 1;
+    field[..width].copy_from_slice(format!("{:0width$o}", value).as_bytes());
+}
+
+// A path longer than 100 bytes goes into the 155-byte prefix field up to one of its slashes
+fn split_name(name: &str) -> Option<(&str, &str)> {
+    if name.len() <= 100 {
+        return Some(("", name));
+    }
+    name.match_indices('/')
+        .map(|(i, _)| (&name[..i], &name[i + 1..]))
+        .find(|(prefix, rest)| prefix.len() <= 155 && rest.len() <= 100 && !rest.is_empty())
+}
+
+pub struct Writer {
+    out: Vec<u8>,
+    mtime: u64,
+}
+
+// Every member is stamped with the time the writer was made
+impl Default for Writer {
+    fn default() -> Writer {
+        let mtime = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        Writer { out: Vec::new(), mtime }
+    }
+}
+
+impl Writer {
+    pub fn add(&mut self, path: &str, member: Member, mode: u32) -> Result<(), Box<dyn std::error::Error>> {
+        let name = match member {
+            Member::Dir => format!("{}/", path),
+            _ => path.to_string(),
+        };
+        let (prefix, name) = split_name(&name).ok_or_else(|| t!("'{}' is too long for a tar archive", path))?;
+        let (typeflag, body, link): (u8, &[u8], &str) = match member {
+            Member::Dir => (b'5', &[], ""),
+            Member::File(bytes) => (b'0', bytes, ""),
+            Member::Link(target) if target.len() <= 100 => (b'2', &[], target),
+            Member::Link(_) => return Err(t!("the link target of '{}' is too long for a tar archive", path).into()),
+        };
+
+        let mut header = [0u8; BLOCK];
+        header[..name.len()].copy_from_slice(name.as_bytes());
+        octal(&mut header[100..108], u64::from(mode & 0o7777));
+        octal(&mut header[108..116], 0);
+        octal(&mut header[116..124], 0);
+        octal(&mut header[124..136], body.len() as u64);
+        octal(&mut header[136..148], self.mtime);
+        header[156] = typeflag;
+        header[157..157 + link.len()].copy_from_slice(link.as_bytes());
+        header[257..263].copy_from_slice(b"ustar\0");
+        header[263..265].copy_from_slice(b"00");
+        header[345..345 + prefix.len()].copy_from_slice(prefix.as_bytes());
+        // The checksum is taken with its own field read as spaces
+        header[148..156].fill(b' ');
+        let sum: u32 = header.iter().map(|&b| u32::from(b)).sum();
+        header[148..155].copy_from_slice(format!("{:06o}\0", sum).as_bytes());
+
+        self.out.extend_from_slice(&header);
+        self.out.extend_from_slice(body);
+        self.out.resize(self.out.len().div_ceil(BLOCK) * BLOCK, 0);
+        Ok(())
+    }
+
+    // The archive, closed by its two zero blocks
+    pub fn finish(mut self) -> Vec<u8> {
+        self.out.resize(self.out.len() + 2 * BLOCK, 0);
+        self.out
+    }
+}
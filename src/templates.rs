@@ -0,0 +1,96 @@
+// File: src\templates.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: `mks template save|list|delete` - the named presets in the template store
+// License: MIT
+
+use std::{fs, path::PathBuf};
+
+use crate::{
+    presets::{self, split_front_matter},
+    is_valid_structure, resolve_lines, ParseOptions,
+};
+
+// Names become file names and must not look like a path to `--preset`
+// (no '/' and no extension), so they stay plain: `rust-cli`, `web_v2`
+fn store_path(name: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
+        return Err(t!("'{}' is not a template name; use letters, digits, '-' and '_'", name).into());
+    }
+    presets::locate(name, None)
+}
+
+// Store `content` as template `name`. The tree (and its `+++` header) must
+// parse; an existing template is only replaced with `force`.
+pub fn save(name: &str, content: &str, force: bool, popts: &ParseOptions) -> Result<(), Box<dyn std::error::Error>> {
+    let path = store_path(name)?;
+    if path.exists() && !force {
+        return Err(t!("template '{}' already exists; --force replaces it", name).into());
+    }
+    let (_, lines) = split_front_matter(content)?;
+    if !is_valid_structure(&lines, popts) {
+        return Err(t!("the tree for '{}' has no entries", name).into());
+    }
+    resolve_lines(&lines, popts).check()?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut content = content.to_string();
+    if !content.ends_with('\n') {
+        content.push('\n');
+    }
+    fs::write(&path, content).map_err(|e| t!("could not write '{}': {}", path.display(), e))?;
+    println!("💾 {}", t!("Saved template '{}' to {}", name, path.display()));
+    println!("💡 {}", t!("Apply it with `mks template apply {} -C <dir>`", name));
+    Ok(())
+}
+
+pub fn list() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = presets::templates_dir().ok_or(t!("cannot locate the config directory (HOME is not set)"))?;
+    let mut found: Vec<(String, String)> = match fs::read_dir(&dir) {
+        Ok(entries) => entries
+            .filter_map(Result::ok)
+            .filter_map(|entry| {
+                let path = entry.path();
+                let name = path.file_name()?.to_str()?.strip_suffix(".txt")?.to_string();
+                let content = fs::read_to_string(&path).ok()?;
+                let detail = match split_front_matter(&content) {
+                    Ok((front, lines)) => {
+                        let count = lines.iter().filter(|line| !line.trim().is_empty()).count();
+                        match front.extends {
+                            Some(parent) => t!("{} lines, extends {}", count, parent),
+                            None => t!("{} lines", count),
+                        }
+                    }
+                    Err(e) => t!("unreadable: {}", e),
+                };
+                Some((name, detail))
+            })
+            .collect(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(t!("could not read '{}': {}", dir.display(), e).into()),
+    };
+    if found.is_empty() {
+        println!("📭 {}", t!("No templates in {}", dir.display()));
+        return Ok(());
+    }
+    found.sort();
+    println!("🧩 {}", t!("{} template(s) in {}:", found.len(), dir.display()));
+    let width = found.iter().map(|(name, _)| name.chars().count()).max().unwrap_or(0);
+    for (name, detail) in found {
+        println!("   {:<width$}  {}", name, detail, width = width);
+    }
+    Ok(())
+}
+
+pub fn delete(name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let path = store_path(name)?;
+    match fs::remove_file(&path) {
+        Ok(()) => {
+            println!("🗑️ {}", t!("Deleted template '{}'", name));
+            Ok(())
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Err(t!("there is no template '{}' (see `mks template list`)", name).into()),
+        Err(e) => Err(t!("could not delete '{}': {}", path.display(), e).into()),
+    }
+}
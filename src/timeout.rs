@@ -0,0 +1,137 @@
+// File: src\timeout.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: `--timeout 60s` - stop starting operations after a time limit
+// License: MIT
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Mutex, PoisonError,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::config::Config;
+
+// What `mks` exits with when the limit was reached, as GNU `timeout` does
+pub const EXIT_CODE: i32 = 124;
+
+// How long one operation that is already running may take past the limit
+// before the process gives up on it (a hung network mount)
+const GRACE: Duration = Duration::from_secs(10);
+
+// Filesystem operations in progress (several with `--jobs`)
+static BUSY: AtomicUsize = AtomicUsize::new(0);
+
+// "90", "90s", "1500ms", "2m", "1h"
+pub fn parse(text: &str) -> Option<Duration> {
+    let text = text.trim();
+    let split = text.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let number: f64 = number.parse().ok().filter(|n: &f64| *n > 0.0 && n.is_finite())?;
+    let secs = match unit.trim() {
+        "" | "s" => number,
+        "ms" => number / 1000.0,
+        "m" => number * 60.0,
+        "h" => number * 3600.0,
+        _ => return None,
+    };
+    Some(Duration::from_secs_f64(secs))
+}
+
+pub fn shown(limit: Duration) -> String {
+    format!("{}s", limit.as_secs_f64())
+}
+
+pub struct Deadline {
+    pub limit: Duration,
+    // Starts with the first operation, so prompts and clipboard reads
+    // before it do not count
+    start: Mutex<Option<Instant>>,
+    // Off in the watch modes, which must outlive a hung pass
+    watchdog: AtomicBool,
+}
+
+// `--timeout` for this run, else `timeout` from the config
+pub fn from_args(flag: Option<&str>, config: &Config) -> Result<Option<Deadline>, Box<dyn std::error::Error>> {
+    let (value, origin) = match flag {
+        Some(value) => (value, "--timeout"),
+        None => match &config.timeout {
+            Some(value) => (value.as_str(), "timeout"),
+            None => return Ok(None),
+        },
+    };
+    let limit = parse(value).ok_or_else(|| t!("{} expects a duration such as 90s, 5m or 1500ms, got '{}'", origin, value))?;
+    Ok(Some(Deadline { limit, start: Mutex::new(None), watchdog: AtomicBool::new(true) }))
+}
+
+impl Deadline {
+    // Whether the limit has passed; the first call starts the clock and a
+    // watchdog for operations that never return
+    pub fn expired(&self) -> bool {
+        let mut start = self.start.lock().unwrap_or_else(PoisonError::into_inner);
+        let start = *start.get_or_insert_with(|| {
+            let limit = self.limit;
+            if !self.watchdog.load(Ordering::SeqCst) {
+                return Instant::now();
+            }
+            thread::spawn(move || {
+                thread::sleep(limit + GRACE);
+                if BUSY.load(Ordering::SeqCst) > 0 {
+                    eprintln!(
+                        "❌ {}",
+                        t!("An operation was still running {} after --timeout {}; giving up", shown(GRACE), shown(limit))
+                    );
+                    std::process::exit(EXIT_CODE);
+                }
+            });
+            Instant::now()
+        });
+        start.elapsed() >= self.limit
+    }
+
+    // `watch`, `watch-dir` and `clipboard --watch` apply one pass after
+    // another: each pass gets the whole limit, and a pass that overruns it
+    // is reported rather than ending the process
+    pub fn next_pass(&self) {
+        self.watchdog.store(false, Ordering::SeqCst);
+        *self.start.lock().unwrap_or_else(PoisonError::into_inner) = None;
+    }
+}
+
+// Runs one operation, marked as in progress for the watchdog
+pub fn guarded<T>(operation: impl FnOnce() -> T) -> T {
+    BUSY.fetch_add(1, Ordering::SeqCst);
+    let result = operation();
+    BUSY.fetch_sub(1, Ordering::SeqCst);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn durations_take_a_unit() {
+        assert_eq!(parse("90"), Some(Duration::from_secs(90)));
+        assert_eq!(parse("1500ms"), Some(Duration::from_millis(1500)));
+        assert_eq!(parse("2m"), Some(Duration::from_secs(120)));
+        assert_eq!(parse("0"), None);
+        assert_eq!(parse("5d"), None);
+    }
+
+    #[test]
+    fn each_pass_gets_the_whole_limit() {
+        let deadline = from_args(Some("50ms"), &Config::default()).unwrap().unwrap();
+        deadline.next_pass();
+        assert!(!deadline.expired());
+        thread::sleep(Duration::from_millis(60));
+        assert!(deadline.expired());
+
+        deadline.next_pass();
+        assert!(!deadline.expired());
+        assert!(!deadline.watchdog.load(Ordering::SeqCst));
+    }
+}
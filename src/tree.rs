@@ -0,0 +1,65 @@
+// File: src\tree.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: Arena of parsed nodes linked by parent/child ids
+// License: MIT
+
+use crate::NodeKind;
+
+pub type NodeId = usize;
+
+pub struct Node {
+    // A single name; `a & b` lines become sibling nodes
+    pub name: String,
+    pub kind: NodeKind,
+    // 0-based input line
+    pub line: usize,
+    pub parent: Option<NodeId>,
+    pub children: Vec<NodeId>,
+}
+
+// Every node of one input, in document order. Ids are indexes into `nodes`
+// and never change once handed out.
+#[derive(Default)]
+pub struct Tree {
+    nodes: Vec<Node>,
+}
+
+impl Tree {
+    pub fn add(&mut self, parent: Option<NodeId>, name: &str, kind: NodeKind, line: usize) -> NodeId {
+        let id = self.nodes.len();
+        self.nodes.push(Node { name: name.to_string(), kind, line, parent, children: Vec::new() });
+        if let Some(parent) = parent {
+            self.nodes[parent].children.push(id);
+        }
+        id
+    }
+
+    pub fn node(&self, id: NodeId) -> &Node {
+        &self.nodes[id]
+    }
+
+    // Ids from the root down to `id` itself
+    fn lineage(&self, id: NodeId) -> Vec<NodeId> {
+        let mut ids = vec![id];
+        while let Some(parent) = self.nodes[ids[ids.len() - 1]].parent {
+            ids.push(parent);
+        }
+        ids.reverse();
+        ids
+    }
+
+    // Number of ancestors; roots are at depth 0
+    pub fn depth(&self, id: NodeId) -> usize {
+        self.lineage(id).len() - 1
+    }
+
+    // `root/dir/name`
+    pub fn path(&self, id: NodeId) -> String {
+        self.lineage(id)
+            .into_iter()
+            .map(|i| self.nodes[i].name.as_str())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
@@ -0,0 +1,325 @@
+// File: src\validate.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: File name rules per target OS, and why a name breaks them
+// License: MIT
+
+use std::fmt;
+
+use crate::{escape, glyphs};
+
+// Longest name, in bytes, that the common filesystems accept
+const MAX_NAME_BYTES: usize = 255;
+
+// Device names Windows reserves, with or without an extension ("con.txt")
+const RESERVED: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL",
+    "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8", "COM9",
+    "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+const WINDOWS_FORBIDDEN: &str = r#"<>:"/\|?*"#;
+
+// Whose rules a name has to follow
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Target {
+    Windows,
+    Posix,
+    // Valid on both, so a tree creates the same everywhere (the default)
+    Portable,
+}
+
+impl Target {
+    // The OS `mks` runs on, used by `--raw-names`
+    pub fn host() -> Self {
+        if cfg!(windows) {
+            Target::Windows
+        } else {
+            Target::Posix
+        }
+    }
+
+    fn windows(self) -> bool {
+        matches!(self, Target::Windows | Target::Portable)
+    }
+}
+
+// Why a name cannot be created. Positions count characters from 1.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Invalid {
+    Empty,
+    TooLong(usize),
+    // "." and ".." name the folders themselves
+    DotName,
+    // Nothing but tree glyphs, as left over from a drawing
+    OnlyGlyphs,
+    Reserved(String),
+    BadChar { ch: char, position: usize },
+    Trailing(char),
+}
+
+impl fmt::Display for Invalid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self {
+            Invalid::Empty => t!("the name is empty").to_string(),
+            Invalid::TooLong(bytes) => t!("{} bytes is over the {}-byte limit", bytes, MAX_NAME_BYTES),
+            Invalid::DotName => t!("'.' and '..' cannot be created").to_string(),
+            Invalid::OnlyGlyphs => t!("only tree drawing characters").to_string(),
+            Invalid::Reserved(word) => t!("'{}' is a reserved name on Windows", word),
+            Invalid::BadChar { ch, position } if ch.is_control() => {
+                t!("control character U+{} at position {}", format!("{:04X}", *ch as u32), position)
+            }
+            Invalid::BadChar { ch, position } => t!("'{}' at position {} is not allowed", ch, position),
+            Invalid::Trailing(' ') => t!("ends with a space").to_string(),
+            Invalid::Trailing(_) => t!("ends with a dot").to_string(),
+        };
+        f.write_str(&text)
+    }
+}
+
+// The first rule of `target` that `name` breaks. Only ASCII case folding is
+// used, so the result does not depend on the locale.
+pub fn check(name: &str, target: Target) -> Result<(), Invalid> {
+    if name.is_empty() {
+        return Err(Invalid::Empty);
+    }
+    if name.len() > MAX_NAME_BYTES {
+        return Err(Invalid::TooLong(name.len()));
+    }
+    if name == "." || name == ".." {
+        return Err(Invalid::DotName);
+    }
+    if let Some((position, ch)) = name.chars().enumerate().find(|&(_, c)| c == '/' || c == '\0') {
+        return Err(Invalid::BadChar { ch, position: position + 1 });
+    }
+    if !target.windows() {
+        return Ok(());
+    }
+
+    let trimmed = name.trim();
+    let base = trimmed.split('.').next().unwrap_or(trimmed).trim_end();
+    if let Some(word) = RESERVED.iter().find(|word| base.eq_ignore_ascii_case(word)) {
+        return Err(Invalid::Reserved(word.to_string()));
+    }
+    let bad = |c: char| WINDOWS_FORBIDDEN.contains(c) || (c as u32) < 0x20;
+    if let Some((position, ch)) = name.chars().enumerate().find(|&(_, c)| bad(c)) {
+        return Err(Invalid::BadChar { ch, position: position + 1 });
+    }
+    match name.chars().last() {
+        Some(c @ (' ' | '.')) => Err(Invalid::Trailing(c)),
+        _ => Ok(()),
+    }
+}
+
+// What every tree name is held to unless `--raw-names` is given: the rules
+// of both OSes, where `\xHH` byte escapes count as the byte they stand for
+// and a name made of tree glyphs alone is a drawing artifact
+pub fn portable(name: &str) -> Result<(), Invalid> {
+    let decoded;
+    let name = if escape::has_raw_bytes(name) {
+        decoded = escape::os_path(name).to_string_lossy().into_owned();
+        decoded.as_str()
+    } else {
+        name
+    };
+    if name.trim().is_empty() {
+        return Err(Invalid::Empty);
+    }
+    if name.trim().chars().all(glyphs::is_indent) {
+        return Err(Invalid::OnlyGlyphs);
+    }
+    check(name, Target::Portable)
+}
+
+// `src/utils/helpers.rs` written on one tree line: every part has to be a
+// portable name. Returns the first part that is not, and why.
+pub fn portable_path(path: &str) -> Result<(), (&str, Invalid)> {
+    path.split('/').try_for_each(|part| portable(part).map_err(|why| (part, why)))
+}
+
+// `--sanitize`: the nearest portable name, fixing each reason `portable`
+// gives in turn. A name that is empty, `.`/`..` or only glyphs has no fix.
+pub fn sanitize(name: &str) -> Option<String> {
+    // Byte escapes are checked as the bytes they stand for, so the positions
+    // would not point into `name`; such names are kept or refused as they are
+    if escape::has_raw_bytes(name) {
+        return portable(name).ok().map(|_| name.to_string());
+    }
+    let mut name = name.trim().to_string();
+    loop {
+        match portable(&name) {
+            Ok(()) => return Some(name),
+            Err(Invalid::BadChar { position, .. }) => {
+                name = name.chars().enumerate().map(|(i, c)| if i + 1 == position { '_' } else { c }).collect();
+            }
+            Err(Invalid::Trailing(_)) => name.truncate(name.trim_end_matches([' ', '.']).len()),
+            // `con.txt` -> `con_.txt`; the name is trimmed, so the word starts it
+            Err(Invalid::Reserved(word)) => name.insert(word.len(), '_'),
+            Err(Invalid::TooLong(_)) => {
+                let mut end = MAX_NAME_BYTES;
+                while !name.is_char_boundary(end) {
+                    end -= 1;
+                }
+                name.truncate(end);
+            }
+            Err(Invalid::Empty | Invalid::DotName | Invalid::OnlyGlyphs) => return None,
+        }
+    }
+}
+
+// `sanitize` for every part of an inline path
+pub fn sanitize_path(path: &str) -> Option<String> {
+    let parts: Option<Vec<String>> = path.split('/').map(sanitize).collect();
+    parts.map(|parts| parts.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    const TARGETS: [Target; 3] = [Target::Windows, Target::Posix, Target::Portable];
+
+    // Everything Windows refuses inside a name: its specials and the controls
+    fn windows_bad() -> Vec<char> {
+        WINDOWS_FORBIDDEN.chars().chain((0u8..0x20).map(char::from)).collect()
+    }
+
+    // `word` with the case of each letter taken from the bits of `mask`
+    fn mixed_case(word: &str, mask: u32) -> String {
+        word.chars()
+            .enumerate()
+            .map(|(i, c)| if mask >> i & 1 == 1 { c.to_ascii_lowercase() } else { c })
+            .collect()
+    }
+
+    fn is_reserved(name: &str) -> bool {
+        RESERVED.iter().any(|word| name.eq_ignore_ascii_case(word))
+    }
+
+    proptest! {
+        // Windows
+
+        #[test]
+        fn windows_bad_char_carries_its_position(
+            prefix in "[a-zA-Z0-9é文 _-]{0,20}",
+            bad in prop::sample::select(windows_bad()),
+            suffix in "[a-zA-Z0-9é文 ._-]{0,20}",
+        ) {
+            // No '.' before the bad character, so the part before an
+            // extension never spells a reserved word (checked first)
+            let name = format!("{}{}{}", prefix, bad, suffix);
+            let position = prefix.chars().count() + 1;
+            prop_assert_eq!(check(&name, Target::Windows), Err(Invalid::BadChar { ch: bad, position }));
+        }
+
+        #[test]
+        fn windows_rejects_reserved_words_in_any_case(
+            word in prop::sample::select(RESERVED),
+            mask in any::<u32>(),
+            extension in "(\\.[a-z0-9]{1,5})?",
+        ) {
+            let name = format!("{}{}", mixed_case(word, mask), extension);
+            prop_assert_eq!(check(&name, Target::Windows), Err(Invalid::Reserved(word.to_string())));
+        }
+
+        #[test]
+        fn windows_rejects_trailing_dots_and_spaces(stem in "[a-zA-Z0-9_-]{1,20}", last in prop::sample::select(vec!['.', ' '])) {
+            prop_assume!(!is_reserved(&stem));
+            prop_assert_eq!(check(&format!("{}{}", stem, last), Target::Windows), Err(Invalid::Trailing(last)));
+        }
+
+        #[test]
+        fn windows_rules_are_the_portable_ones_for_plain_names(name in "[a-zA-Z0-9 ._<>:|?*-]{1,30}") {
+            prop_assert_eq!(check(&name, Target::Windows), check(&name, Target::Portable));
+        }
+
+        // Posix
+
+        #[test]
+        fn posix_only_refuses_slash_and_nul(
+            prefix in "[a-zA-Z0-9é文 _-]{0,20}",
+            bad in prop::sample::select(windows_bad()),
+            suffix in "[a-zA-Z0-9é文 ._-]{0,20}",
+        ) {
+            let name = format!("{}{}{}", prefix, bad, suffix);
+            let expected = match bad {
+                '/' | '\0' => Err(Invalid::BadChar { ch: bad, position: prefix.chars().count() + 1 }),
+                _ => Ok(()),
+            };
+            prop_assert_eq!(check(&name, Target::Posix), expected);
+        }
+
+        #[test]
+        fn posix_allows_reserved_words_and_trailing_dots(word in prop::sample::select(RESERVED), last in prop::sample::select(vec!['.', ' '])) {
+            prop_assert_eq!(check(word, Target::Posix), Ok(()));
+            prop_assert_eq!(check(&format!("x{}", last), Target::Posix), Ok(()));
+        }
+
+        #[test]
+        fn posix_accepts_whatever_windows_accepts(name in "\\PC{1,30}") {
+            if check(&name, Target::Windows).is_ok() {
+                prop_assert_eq!(check(&name, Target::Posix), Ok(()));
+            }
+        }
+
+        // Portable
+
+        #[test]
+        fn portable_bad_char_carries_its_position(
+            prefix in "[a-zA-Z0-9é文 _-]{0,20}",
+            bad in prop::sample::select(windows_bad()),
+            suffix in "[a-zA-Z0-9é文 ._-]{0,20}",
+        ) {
+            let name = format!("x{}{}{}", prefix, bad, suffix);
+            let position = prefix.chars().count() + 2;
+            prop_assert_eq!(portable(&name), Err(Invalid::BadChar { ch: bad, position }));
+        }
+
+        #[test]
+        fn portable_rejects_reserved_words_and_trailing_dots(
+            word in prop::sample::select(RESERVED),
+            mask in any::<u32>(),
+            stem in "[a-zA-Z0-9_-]{1,20}",
+            last in prop::sample::select(vec!['.', ' ']),
+        ) {
+            prop_assert_eq!(portable(&mixed_case(word, mask)), Err(Invalid::Reserved(word.to_string())));
+            prop_assume!(!is_reserved(&stem));
+            prop_assert_eq!(portable(&format!("{}{}", stem, last)), Err(Invalid::Trailing(last)));
+        }
+
+        #[test]
+        fn portable_names_are_valid_for_every_target(name in "\\PC{0,30}|[a-zA-Z0-9 ._<>:|?*\\\\-]{0,30}") {
+            // `\xHH` stands for a byte; `check` sees the names as the OS would
+            prop_assume!(!escape::has_raw_bytes(&name));
+            if portable(&name).is_ok() {
+                for target in TARGETS {
+                    prop_assert_eq!(check(&name, target), Ok(()), "{:?}", target);
+                }
+            }
+        }
+
+        #[test]
+        fn too_long_names_report_their_length(name in "[a-zé文]{100,300}") {
+            prop_assume!(name.len() > MAX_NAME_BYTES);
+            for target in TARGETS {
+                prop_assert_eq!(check(&name, target), Err(Invalid::TooLong(name.len())));
+            }
+        }
+
+        #[test]
+        fn sanitized_names_are_portable(name in "\\PC{1,30}|[a-zA-Z0-9 ._<>:|?*\\\\-]{1,30}") {
+            prop_assume!(!escape::has_raw_bytes(&name));
+            match sanitize(&name) {
+                Some(fixed) => {
+                    prop_assert_eq!(portable(&fixed), Ok(()));
+                    if portable(&name).is_ok() {
+                        prop_assert_eq!(fixed, name.trim());
+                    }
+                }
+                None => prop_assert!(portable(&name).is_err()),
+            }
+        }
+    }
+}
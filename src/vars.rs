@@ -0,0 +1,103 @@
+// File: src\vars.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: `{{name}}` template variables and their values (`--vars vars.toml`, `--var k=v`)
+// License: MIT
+
+use std::{collections::BTreeMap, fs};
+
+use crate::{project, validate, PlannedEntry};
+
+pub type Vars = BTreeMap<String, String>;
+
+// `name = "value"` pairs; numbers and booleans are taken as written
+pub fn load(path: &str) -> Result<Vars, Box<dyn std::error::Error>> {
+    let text = fs::read_to_string(path).map_err(|e| t!("{}: {}", path, e))?;
+    let table: toml::Table = toml::from_str(&text).map_err(|e| t!("{}: {}", path, e))?;
+    let mut vars = Vars::new();
+    for (key, value) in table {
+        let value = match value {
+            toml::Value::String(s) => s,
+            toml::Value::Integer(_) | toml::Value::Float(_) | toml::Value::Boolean(_) => value.to_string(),
+            _ => return Err(t!("{}: '{}' must be a string, number or boolean", path, key).into()),
+        };
+        vars.insert(key, value);
+    }
+    Ok(vars)
+}
+
+// Names of the `{{name}}` placeholders in `text`, in order of appearance
+pub fn placeholders(text: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let name = rest[start + 2..start + 2 + len].trim();
+        if !name.is_empty() {
+            names.push(name);
+        }
+        rest = &rest[start + 2 + len + 2..];
+    }
+    names
+}
+
+// Replace every placeholder that has a value; unknown ones are left as written
+pub fn expand(text: &str, vars: &Vars) -> String {
+    let mut out = text.to_string();
+    for name in placeholders(text) {
+        if let Some(value) = vars.get(name) {
+            out = out.replace(&format!("{{{{{}}}}}", name), value);
+        }
+    }
+    out
+}
+
+// `--vars vars.toml`, then every `--var name=value` on top of it
+pub fn from_args(file: Option<&str>, pairs: &[String]) -> Result<Vars, Box<dyn std::error::Error>> {
+    let mut vars = match file {
+        Some(path) => load(path)?,
+        None => Vars::new(),
+    };
+    for pair in pairs {
+        match pair.split_once('=') {
+            Some((name, value)) if !name.trim().is_empty() => {
+                vars.insert(name.trim().to_string(), value.to_string());
+            }
+            _ => return Err(t!("--var expects name=value, got '{}'", pair).into()),
+        }
+    }
+    Ok(vars)
+}
+
+// Fill the variables into every path (and file contents) before anything
+// looks at the names. Each name is checked again once filled in; a path
+// placeholder without a value is an error. `{{project}}` is left for the
+// project-name step, which also takes `--var project=...`.
+pub fn apply(plan: &mut [PlannedEntry], vars: &Vars) -> Result<(), Box<dyn std::error::Error>> {
+    let mut values = vars.clone();
+    values.remove("project");
+    for entry in plan.iter_mut() {
+        if !entry.path.contains("{{") && entry.contents.as_deref().is_none_or(|c| !c.contains("{{")) {
+            continue;
+        }
+        let mut parts = Vec::new();
+        for part in entry.path.split('/') {
+            let filled = expand(part, &values);
+            if let Some(name) = placeholders(&filled).into_iter().find(|&name| name != "project") {
+                return Err(t!("line {}: no value for {{{}}}; pass --var {}=... or --vars <file>", entry.line + 1, name, name).into());
+            }
+            let checked = filled.replace(project::PLACEHOLDER, "project");
+            if filled != part {
+                if let Err(why) = validate::portable(&checked) {
+                    return Err(t!("line {}: '{}' is not a valid name after filling in variables: {}", entry.line + 1, filled, why).into());
+                }
+            }
+            parts.push(filled);
+        }
+        entry.path = parts.join("/");
+        entry.contents = entry.contents.as_deref().map(|c| expand(c, &values));
+    }
+    Ok(())
+}
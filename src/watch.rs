@@ -0,0 +1,101 @@
+// File: src\watch.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: `mks watch` - re-apply a tree file every time it is saved
+// License: MIT
+
+use std::{
+    env,
+    path::{Path, PathBuf},
+    sync::mpsc,
+    time::Duration,
+};
+
+use notify::{RecursiveMode, Watcher};
+
+use crate::{
+    create_structure, escape, is_valid_structure, make_dir,
+    metrics::Exporter,
+    plan_from_input, project, read_input, structured, vars,
+    watch_dir::{count, log},
+    Options, PlannedEntry,
+};
+
+// The tree as it is now; None while it holds nothing to create, as in the
+// middle of an editor's save or a half-typed first line
+fn load(tree: &Path, target: &str, opts: &Options) -> Result<Option<Vec<PlannedEntry>>, Box<dyn std::error::Error>> {
+    let file = tree.to_string_lossy();
+    if structured::format_for(opts.format.as_deref(), Some(&file))? == structured::Format::Text {
+        let (lines, _) = read_input(Some(&file), None, opts.fence, opts.download)?;
+        if !is_valid_structure(&lines, &opts.parse) {
+            return Ok(None);
+        }
+    }
+    let (mut plan, _) = plan_from_input(Some(&file), opts)?;
+    vars::apply(&mut plan, &opts.vars)?;
+    project::inject(&mut plan, Some(target), opts.vars.get("project").map(String::as_str))?;
+    Ok(Some(plan))
+}
+
+// Create the entries that are not on disk yet; existing ones are left alone
+fn apply(tree: &Path, target: &str, opts: &Options, exporter: Option<&Exporter>) {
+    let plan = match load(tree, target, opts) {
+        Ok(Some(plan)) => plan,
+        Ok(None) => return log(format!("⏳ {}", t!("{} has no entries yet", tree.display()))),
+        Err(e) => return log(format!("❌ {}", t!("Error: {}", e))),
+    };
+    if let Some(exporter) = exporter {
+        exporter.set_entries(plan.len());
+    }
+    let new: Vec<PlannedEntry> = plan
+        .into_iter()
+        .filter(|entry| std::fs::symlink_metadata(escape::long_path(&escape::os_path(&entry.path))).is_err())
+        .collect();
+    if new.is_empty() {
+        return log(format!("✅ {}", t!("nothing new")));
+    }
+    for entry in &new {
+        log(format!("➕ {}", t!("creating {}", entry.path)));
+    }
+    if let Some(deadline) = &opts.deadline {
+        deadline.next_pass();
+    }
+    let summary = create_structure(&new, Path::new(""), opts);
+    for (path, reason) in summary.problems() {
+        log(format!("⚠️ {}", t!("{}: {}", path, reason)));
+    }
+    count(exporter, &summary);
+}
+
+// Editors save by writing a temporary file and renaming it over the tree, so
+// the folder holding the tree is watched and its events filtered by name
+fn touches(event: &notify::Event, tree: &Path) -> bool {
+    event.paths.iter().any(|path| path.file_name() == tree.file_name())
+}
+
+pub fn run(tree: &str, target: &str, opts: &Options, exporter: Option<Exporter>) -> Result<(), Box<dyn std::error::Error>> {
+    let tree: PathBuf = std::path::absolute(tree)?;
+    let folder = tree.parent().map(Path::to_path_buf).unwrap_or_else(|| PathBuf::from("."));
+    make_dir(Path::new(target), opts.durable)?;
+    env::set_current_dir(target)?;
+
+    log(format!("👀 {}", t!("Watching {}, creating into {}; Ctrl-C to stop", tree.display(), target)));
+    apply(&tree, target, opts, exporter.as_ref());
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    watcher.watch(&folder, RecursiveMode::NonRecursive)?;
+
+    while let Ok(event) = rx.recv() {
+        match event {
+            Err(e) => log(format!("⚠️ {}", t!("watch error: {}", e))),
+            Ok(event) if touches(&event, &tree) && !event.kind.is_access() => {
+                // One save is often several writes; settle before reading
+                while rx.recv_timeout(Duration::from_millis(150)).is_ok() {}
+                apply(&tree, target, opts, exporter.as_ref());
+            }
+            Ok(_) => {}
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,79 @@
+// File: src\watch_dir.rs
+// Author: Hadi Cahyadi <cumulus13@gmail.com>
+// Date: 2026-10-16
+// Description: `mks watch-dir` - keep a directory conforming to a tree file
+// License: MIT
+
+use std::{
+    env,
+    path::Path,
+    sync::mpsc,
+    time::Duration,
+};
+
+use chrono::Local;
+use notify::{RecursiveMode, Watcher};
+
+use crate::{create_structure, escape, make_dir, metrics::Exporter, project, Options, PlannedEntry, Summary};
+
+pub fn log(message: impl std::fmt::Display) {
+    println!("[{}] {}", Local::now().format("%H:%M:%S"), message);
+}
+
+// Recreate whatever is missing; existing files are never rewritten here
+fn heal(plan: &[PlannedEntry], opts: &Options, exporter: Option<&Exporter>) {
+    let missing: Vec<PlannedEntry> = plan
+        .iter()
+        .filter(|entry| std::fs::symlink_metadata(escape::long_path(&escape::os_path(&entry.path))).is_err())
+        .cloned()
+        .collect();
+    if missing.is_empty() {
+        return;
+    }
+    for entry in &missing {
+        log(format!("♻️ {}", t!("recreating {}", entry.path)));
+    }
+    if let Some(deadline) = &opts.deadline {
+        deadline.next_pass();
+    }
+    let summary = create_structure(&missing, Path::new(""), opts);
+    for (path, reason) in summary.problems() {
+        log(format!("⚠️ {}", t!("{}: {}", path, reason)));
+    }
+    count(exporter, &summary);
+}
+
+// Add a pass to the metrics, if they are kept
+pub fn count(exporter: Option<&Exporter>, summary: &Summary) {
+    if let Some(exporter) = exporter {
+        if let Err(e) = exporter.record(summary.created().len(), summary.failed()) {
+            log(format!("⚠️ {}", t!("could not write metrics: {}", e)));
+        }
+    }
+}
+
+pub fn run(mut plan: Vec<PlannedEntry>, target: &str, opts: &Options, exporter: Option<Exporter>) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(project) = project::inject(&mut plan, Some(target), opts.vars.get("project").map(String::as_str))? {
+        println!("🏷️ {}", t!("Project name: {}", project));
+    }
+    make_dir(Path::new(target), opts.durable)?;
+    env::set_current_dir(target)?;
+
+    log(format!("👀 {}", t!("Watching {} ({} entries); Ctrl-C to stop", target, plan.len())));
+    heal(&plan, opts, exporter.as_ref());
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    watcher.watch(Path::new("."), RecursiveMode::Recursive)?;
+
+    while let Ok(event) = rx.recv() {
+        if let Err(e) = event {
+            log(format!("⚠️ {}", t!("watch error: {}", e)));
+            continue;
+        }
+        // A `rm -r` arrives as a burst of events; settle before checking
+        while rx.recv_timeout(Duration::from_millis(150)).is_ok() {}
+        heal(&plan, opts, exporter.as_ref());
+    }
+    Ok(())
+}